@@ -1,6 +1,6 @@
 use std::{collections::HashSet, convert::Infallible, ops::Range};
 
-use proliferatr::InputGenerator;
+use proliferatr::{generic::Blocklist, InputGenerator};
 use rand::{
     distributions::{Alphanumeric, Uniform},
     prelude::Distribution,
@@ -34,7 +34,8 @@ const KEYWORD_PROBABILITY: f64 = 0.7;
 
 /// The strategy will be to generate random alphanumeric strings, injecting the
 /// special keywords at a random number of random locations. The real inputs
-/// have 1000 lines, we're going to gen at least that many.
+/// have 1000 lines, we're going to gen at least that many. Lines that happen to
+/// spell out something rude are thrown away and generated again.
 #[derive(Debug, Default, Clone, Copy)]
 pub struct Day01;
 
@@ -60,9 +61,10 @@ impl InputGenerator for Day01 {
         let num_lines = rng.gen_range(NUM_LINES);
         let len_dist = Uniform::from(BASE_LINE_LENGTH);
 
+        let blocklist = Blocklist::embedded();
         let mut out = Vec::with_capacity(num_lines);
 
-        for _ in 0..num_lines {
+        while out.len() < num_lines {
             let len = len_dist.sample(rng);
 
             // decide if we want to insert any keywords
@@ -104,7 +106,10 @@ impl InputGenerator for Day01 {
                 s.push(char::from_digit(rng.gen_range(1..10), 10).unwrap_or('8'));
             }
 
-            out.push(s.to_lowercase());
+            let s = s.to_lowercase();
+            if blocklist.is_clean(&s) {
+                out.push(s);
+            }
         }
 
         Ok(out)
@@ -1,7 +1,7 @@
-use std::{collections::HashSet, convert::Infallible, fmt::Display};
+use std::fmt::Display;
 
 use itertools::Itertools;
-use proliferatr::InputGenerator;
+use proliferatr::{graph::CycleNetwork, InputGenerator};
 use rand::{seq::SliceRandom, Rng};
 
 use super::Day;
@@ -19,6 +19,7 @@ const INST_PRIMES: &[usize] = &[
 // anything goes but A and Z
 const P_CONTINUE_RUN: f64 = 0.70;
 const MAX_RUN: usize = 4;
+const KEY_LENGTH: usize = 4;
 const ALPHA_CHARS: &[u8] = b"BCDFGHJKLMNPQRSTVWX";
 
 /// So the real inputs are very special, in that they describe six separate
@@ -61,7 +62,7 @@ impl Day for Day08 {
 }
 
 impl InputGenerator for Day08 {
-    type GeneratorError = Infallible;
+    type GeneratorError = anyhow::Error;
     type Output = (String, Vec<Node>);
 
     fn gen_input<R: Rng + Clone + ?Sized>(
@@ -74,9 +75,6 @@ impl InputGenerator for Day08 {
             .copied()
             .collect::<Vec<_>>();
         let inst_length = INST_PRIMES.choose(rng).copied().unwrap();
-        let mut seen: HashSet<String> =
-            HashSet::with_capacity(lengths.iter().sum::<usize>() * 2 + NUM_LOOPS);
-        let mut nodes = Vec::with_capacity(lengths.iter().sum::<usize>() * 2 + NUM_LOOPS);
         let mut instructions = String::with_capacity(inst_length);
 
         // we have to start with 'L' so we don't accidentally create another run
@@ -85,7 +83,7 @@ impl InputGenerator for Day08 {
         let mut run = 1;
         instructions.push(prev);
 
-        for _ in 1..(inst_length - 5) {
+        for _ in 1..(inst_length - KEY_LENGTH - 1) {
             if prev == 'R' && run >= 3 {
                 // we have to pick an 'L'
                 instructions.push('L');
@@ -106,45 +104,28 @@ impl InputGenerator for Day08 {
         // the last 5 chars are fixed, because we need to make sure we break a
         // potential existing run of 'R's and then include 4 'R's
         instructions.push('L');
-        instructions.push('R');
-        instructions.push('R');
-        instructions.push('R');
-        instructions.push('R');
-
-        // now generate the loops
-
-        // the first loop is special because it'll contain AAA and ZZZ.
-        seen.insert("AAA".into());
-        seen.insert("ZZZ".into());
-        nodes.extend(make_loop(rng, lengths[0], "AAA", "ZZZ", &mut seen));
-
-        #[allow(clippy::needless_range_loop)]
-        for i in 1..lengths.len() {
-            let start = loop {
-                let mut s = String::with_capacity(3);
-                s.push(ALPHA_CHARS[rng.gen_range(0..ALPHA_CHARS.len())] as char);
-                s.push(ALPHA_CHARS[rng.gen_range(0..ALPHA_CHARS.len())] as char);
-                s.push('A');
-                if !seen.contains(&s) {
-                    seen.insert(s.clone());
-                    break s;
-                }
-            };
-            let end = loop {
-                let mut s = String::with_capacity(3);
-                s.push(ALPHA_CHARS[rng.gen_range(0..ALPHA_CHARS.len())] as char);
-                s.push(ALPHA_CHARS[rng.gen_range(0..ALPHA_CHARS.len())] as char);
-                s.push('Z');
-                if !seen.contains(&s) {
-                    seen.insert(s.clone());
-                    break s;
-                }
-            };
-            nodes.extend(make_loop(rng, lengths[i], &start, &end, &mut seen));
-        }
-
-        // randomize the order of all the nodes to obscrure the implementation
-        nodes.shuffle(rng);
+        instructions.push_str(&"R".repeat(KEY_LENGTH));
+
+        // now generate the loops. The first loop is special because it'll
+        // contain AAA and ZZZ.
+        let network = CycleNetwork::builder()
+            .instructions(instructions.clone())
+            .cycles(lengths)
+            .key_length(KEY_LENGTH)
+            .charset(ALPHA_CHARS)
+            .named_cycle(("AAA".into(), "ZZZ".into()))
+            .build()?
+            .generate(rng)?;
+
+        let nodes = network
+            .nodes
+            .into_iter()
+            .map(|n| Node {
+                name: n.name,
+                left: n.left,
+                right: n.right,
+            })
+            .collect();
 
         Ok((instructions, nodes))
     }
@@ -162,132 +143,3 @@ impl Display for Node {
         write!(f, "{} = ({}, {})", &self.name, &self.left, &self.right)
     }
 }
-
-fn make_loop<R: Rng + Clone + ?Sized>(
-    rng: &mut R,
-    len: usize,
-    start: &str,
-    end: &str,
-    seen: &mut HashSet<String>,
-) -> Vec<Node> {
-    let mut nodes = Vec::with_capacity(len * 2 + 1);
-
-    // the first node is outside of the loop
-    nodes.push(Node {
-        name: start.to_string(),
-        left: make_name(rng, seen),
-        right: make_name(rng, seen),
-    });
-
-    let mut cur = vec![nodes[0].left.clone(), nodes[0].right.clone()];
-
-    // last 4 nodes are special
-    for _ in 0..(len - 4) {
-        let left_child_name = make_name(rng, seen);
-        let right_child_name = make_name(rng, seen);
-
-        let right = Node {
-            name: cur.pop().unwrap(),
-            left: left_child_name.clone(),
-            right: right_child_name.clone(),
-        };
-
-        let left = Node {
-            name: cur.pop().unwrap(),
-            left: left_child_name.clone(),
-            right: right_child_name.clone(),
-        };
-
-        // yeah, this ordering isn't going to be a problem or anything
-        cur.push(left_child_name);
-        cur.push(right_child_name);
-
-        // this means that nodes[1] is going to be the first left and
-        // nodes[2] is going to be the first right
-        nodes.push(left);
-        nodes.push(right);
-    }
-
-    // shunt
-    for _ in 0..2 {
-        let left_child_name = make_name(rng, seen);
-        let right_child_name = make_name(rng, seen);
-
-        // the right node is normal
-        let right = Node {
-            name: cur.pop().unwrap(),
-            left: left_child_name.clone(),
-            right: right_child_name.clone(),
-        };
-
-        // the left node has both it's left and right pointing to the left
-        let left = Node {
-            name: cur.pop().unwrap(),
-            left: left_child_name.clone(),
-            right: left_child_name.clone(),
-        };
-
-        cur.push(left_child_name);
-        cur.push(right_child_name);
-
-        nodes.push(left);
-        nodes.push(right);
-    }
-
-    // node group of the shunt leading to the end
-    let left_child_name = make_name(rng, seen);
-    let right_child_name = end.to_string();
-
-    let right = Node {
-        name: cur.pop().unwrap(),
-        left: left_child_name.clone(),
-        right: right_child_name.clone(),
-    };
-
-    let left = Node {
-        name: cur.pop().unwrap(),
-        left: left_child_name.clone(),
-        right: left_child_name.clone(),
-    };
-
-    cur.push(left_child_name);
-    cur.push(right_child_name);
-
-    nodes.push(left);
-    nodes.push(right);
-
-    // then the last two nodes. The left is "normal", the right is our special
-    // end node
-    let left_child_name = nodes[1].name.clone();
-    let right_child_name = nodes[2].name.clone();
-
-    let right = Node {
-        name: cur.pop().unwrap(),
-        left: left_child_name.clone(),
-        right: right_child_name.clone(),
-    };
-
-    let left = Node {
-        name: cur.pop().unwrap(),
-        left: left_child_name.clone(),
-        right: right_child_name.clone(),
-    };
-
-    nodes.push(left);
-    nodes.push(right);
-
-    nodes
-}
-
-fn make_name<R: Rng + Clone + ?Sized>(rng: &mut R, seen: &mut HashSet<String>) -> String {
-    loop {
-        let mut s = String::with_capacity(3);
-        s.push(ALPHA_CHARS[rng.gen_range(0..ALPHA_CHARS.len())] as char);
-        s.push(ALPHA_CHARS[rng.gen_range(0..ALPHA_CHARS.len())] as char);
-        s.push(ALPHA_CHARS[rng.gen_range(0..ALPHA_CHARS.len())] as char);
-        if !seen.contains(&s) {
-            seen.insert(s.clone());
-            return s;
-        }
-    }
-}
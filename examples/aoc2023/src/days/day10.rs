@@ -1,8 +1,9 @@
-use itertools::Itertools;
 use proliferatr::{
     bound::Bound2D,
-    direction::Cardinal,
-    path::{ClosedPath, PathMutator, PointPath, UnitSegmentAdder},
+    grid::{CharGrid, Grid},
+    path::{
+        AsciiPipes, ClosedPath, PathRasterizer, PointPath, RandomPathMutator, UnitSegmentAdder,
+    },
     point::Point,
     InputGenerator,
 };
@@ -43,31 +44,26 @@ impl Day for Day10 {
     fn generate<R: Rng + Clone + ?Sized>(
         rng: &mut R,
     ) -> Result<String, <Self as proliferatr::InputGenerator>::GeneratorError> {
-        Ok(Day10
-            .gen_input(rng)?
-            .iter()
-            .map(|r| r.iter().collect::<String>())
-            .join("\n"))
+        Ok(Day10.gen_input(rng)?.to_string())
     }
 }
 
 impl InputGenerator for Day10 {
     type GeneratorError = anyhow::Error;
-    type Output = Vec<Vec<char>>;
+    type Output = CharGrid;
 
     fn gen_input<R: Rng + Clone + ?Sized>(
         &self,
         rng: &mut R,
     ) -> Result<Self::Output, Self::GeneratorError> {
-        let mut grid = vec![vec!['.'; DIMENSION]; DIMENSION];
+        let mut grid = Grid::new(DIMENSION, DIMENSION, '.');
 
         // create the initial square path and translate it to the center of the
         // grid
         let mut path = ClosedPath::rect_path(STARTING_SQUARE_SIDE, STARTING_SQUARE_SIDE)?;
         path.translate(INITIAL_OFFSET);
 
-        let mut segment_adder = UnitSegmentAdder::builder()
-            .rng(rng.clone())
+        let mut segment_adder: UnitSegmentAdder = UnitSegmentAdder::builder()
             .passes(NUM_ALTERATION_PASSES)
             .bounds(BOUNDS)
             .build()?;
@@ -82,47 +78,26 @@ impl InputGenerator for Day10 {
         }
 
         // alter our starting path by addiing random segments
-        segment_adder.mutate(&mut path);
+        segment_adder.mutate_with(&mut path, rng);
 
         // pick a random spot for the S
         let s_idx = rng.gen_range(0..path.len());
 
-        let mut points: Vec<_> = path.points().copied().collect();
-
-        // we're going to extend the closed path, which already has the first
-        // point duplicated onto the end, to have the first _and_ second points
-        // duplicated.
-        points.push(points[1]);
-
-        for (p1, p2, p3) in points.iter().tuple_windows() {
-            // these unwraps should be safe because the points should be
-            // different
-            let d1 = p1.cardinal_to(p2).unwrap();
-            let d2 = p2.cardinal_to(p3).unwrap();
-
-            let ch = match (d1, d2) {
-                (Cardinal::East, Cardinal::East) | (Cardinal::West, Cardinal::West) => '-',
-                (Cardinal::North, Cardinal::North) | (Cardinal::South, Cardinal::South) => '|',
-                (Cardinal::East, Cardinal::South) | (Cardinal::North, Cardinal::West) => '7',
-                (Cardinal::East, Cardinal::North) | (Cardinal::South, Cardinal::West) => 'J',
-                (Cardinal::West, Cardinal::South) | (Cardinal::North, Cardinal::East) => 'F',
-                (Cardinal::West, Cardinal::North) | (Cardinal::South, Cardinal::East) => 'L',
-                _ => unreachable!("Unexpected combo ({:?}, {:?})", d1, d2),
-            };
-
-            grid[DIMENSION - 1 - p2.y as usize][p2.x as usize] = ch;
-        }
+        PathRasterizer::builder()
+            .glyphs(AsciiPipes)
+            .start('S')
+            .start_idx(s_idx)
+            .build()?
+            .draw(&path, &mut grid)?;
 
-        let s = points[s_idx];
-        grid[DIMENSION - 1 - s.y as usize][s.x as usize] = 'S';
+        let s = *path.get(s_idx).unwrap();
 
         // we now want to randomly fill the other characters to disguise the path
-        #[allow(clippy::needless_range_loop)]
-        for r in 0..DIMENSION {
-            for c in 0..DIMENSION {
+        for y in 0..DIMENSION {
+            for x in 0..DIMENSION {
                 let p = Point {
-                    x: c as i64,
-                    y: r as i64,
+                    x: x as i64,
+                    y: y as i64,
                 };
 
                 // don't accidentally create a path leading into the S
@@ -130,8 +105,9 @@ impl InputGenerator for Day10 {
                     continue;
                 }
 
-                if grid[r][c] == '.' {
-                    grid[r][c] = *FILLER_CHARS.choose(rng).unwrap() as char;
+                let cell = &mut grid[DIMENSION - 1 - y][x];
+                if *cell == '.' {
+                    *cell = *FILLER_CHARS.choose(rng).unwrap() as char;
                 }
             }
         }
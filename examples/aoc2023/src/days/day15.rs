@@ -1,8 +1,11 @@
-use std::{collections::HashSet, fmt::Display, ops::Range};
+use std::{
+    fmt::Display,
+    ops::{Range, RangeInclusive},
+};
 
 use itertools::Itertools;
 use proliferatr::{
-    generic::{token::LOWER_ALPHA_CHARS, StringToken},
+    generic::{Charset, StringToken, UniqueTokens},
     InputGenerator,
 };
 use rand::{seq::SliceRandom, Rng};
@@ -12,7 +15,7 @@ use super::Day;
 const LENS_RANGE: Range<u8> = 1..10;
 const NUM_UNIQUE_KEYS: Range<usize> = 500..601;
 const NUM_OPERATIONS: Range<usize> = 4000..5000;
-const KEY_LEN: Range<usize> = 2..7;
+const KEY_LENGTH: RangeInclusive<usize> = 2..=6;
 
 /// Strategy is going to be to generate a fixed number of keys, then perform
 /// operations using all of those keys.
@@ -36,29 +39,20 @@ impl InputGenerator for Day15 {
         rng: &mut R,
     ) -> Result<Self::Output, Self::GeneratorError> {
         let key_gen = StringToken::builder()
-            .length(KEY_LEN)
-            .charset(LOWER_ALPHA_CHARS)
-            .build()
-            .unwrap();
+            .charset(Charset::lower_consonants())
+            .length(KEY_LENGTH)
+            .build()?;
 
         let num_keys = rng.gen_range(NUM_UNIQUE_KEYS);
-        let mut keys = HashSet::with_capacity(num_keys);
-
-        while keys.len() < num_keys {
-            let key = key_gen.gen_input(rng)?;
-            if keys.contains(&key) {
-                continue;
-            }
-
-            keys.insert(key);
-        }
-
-        let key_refs = keys.iter().collect::<Vec<_>>();
+        let keys = UniqueTokens::builder()
+            .token(key_gen)
+            .build()?
+            .take(num_keys, rng)?;
 
         let num_instructions = rng.gen_range(NUM_OPERATIONS);
         let instructions = (0..num_instructions)
             .map(|_| {
-                let key = key_refs.choose(rng).unwrap();
+                let key = keys.choose(rng).unwrap();
                 if rng.gen_bool(0.5) {
                     Instruction {
                         key: key.as_str(),
@@ -1,13 +1,18 @@
-use std::convert::Infallible;
-
-use proliferatr::{grid::DigitGrid, InputGenerator};
-use rand::{distributions::Uniform, prelude::Distribution, Rng};
+use proliferatr::{
+    grid::{
+        field::{Falloff, FieldGenerator, Metric, RadialGradient, WhiteNoise},
+        DigitGrid,
+    },
+    point::Point,
+    InputGenerator,
+};
+use rand::Rng;
 
 use super::Day;
 
 const DIMENSION: usize = 141;
-const CENTER: usize = DIMENSION / 2;
-const OUTER_DIST: usize = CENTER - 6;
+const CENTER: i64 = DIMENSION as i64 / 2;
+const OUTER_DIST: f64 = (CENTER - 6) as f64;
 
 /// It appears like the center of the real inputs have much higher numbers than
 /// the edges
@@ -23,47 +28,34 @@ impl Day for Day17 {
 }
 
 impl InputGenerator for Day17 {
-    type GeneratorError = Infallible;
+    type GeneratorError = anyhow::Error;
     type Output = DigitGrid;
 
     fn gen_input<R: Rng + Clone + ?Sized>(
         &self,
         rng: &mut R,
     ) -> Result<Self::Output, Self::GeneratorError> {
-        let mut out = DigitGrid::new(DIMENSION, DIMENSION, 0);
-        let center = Location {
-            row: CENTER,
-            col: CENTER,
-        };
-
-        let inner = Uniform::from(4..10);
-        let outer = Uniform::from(1..7);
-
-        #[allow(clippy::needless_range_loop)]
-        for row in 0..DIMENSION {
-            for col in 0..DIMENSION {
-                let loc = Location { row, col };
-                if loc.manhattan_dist(&center) < OUTER_DIST {
-                    out[row][col] = inner.sample(rng);
-                } else {
-                    out[row][col] = outer.sample(rng);
-                }
-            }
-        }
-
-        Ok(out)
-    }
-}
-
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-struct Location {
-    row: usize,
-    col: usize,
-}
-
-impl Location {
-    pub fn manhattan_dist(&self, other: &Self) -> usize {
-        self.row.max(other.row) - self.row.min(other.row) + self.col.max(other.col)
-            - self.col.min(other.col)
+        // 1-6 everywhere, raised to 4-9 within the center diamond
+        FieldGenerator::new(DIMENSION, DIMENSION)
+            .range(1, 9)
+            .normalize(false)
+            .layer(
+                RadialGradient::builder()
+                    .center(Point::new(CENTER, CENTER))
+                    .radius(OUTER_DIST)
+                    .metric(Metric::Manhattan)
+                    .falloff(Falloff::Step)
+                    .inner(3.0)
+                    .build()?,
+            )
+            .layer(
+                WhiteNoise::builder()
+                    .min(1.0)
+                    .max(6.0)
+                    .integers(true)
+                    .build()?,
+            )
+            .generate_digits(rng)
+            .map_err(Into::into)
     }
 }
@@ -1,8 +1,6 @@
-use std::{collections::HashSet, fmt::Display};
-
-use itertools::Itertools;
 use proliferatr::{
-    generic::{token::LOWER_ALPHA_CHARS, StringToken},
+    circuit::{BinaryCounter, ModuleKind, PulseNetwork, BROADCASTER},
+    generic::{Charset, StringToken, UniqueTokens},
     InputGenerator,
 };
 use rand::{seq::SliceRandom, Rng};
@@ -31,11 +29,14 @@ const PRIME_CHOICES: &[u32] = &[
 ];
 const NUM_ADDERS: usize = 4;
 const NUM_BITS: usize = 12;
-const KEY_LEN: usize = 2;
+const KEY_LENGTH: usize = 2;
+// enough presses to see every counter fire twice
+const PRESS_LIMIT: usize = 2 << NUM_BITS;
 
-/// We have 4, 12-bit adders that we're going to configure such that when they
+/// We have 4, 12-bit counters that we're going to configure such that when they
 /// reach a particular 12-bit prime, will cause their conjunction to emit a low
-/// pulse.
+/// pulse. The final conjunction only sends a low pulse to `rx` once all four
+/// fire on the same press, which happens after the product of the primes.
 #[derive(Debug, Default, Clone, Copy)]
 pub struct Day20;
 
@@ -56,167 +57,49 @@ impl InputGenerator for Day20 {
         rng: &mut R,
     ) -> Result<Self::Output, Self::GeneratorError> {
         let key_gen = StringToken::builder()
-            .length(KEY_LEN..=KEY_LEN)
-            .charset(LOWER_ALPHA_CHARS)
-            .build()
-            .unwrap();
-
-        let desired_keys = (NUM_BITS + 2) * 4;
-        let mut keys: Vec<String> = Vec::with_capacity(desired_keys);
-        let mut seen_keys: HashSet<String> = HashSet::with_capacity(desired_keys + 3);
-
-        seen_keys.insert("rx".into());
-        let final_key = key_gen.gen_input(rng)?;
-        seen_keys.insert(final_key.clone());
-
-        let mut final_conjuction = Component::new(ComponentKind::Conjunction, &final_key);
-        final_conjuction.links.push("rx");
+            .charset(Charset::lower_consonants())
+            .length(KEY_LENGTH..=KEY_LENGTH)
+            .build()?;
+        let mut key_pool = UniqueTokens::builder()
+            .token(key_gen)
+            .reserve("rx")
+            .reserve(BROADCASTER)
+            .build()?;
+
+        let final_key = key_pool.next(rng)?;
+
+        let mut network = PulseNetwork::new();
+        network.add(ModuleKind::Output, "rx")?;
+        network.add(ModuleKind::Conjunction, final_key.as_str())?;
+        network.connect(&final_key, "rx")?;
+        network.add(ModuleKind::Broadcaster, BROADCASTER)?;
 
         let primes = PRIME_CHOICES
             .choose_multiple(rng, NUM_ADDERS)
             .copied()
             .collect::<Vec<_>>();
-        let mut adders = Vec::with_capacity(NUM_ADDERS);
-
-        while keys.len() < desired_keys {
-            let key = key_gen.gen_input(rng)?;
-            if seen_keys.contains(&key) {
-                continue;
-            }
 
-            seen_keys.insert(key.clone());
-            keys.push(key);
-        }
+        let keys = key_pool.take((NUM_BITS + 2) * NUM_ADDERS, rng)?;
 
-        for (idx, key_group) in keys.chunks(NUM_BITS + 2).enumerate() {
-            adders.push(Adder::new(rng, primes[idx], &final_key, key_group))
+        for (prime, key_group) in primes.iter().zip(keys.chunks(NUM_BITS + 2)) {
+            BinaryCounter::builder()
+                .bits(NUM_BITS)
+                .period(*prime as u64)
+                .build()?
+                .attach(&mut network, key_group, BROADCASTER, &final_key, rng)?;
         }
 
-        let mut broadcaster = Component::new(ComponentKind::Broadcaster, "broadcaster");
-        for adder in adders.iter() {
-            broadcaster.links.push(adder.bits[0].key);
-        }
-
-        let mut out = Vec::with_capacity(desired_keys + 2);
-        out.push(final_conjuction.to_string());
-        out.push(broadcaster.to_string());
-
-        for adder in adders.iter() {
-            adder.populate(&mut out);
+        let expected: usize = primes.iter().map(|p| *p as usize).product();
+        let presses = network.presses_until_low("rx", PRESS_LIMIT)?;
+        if presses != expected {
+            anyhow::bail!(
+                "Expected rx to receive a low pulse after {expected} presses, not {presses}"
+            );
         }
 
+        let mut out: Vec<String> = network.to_string().lines().map(String::from).collect();
         out.shuffle(rng);
 
         Ok(out.join("\n"))
     }
 }
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub enum ComponentKind {
-    FlipFlop,
-    Conjunction,
-    Broadcaster,
-}
-
-impl Display for ComponentKind {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::FlipFlop => "%",
-            Self::Conjunction => "&",
-            Self::Broadcaster => "",
-        }
-        .fmt(f)
-    }
-}
-
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct Component<'a> {
-    kind: ComponentKind,
-    key: &'a str,
-    links: Vec<&'a str>,
-}
-
-impl<'a> Component<'a> {
-    pub fn new(kind: ComponentKind, key: &'a str) -> Self {
-        Self {
-            kind,
-            key,
-            links: Vec::default(),
-        }
-    }
-}
-
-impl<'a> Display for Component<'a> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}{} -> {}",
-            self.kind,
-            self.key,
-            self.links.iter().join(", ")
-        )
-    }
-}
-
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct Adder<'a> {
-    bits: Vec<Component<'a>>,
-    conjunction: Component<'a>,
-    nand: Component<'a>,
-}
-
-impl<'a> Adder<'a> {
-    pub fn new<R: Rng + Clone + ?Sized>(
-        rng: &mut R,
-        prime: u32,
-        final_conjuction: &'a str,
-        keys: &'a [String],
-    ) -> Self {
-        let mut bits: Vec<Component> = (0..NUM_BITS)
-            .map(|i| Component::new(ComponentKind::FlipFlop, keys[i].as_str()))
-            .collect();
-        let mut conjunction = Component::new(ComponentKind::Conjunction, keys[12].as_str());
-        let mut nand = Component::new(ComponentKind::Conjunction, keys[13].as_str());
-        nand.links.push(final_conjuction);
-
-        for i in 0..NUM_BITS {
-            let bit = 1_u32 << i;
-            if prime & bit != 0 {
-                if i < NUM_BITS - 1 {
-                    bits[i].links.push(keys[12].as_str());
-                    bits[i].links.push(keys[i + 1].as_str());
-                } else {
-                    bits[i].links.push(keys[12].as_str());
-                }
-            } else if i < NUM_BITS - 1 {
-                bits[i].links.push(keys[i + 1].as_str());
-            }
-
-            if prime & bit == 0 || i == 0 {
-                conjunction.links.push(keys[i].as_str());
-            }
-        }
-
-        // insert the NAND node into a random position after the first index
-        // we probably don't have to handle the edge case where this is empty,
-        // since it should not be possible
-        conjunction
-            .links
-            .insert(rng.gen_range(1..conjunction.links.len()), keys[13].as_str());
-
-        Self {
-            bits,
-            conjunction,
-            nand,
-        }
-    }
-
-    pub fn populate(&self, out: &mut Vec<String>) {
-        for bit in self.bits.iter() {
-            out.push(bit.to_string());
-        }
-
-        out.push(self.conjunction.to_string());
-        out.push(self.nand.to_string());
-    }
-}
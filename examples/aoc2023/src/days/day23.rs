@@ -1,12 +1,14 @@
-use std::{collections::VecDeque, convert::Infallible};
-
 use itertools::Itertools;
 use proliferatr::{
+    bound::Bound2D,
     direction::Cardinal,
-    maze::{Location, MazeGrid},
+    maze::{Location, MazeGrid, MazeMark, MazeRenderer, Opening},
+    path::{Path, PointPath, RandomPathMutator, SelfAvoidingPath, UnitSegmentAlterer},
+    point::Point,
     InputGenerator,
 };
-use rand::{seq::SliceRandom, Rng};
+use rand::Rng;
+use rustc_hash::FxHashSet;
 
 use super::Day;
 
@@ -14,15 +16,21 @@ const DIMENSION: usize = 70;
 // this doesn't divide the grid evenly, but it's good enough.
 const TILE_WIDTH: usize = 11;
 const NUM_ALTERATION_CYCLES: usize = 30;
-const ALTERATIONS: &[Alt] = &[Alt::Nothing, Alt::Expand, Alt::Contract];
+const BOUNDS: Bound2D = Bound2D {
+    min_x: 0,
+    max_x: DIMENSION as i64 - 1,
+    min_y: 0,
+    max_y: DIMENSION as i64 - 1,
+};
 
 /// Divide the grid into 11x11 tiles, randomly selecting a point in each tile
 /// to be the location of a junction. The upper right and lower left corners
 /// will have a "junction" with only two neighbors, which will turn them into
 /// non-junctions in the final grid.
 ///
-/// For each junction, construct a shortest path to each neighbor for which a
-/// joining path does not already exist.
+/// For each junction, route a self-avoiding corridor to its east and south
+/// neighbors, leaving each junction and entering the next from the matching
+/// side.
 ///
 /// Alter each path repeatedly within the confines of the grid (like we do for
 /// the closed paths for days 10 and 18).
@@ -43,7 +51,7 @@ impl Day for Day23 {
 }
 
 impl InputGenerator for Day23 {
-    type GeneratorError = Infallible;
+    type GeneratorError = anyhow::Error;
     type Output = String;
 
     fn gen_input<R: Rng + Clone + ?Sized>(
@@ -58,119 +66,137 @@ impl InputGenerator for Day23 {
             Cardinal::East
         };
 
-        let (mut paths, junctions, mut occupied) = 'outer: loop {
-            let mut occupied = vec![vec![false; DIMENSION]; DIMENSION];
-            let mut junctions = vec![vec![Location::default(); 6]; 6];
+        let mut junctions = vec![vec![Location::default(); 6]; 6];
 
-            // we can probably only include the south and east nodes.
-            #[allow(clippy::needless_range_loop)]
-            for tile_r in 0..6 {
-                let max_r = TILE_WIDTH * (tile_r + 1) - 4;
-                let min_r = TILE_WIDTH * tile_r + 4;
+        // we can probably only include the south and east nodes.
+        #[allow(clippy::needless_range_loop)]
+        for tile_r in 0..6 {
+            let max_r = TILE_WIDTH * (tile_r + 1) - 4;
+            let min_r = TILE_WIDTH * tile_r + 4;
 
-                for tile_c in 0..6 {
-                    let max_c = TILE_WIDTH * (tile_c + 1) - 4;
-                    let min_c = TILE_WIDTH * tile_c + 4;
+            for tile_c in 0..6 {
+                let max_c = TILE_WIDTH * (tile_c + 1) - 4;
+                let min_c = TILE_WIDTH * tile_c + 4;
 
-                    let loc: Location =
-                        (rng.gen_range(min_r..max_r), rng.gen_range(min_c..max_c)).into();
-                    junctions[tile_r][tile_c] = loc;
-                }
-            }
-
-            let mut paths = Vec::new();
-            if let Some(p) = initial_path(
-                &Location::default(),
-                &junctions[0][0],
-                first_dir,
-                &mut occupied,
-            ) {
-                paths.push(p);
-            } else {
-                continue 'outer;
+                let loc: Location =
+                    (rng.gen_range(min_r..max_r), rng.gen_range(min_c..max_c)).into();
+                junctions[tile_r][tile_c] = loc;
             }
+        }
 
-            for row in 0..6 {
-                // row 0
-                for col in 0..6 {
-                    if col < 5 {
-                        if let Some(p) = initial_path(
-                            &junctions[row][col],
-                            &junctions[row][col + 1],
-                            Cardinal::East,
-                            &mut occupied,
-                        ) {
-                            paths.push(p);
-                        } else {
-                            continue 'outer;
-                        }
-                    }
+        let last_dir = if rng.gen_bool(0.5) {
+            Cardinal::South
+        } else {
+            Cardinal::East
+        };
 
-                    if row < 5 {
-                        if let Some(p) = initial_path(
-                            &junctions[row][col],
-                            &junctions[row + 1][col],
-                            Cardinal::South,
-                            &mut occupied,
-                        ) {
-                            paths.push(p);
-                        } else {
-                            continue 'outer;
-                        }
-                    }
+        // every corridor as (start, end, direction it leaves the start and
+        // enters the end)
+        let mut corridors = vec![(Location::default(), junctions[0][0], first_dir)];
+        for row in 0..6 {
+            for col in 0..6 {
+                if col < 5 {
+                    corridors.push((junctions[row][col], junctions[row][col + 1], Cardinal::East));
                 }
-            }
 
-            let last_dir = if rng.gen_bool(0.5) {
-                Cardinal::South
-            } else {
-                Cardinal::East
-            };
-
-            // path to the last exit
-            if let Some(p) = initial_path(
-                &junctions[5][5],
-                &Location {
-                    row: DIMENSION - 1,
-                    col: DIMENSION - 1,
-                },
-                last_dir,
-                &mut occupied,
-            ) {
-                paths.push(p);
-            } else {
-                continue 'outer;
+                if row < 5 {
+                    corridors.push((
+                        junctions[row][col],
+                        junctions[row + 1][col],
+                        Cardinal::South,
+                    ));
+                }
             }
+        }
+        corridors.push((
+            junctions[5][5],
+            (DIMENSION - 1, DIMENSION - 1).into(),
+            last_dir,
+        ));
+
+        // reserve the ends of every corridor and the cells next to them, so a
+        // corridor can't cut another off from its junction
+        let mut occupied: FxHashSet<Point> = FxHashSet::default();
+        for (start, end, dir) in corridors.iter() {
+            occupied.extend([
+                to_point(start),
+                to_point(end),
+                to_point(&step(start, *dir, true)),
+                to_point(&step(end, *dir, false)),
+            ]);
+        }
 
-            break (paths, junctions, occupied);
-        };
+        let mut paths = Vec::with_capacity(corridors.len());
+        for (start, end, dir) in corridors.iter() {
+            let from = to_point(&step(start, *dir, true));
+            let to = to_point(&step(end, *dir, false));
+
+            let mut avoid = occupied.clone();
+            avoid.remove(&from);
+            avoid.remove(&to);
+
+            let route = SelfAvoidingPath::builder()
+                .start(from)
+                .end(to)
+                .bounds(BOUNDS)
+                .avoid(avoid)
+                .build()?
+                .gen_path(rng)?;
+
+            let path: Path = std::iter::once(to_point(start))
+                .chain(route.points().copied())
+                .chain(std::iter::once(to_point(end)))
+                .collect();
+
+            occupied.extend(path.points().copied());
+            paths.push(path);
+        }
 
-        // mutate the paths
+        // mutate the paths, keeping the first and last segments in place so
+        // they still meet the junctions from the right side
         for _ in 0..NUM_ALTERATION_CYCLES {
-            for p in paths.iter_mut() {
-                p.alter(rng, &mut occupied);
+            for path in paths.iter_mut() {
+                let mut alterer: UnitSegmentAlterer = UnitSegmentAlterer::builder()
+                    .bounds(BOUNDS)
+                    .end_margin(1)
+                    .avoid(occupied.clone())
+                    .build()?;
+
+                let before: Vec<Point> = path.points().copied().collect();
+                if alterer.mutate_with(path, rng) {
+                    for p in before.iter() {
+                        occupied.remove(p);
+                    }
+                    occupied.extend(path.points().copied());
+                }
             }
         }
 
         // translate the paths into the grid
         for path in paths.iter() {
-            for (p1, p2) in path.locations.iter().tuple_windows() {
-                let d = p1.dir_to(p2);
-                let v1 = grid.get(p1).unwrap();
-                grid.set(p1, v1 | d as u8);
+            for (p1, p2) in path.points().map(to_location).tuple_windows() {
+                let d = p1.dir_to(&p2);
+                let v1 = grid.get(&p1).unwrap();
+                grid.set(&p1, v1 | d as u8);
 
-                let v2 = grid.get(p2).unwrap();
-                grid.set(p2, v2 | d.opposite() as u8);
+                let v2 = grid.get(&p2).unwrap();
+                grid.set(&p2, v2 | d.opposite() as u8);
             }
         }
 
-        let mut chars = grid.char_representation();
+        let mut renderer = MazeRenderer::builder();
+        renderer
+            .entrance(Opening::new((0, 0), Cardinal::North))
+            .exit(Opening::new(
+                (DIMENSION - 1, DIMENSION - 1),
+                Cardinal::South,
+            ));
+
         // place arrows at the junctions
         #[allow(clippy::needless_range_loop)]
         for r in 0..6 {
             for c in 0..6 {
-                let row = junctions[r][c].row * 2 + 1;
-                let col = junctions[r][c].col * 2 + 1;
+                let loc = junctions[r][c];
 
                 // these are the "empty" corners
                 if (r == 0 && c == 5) || (r == 5 && c == 0) {
@@ -178,219 +204,45 @@ impl InputGenerator for Day23 {
                 }
 
                 if r > 0 || (c == 0 && first_dir == Cardinal::South) {
-                    chars[row - 1][col] = 'v';
+                    renderer.mark(MazeMark::side(loc, Cardinal::North, 'v'));
                 }
 
                 if r < 5 {
-                    chars[row + 1][col] = 'v';
+                    renderer.mark(MazeMark::side(loc, Cardinal::South, 'v'));
                 }
 
                 if c > 0 || (r == 0 && first_dir == Cardinal::East) {
-                    chars[row][col - 1] = '>';
+                    renderer.mark(MazeMark::side(loc, Cardinal::West, '>'));
                 }
 
                 if c < 5 {
-                    chars[row][col + 1] = '>';
-                }
-            }
-        }
-
-        Ok(chars
-            .iter()
-            .map(|r| r.iter().collect::<String>())
-            .join("\n"))
-    }
-}
-
-// because of how we pick junctions, it should be unlikely that lines will
-// cross, but, if they do, we'll just take the lazy way out and regen the whole
-// junctions
-fn initial_path(
-    start: &Location,
-    end: &Location,
-    dir: Cardinal,
-    occupied: &mut [Vec<bool>],
-) -> Option<PointPath> {
-    let mut path = VecDeque::default();
-    path.push_front(*start);
-    occupied[start.row][start.col] = true;
-    occupied[end.row][end.col] = true;
-
-    // we know because of our bounds for the junction locations, that these
-    // operations are safe
-    let (mut cur, end_target) = match dir {
-        Cardinal::East => (
-            Location {
-                row: start.row,
-                col: start.col + 1,
-            },
-            Location {
-                row: end.row,
-                col: end.col - 1,
-            },
-        ),
-        Cardinal::South => (
-            Location {
-                row: start.row + 1,
-                col: start.col,
-            },
-            Location {
-                row: end.row - 1,
-                col: end.col,
-            },
-        ),
-        _ => unreachable!(
-            "Should not have started initial path in direction other than east or south"
-        ),
-    };
-
-    occupied[cur.row][cur.col] = true;
-
-    path.push_back(cur);
-
-    match dir {
-        Cardinal::East => {
-            // move two units east
-            for _ in 0..2 {
-                cur.col += 1;
-                path.push_back(cur);
-                if occupied[cur.row][cur.col] {
-                    return None;
-                }
-                occupied[cur.row][cur.col] = true;
-            }
-
-            // align the rows
-            while cur.row != end_target.row {
-                if cur.row < end_target.row {
-                    cur.row += 1;
-                } else {
-                    cur.row -= 1;
-                }
-                path.push_back(cur);
-                if occupied[cur.row][cur.col] {
-                    return None;
-                }
-                occupied[cur.row][cur.col] = true;
-            }
-
-            // now move east until we hit the target
-            while cur.col != end_target.col {
-                cur.col += 1;
-                path.push_back(cur);
-                if occupied[cur.row][cur.col] {
-                    return None;
+                    renderer.mark(MazeMark::side(loc, Cardinal::East, '>'));
                 }
-                occupied[cur.row][cur.col] = true;
             }
         }
-        Cardinal::South => {
-            // move two units south
-            for _ in 0..2 {
-                cur.row += 1;
-                path.push_back(cur);
-                if occupied[cur.row][cur.col] {
-                    return None;
-                }
-                occupied[cur.row][cur.col] = true;
-            }
-
-            // align the cols
-            while cur.col != end_target.col {
-                if cur.col < end_target.col {
-                    cur.col += 1;
-                } else {
-                    cur.col -= 1;
-                }
-                path.push_back(cur);
-                if occupied[cur.row][cur.col] {
-                    return None;
-                }
-                occupied[cur.row][cur.col] = true;
-            }
 
-            // now move south until we hit the target
-            while cur.row != end_target.row {
-                cur.row += 1;
-                path.push_back(cur);
-                if occupied[cur.row][cur.col] {
-                    return None;
-                }
-                occupied[cur.row][cur.col] = true;
-            }
-        }
-        _ => unreachable!(
-            "Should not have started initial path in direction other than east or south"
-        ),
+        Ok(renderer.build()?.render_string(&grid)?)
     }
-
-    path.push_back(*end);
-    Some(PointPath { locations: path })
 }
 
-#[derive(Debug, Default, Clone, PartialEq, Eq)]
-pub struct PointPath {
-    locations: VecDeque<Location>,
-}
-
-impl PointPath {
-    pub fn alter<R: Rng + Clone + ?Sized>(&mut self, rng: &mut R, occupied: &mut [Vec<bool>]) {
-        for i in (1..(self.locations.len() - 2)).rev() {
-            let mut p1 = self.locations[i];
-            let mut p2 = self.locations[i + 1];
-
-            let orient = if p1.row == p2.row {
-                Orientation::Horizontal
-            } else {
-                Orientation::Vertical
-            };
-
-            let alteration = ALTERATIONS.choose(rng).unwrap();
-            match alteration {
-                Alt::Expand => match orient {
-                    Orientation::Vertical if p1.col > 0 => {
-                        p1.col -= 1;
-                        p2.col -= 1;
-                    }
-                    Orientation::Horizontal if p1.row > 0 => {
-                        p1.row -= 1;
-                        p2.row -= 1;
-                    }
-                    _ => continue,
-                },
-                Alt::Contract => match orient {
-                    Orientation::Vertical if p1.col < DIMENSION - 1 => {
-                        p1.col += 1;
-                        p2.col += 1;
-                    }
-                    Orientation::Horizontal if p1.row < DIMENSION - 1 => {
-                        p1.row += 1;
-                        p2.row += 1;
-                    }
-                    _ => continue,
-                },
-                Alt::Nothing => continue,
-            }
-
-            if !occupied[p1.row][p1.col] && !occupied[p2.row][p2.col] {
-                occupied[p1.row][p1.col] = true;
-                occupied[p2.row][p2.col] = true;
-                self.locations.insert(i + 1, p2);
-                self.locations.insert(i + 1, p1);
-            }
-        }
+/// The neighbor of `loc` in the direction `dir` (or the opposite direction if
+/// not `forward`). Corridors only ever run east or south.
+fn step(loc: &Location, dir: Cardinal, forward: bool) -> Location {
+    let mut next = *loc;
+    match (dir, forward) {
+        (Cardinal::East, true) => next.col += 1,
+        (Cardinal::East, false) => next.col -= 1,
+        (Cardinal::South, true) => next.row += 1,
+        (Cardinal::South, false) => next.row -= 1,
+        _ => unreachable!("Corridors only run east or south"),
     }
+    next
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub enum Alt {
-    Nothing,
-    Contract,
-    Expand,
+fn to_point(loc: &Location) -> Point {
+    Point::new(loc.col as i64, loc.row as i64)
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub enum Orientation {
-    Horizontal,
-    Vertical,
+fn to_location(point: &Point) -> Location {
+    (point.y as usize, point.x as usize).into()
 }
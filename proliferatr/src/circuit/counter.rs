@@ -0,0 +1,217 @@
+use derive_builder::Builder;
+use rand::Rng;
+
+use super::{CircuitError, ModuleKind, PulseNetwork};
+
+/// A binary counter of flip-flops that sends a high pulse to its output every
+/// `period` presses of the button, and a low pulse at every other press.
+///
+/// The counter is made of `bits` flip-flops chained from the least to most
+/// significant bit, a conjunction that reads the flip-flops of the bits set in
+/// `period`, and a single input conjunction that inverts the output of the
+/// first. When the count reaches `period`, the conjunction fires, sending a
+/// pulse through the inverter to the output and a low pulse to every flip-flop
+/// of a bit not set in `period`, as well as the first. This adds
+/// `2^bits - period` to the count, resetting it to zero.
+///
+/// The `period` must be odd, and less than `2^bits`.
+///
+/// # Examples
+/// ```
+/// use rand::thread_rng;
+/// use proliferatr::circuit::{BinaryCounter, ModuleKind, PulseNetwork, BROADCASTER};
+///
+/// let mut network = PulseNetwork::new();
+/// network.add(ModuleKind::Broadcaster, BROADCASTER).unwrap();
+/// network.add(ModuleKind::Conjunction, "end").unwrap();
+/// network.add(ModuleKind::Output, "rx").unwrap();
+/// network.connect("end", "rx").unwrap();
+///
+/// for (prefix, period) in [('a', 11), ('b', 13)] {
+///     let counter = BinaryCounter::builder().bits(4).period(period).build().unwrap();
+///     let names: Vec<String> = (0..counter.module_count())
+///         .map(|i| format!("{prefix}{i}"))
+///         .collect();
+///
+///     counter
+///         .attach(&mut network, &names, BROADCASTER, "end", &mut thread_rng())
+///         .unwrap();
+/// }
+///
+/// assert_eq!(network.presses_until_low("rx", 100).unwrap(), 143);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct BinaryCounter {
+    bits: usize,
+
+    period: u64,
+}
+
+impl BinaryCounterBuilder {
+    fn validate(&self) -> Result<(), String> {
+        if let Some(bits) = self.bits {
+            if !(1..64).contains(&bits) {
+                return Err("bits must be between 1 and 63.".into());
+            }
+
+            if let Some(period) = self.period {
+                if period % 2 == 0 || period < 3 || period >> bits != 0 {
+                    return Err(format!(
+                        "period must be odd, at least 3, and less than 2^{bits}."
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl BinaryCounter {
+    pub fn builder() -> BinaryCounterBuilder {
+        BinaryCounterBuilder::default()
+    }
+
+    /// The number of modules in the counter, which is the number of `names`
+    /// required by [BinaryCounter::attach].
+    pub fn module_count(&self) -> usize {
+        self.bits + 2
+    }
+
+    /// Add the modules of the counter to the `network`, connecting `input` to
+    /// the first bit and the inverter to `output`. Both `input` and `output`
+    /// must already exist.
+    ///
+    /// The flip-flops are named by the first `bits` names, followed by the
+    /// conjunction and then the inverter. The inverter is placed at a random
+    /// position between the first and last outputs of the conjunction.
+    pub fn attach<R, S>(
+        &self,
+        network: &mut PulseNetwork,
+        names: &[S],
+        input: &str,
+        output: &str,
+        rng: &mut R,
+    ) -> Result<(), CircuitError>
+    where
+        R: Rng + ?Sized,
+        S: AsRef<str>,
+    {
+        if names.len() != self.module_count() {
+            return Err(CircuitError::NameCount {
+                expected: self.module_count(),
+                found: names.len(),
+            });
+        }
+
+        let names: Vec<&str> = names.iter().map(|n| n.as_ref()).collect();
+        let (bits, rest) = names.split_at(self.bits);
+        let (conjunction, inverter) = (rest[0], rest[1]);
+
+        for bit in bits {
+            network.add(ModuleKind::FlipFlop, *bit)?;
+        }
+        network.add(ModuleKind::Conjunction, conjunction)?;
+        network.add(ModuleKind::Conjunction, inverter)?;
+
+        network.connect(input, bits[0])?;
+
+        let mut resets = Vec::with_capacity(self.bits + 1);
+        for (i, bit) in bits.iter().enumerate() {
+            let set = self.period & (1 << i) != 0;
+
+            if set {
+                network.connect(bit, conjunction)?;
+            }
+
+            if i + 1 < self.bits {
+                network.connect(bit, bits[i + 1])?;
+            }
+
+            if !set || i == 0 {
+                resets.push(*bit);
+            }
+        }
+
+        resets.insert(rng.gen_range(1..resets.len().max(2)), inverter);
+        for target in resets {
+            network.connect(conjunction, target)?;
+        }
+
+        network.connect(inverter, output)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::circuit::{Pulse, BROADCASTER};
+
+    use super::*;
+
+    #[test]
+    fn periods() {
+        let mut rng = StdRng::seed_from_u64(3);
+
+        for period in [3, 5, 9, 15, 21, 31] {
+            let mut network = PulseNetwork::new();
+            network.add(ModuleKind::Broadcaster, BROADCASTER).unwrap();
+            network.add(ModuleKind::Output, "out").unwrap();
+
+            let names: Vec<String> = (0..7).map(|i| format!("m{i}")).collect();
+            BinaryCounter::builder()
+                .bits(5)
+                .period(period)
+                .build()
+                .unwrap()
+                .attach(&mut network, &names, BROADCASTER, "out", &mut rng)
+                .unwrap();
+
+            let mut sim = network.simulator().unwrap();
+            let mut fired = Vec::new();
+            for press in 1..=(period as usize * 3) {
+                sim.press_with(|_, to, pulse| {
+                    if to == "out" && pulse == Pulse::High {
+                        fired.push(press);
+                    }
+                });
+            }
+
+            let period = period as usize;
+            assert_eq!(fired, vec![period, period * 2, period * 3]);
+        }
+    }
+
+    #[test]
+    fn validation() {
+        assert!(BinaryCounter::builder().bits(4).period(16).build().is_err());
+        assert!(BinaryCounter::builder().bits(4).period(8).build().is_err());
+        assert!(BinaryCounter::builder().bits(4).period(1).build().is_err());
+        assert!(BinaryCounter::builder().bits(0).period(3).build().is_err());
+
+        let mut network = PulseNetwork::new();
+        let err = BinaryCounter::builder()
+            .bits(4)
+            .period(7)
+            .build()
+            .unwrap()
+            .attach(
+                &mut network,
+                &["a"],
+                BROADCASTER,
+                "out",
+                &mut StdRng::seed_from_u64(1),
+            );
+        assert_eq!(
+            err,
+            Err(CircuitError::NameCount {
+                expected: 6,
+                found: 1
+            })
+        );
+    }
+}
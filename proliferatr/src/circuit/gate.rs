@@ -0,0 +1,226 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+use super::CircuitError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum GateKind {
+    And,
+    Or,
+    Xor,
+}
+
+impl GateKind {
+    pub fn apply(&self, a: bool, b: bool) -> bool {
+        match self {
+            Self::And => a && b,
+            Self::Or => a || b,
+            Self::Xor => a ^ b,
+        }
+    }
+}
+
+impl Display for GateKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::And => "AND",
+            Self::Or => "OR",
+            Self::Xor => "XOR",
+        }
+        .fmt(f)
+    }
+}
+
+/// A logic gate that combines two wires into an `output` wire.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Gate {
+    pub kind: GateKind,
+    pub inputs: (String, String),
+    pub output: String,
+}
+
+impl Display for Gate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} {} -> {}",
+            self.inputs.0, self.kind, self.inputs.1, self.output
+        )
+    }
+}
+
+/// A circuit of named wires, some of which have an initial value, and logic
+/// gates that set the value of the rest.
+///
+/// Numbers are stored across wires named by a prefix followed by the bit
+/// index as two digits, such as `x00` for the least significant bit of `x`.
+///
+/// The circuit is written out as the initial values, one per line (e.g.
+/// `x00: 1`), in order of wire name, followed by a blank line and the gates,
+/// one per line (e.g. `x00 AND y00 -> z00`).
+///
+/// # Examples
+/// ```
+/// use proliferatr::circuit::{Gate, GateCircuit, GateKind};
+///
+/// let mut circuit = GateCircuit::default();
+/// circuit.set_number('x', 0b10, 2);
+/// circuit.set_number('y', 0b11, 2);
+///
+/// for (a, kind, b, out) in [
+///     ("x00", GateKind::And, "y00", "z00"),
+///     ("x01", GateKind::Xor, "y01", "z01"),
+///     ("x01", GateKind::Or, "y01", "z02"),
+/// ] {
+///     circuit.gates.push(Gate {
+///         kind,
+///         inputs: (a.into(), b.into()),
+///         output: out.into(),
+///     });
+/// }
+///
+/// assert_eq!(circuit.number('z').unwrap(), 0b100);
+/// assert!(circuit.to_string().starts_with("x00: 0\nx01: 1\ny00: 1"));
+/// assert!(circuit
+///     .to_string()
+///     .ends_with("\n\nx00 AND y00 -> z00\nx01 XOR y01 -> z01\nx01 OR y01 -> z02"));
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct GateCircuit {
+    pub inputs: BTreeMap<String, bool>,
+    pub gates: Vec<Gate>,
+}
+
+impl GateCircuit {
+    /// The name of the wire holding bit `bit` of the number `prefix`.
+    pub fn wire(prefix: char, bit: usize) -> String {
+        format!("{prefix}{bit:02}")
+    }
+
+    /// Set the initial values of the wires for the lowest `bits` bits of
+    /// `value`, under the given `prefix`. Bits past the 64th are set to 0.
+    pub fn set_number(&mut self, prefix: char, value: u64, bits: usize) {
+        for bit in 0..bits {
+            let set = u32::try_from(bit)
+                .ok()
+                .and_then(|bit| value.checked_shr(bit))
+                .is_some_and(|v| v & 1 == 1);
+            self.inputs.insert(Self::wire(prefix, bit), set);
+        }
+    }
+
+    /// Compute the value of every wire.
+    ///
+    /// Fails if the value of a gate's output cannot be determined, because
+    /// one of its inputs never gets a value or depends on the output itself.
+    pub fn evaluate(&self) -> Result<BTreeMap<String, bool>, CircuitError> {
+        let mut values = self.inputs.clone();
+        let mut pending: Vec<&Gate> = self.gates.iter().collect();
+
+        while !pending.is_empty() {
+            let before = pending.len();
+
+            pending.retain(
+                |gate| match (values.get(&gate.inputs.0), values.get(&gate.inputs.1)) {
+                    (Some(a), Some(b)) => {
+                        let value = gate.kind.apply(*a, *b);
+                        values.insert(gate.output.clone(), value);
+                        false
+                    }
+                    _ => true,
+                },
+            );
+
+            if pending.len() == before {
+                return Err(CircuitError::Unresolvable(pending[0].output.clone()));
+            }
+        }
+
+        Ok(values)
+    }
+
+    /// Evaluate the circuit and read the number stored across the wires with
+    /// the given `prefix`. Wires for bits past the 64th are ignored.
+    pub fn number(&self, prefix: char) -> Result<u64, CircuitError> {
+        let values = self.evaluate()?;
+
+        Ok(values
+            .iter()
+            .filter_map(|(wire, value)| {
+                let bit = wire.strip_prefix(prefix)?.parse::<u32>().ok()?;
+                u64::from(*value).checked_shl(bit)
+            })
+            .fold(0, |number, bit| number | bit))
+    }
+
+    /// Swap the output wires of the gates at the indexes `a` and `b`.
+    ///
+    /// # Panics
+    /// Panics if either index is out of bounds.
+    pub fn swap_outputs(&mut self, a: usize, b: usize) {
+        let output = std::mem::take(&mut self.gates[a].output);
+        self.gates[a].output = std::mem::replace(&mut self.gates[b].output, output);
+    }
+}
+
+impl Display for GateCircuit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (wire, value) in self.inputs.iter() {
+            writeln!(f, "{}: {}", wire, u8::from(*value))?;
+        }
+
+        for gate in self.gates.iter() {
+            write!(f, "\n{}", gate)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evaluation() {
+        let mut circuit = GateCircuit::default();
+        circuit.set_number('x', 1, 1);
+        circuit.gates.push(Gate {
+            kind: GateKind::Or,
+            inputs: ("x00".into(), "b".into()),
+            output: "a".into(),
+        });
+        circuit.gates.push(Gate {
+            kind: GateKind::And,
+            inputs: ("x00".into(), "a".into()),
+            output: "b".into(),
+        });
+
+        // a and b depend on each other
+        assert_eq!(
+            circuit.evaluate(),
+            Err(CircuitError::Unresolvable("a".into()))
+        );
+
+        circuit.gates[0].inputs.1 = "x00".into();
+        let values = circuit.evaluate().unwrap();
+        assert_eq!(values.get("b"), Some(&true));
+
+        circuit.swap_outputs(0, 1);
+        assert_eq!(circuit.gates[0].output, "b");
+        assert_eq!(circuit.gates[1].output, "a");
+        assert!(circuit.evaluate().is_err());
+    }
+
+    #[test]
+    fn wide_numbers() {
+        let mut circuit = GateCircuit::default();
+        circuit.set_number('x', u64::MAX, 65);
+        assert_eq!(circuit.inputs.get("x63"), Some(&true));
+        assert_eq!(circuit.inputs.get("x64"), Some(&false));
+        assert_eq!(circuit.number('x').unwrap(), u64::MAX);
+
+        circuit.inputs.insert("x64".into(), true);
+        circuit.inputs.insert("x063".into(), true);
+        assert_eq!(circuit.number('x').unwrap(), u64::MAX);
+    }
+}
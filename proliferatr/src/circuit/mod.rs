@@ -0,0 +1,44 @@
+//! Circuits of modules that pass pulses or logic gates that combine wires,
+//! along with generators for counters and adders built from them.
+use thiserror::Error;
+
+use crate::generic::TokenError;
+
+mod counter;
+mod gate;
+mod pulse;
+mod ripple_adder;
+
+pub use counter::{BinaryCounter, BinaryCounterBuilder, BinaryCounterBuilderError};
+pub use gate::{Gate, GateCircuit, GateKind};
+pub use pulse::{Module, ModuleKind, Pulse, PulseCounts, PulseNetwork, Simulator, BROADCASTER};
+pub use ripple_adder::{
+    RippleAdder, RippleAdderBuilder, RippleAdderBuilderError, RippleAdderCircuit,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum CircuitError {
+    #[error("A module or wire named {0} already exists.")]
+    DuplicateName(String),
+
+    #[error("There is no module or wire named {0}.")]
+    UnknownName(String),
+
+    #[error("The network has no broadcaster.")]
+    MissingBroadcaster,
+
+    #[error("The network is not supported: {0}.")]
+    UnsupportedStructure(String),
+
+    #[error("Gave up after {0} button presses.")]
+    LimitReached(usize),
+
+    #[error("Expected {expected} names, but found {found}.")]
+    NameCount { expected: usize, found: usize },
+
+    #[error("The value of wire {0} cannot be determined.")]
+    Unresolvable(String),
+
+    #[error(transparent)]
+    Token(#[from] TokenError),
+}
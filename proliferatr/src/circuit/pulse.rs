@@ -0,0 +1,427 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt::Display,
+    ops::AddAssign,
+};
+
+use super::CircuitError;
+
+/// The name of the module that receives a low pulse whenever the button is
+/// pressed.
+pub const BROADCASTER: &str = "broadcaster";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Pulse {
+    Low,
+    High,
+}
+
+/// The behavior of a module in a [PulseNetwork].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ModuleKind {
+    /// Sends every pulse it receives to all of its outputs.
+    Broadcaster,
+
+    /// Ignores high pulses. A low pulse toggles it, and it sends a high pulse
+    /// if it is now on, or a low pulse if it is now off.
+    FlipFlop,
+
+    /// Remembers the last pulse from each of its inputs (initially low), and
+    /// sends a low pulse if they are all high, or a high pulse otherwise.
+    Conjunction,
+
+    /// Receives pulses without doing anything, and is not written out.
+    Output,
+}
+
+impl Display for ModuleKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::FlipFlop => "%",
+            Self::Conjunction => "&",
+            Self::Broadcaster | Self::Output => "",
+        }
+        .fmt(f)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Module {
+    pub name: String,
+    pub kind: ModuleKind,
+    pub outputs: Vec<String>,
+}
+
+impl Display for Module {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}{} -> {}",
+            self.kind,
+            self.name,
+            self.outputs.join(", ")
+        )
+    }
+}
+
+/// The number of low and high pulses sent.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PulseCounts {
+    pub low: usize,
+    pub high: usize,
+}
+
+impl PulseCounts {
+    pub fn product(&self) -> usize {
+        self.low * self.high
+    }
+}
+
+impl AddAssign for PulseCounts {
+    fn add_assign(&mut self, rhs: Self) {
+        self.low += rhs.low;
+        self.high += rhs.high;
+    }
+}
+
+/// A network of named modules that send pulses to one another, starting with
+/// a low pulse to the [BROADCASTER] every time a button is pressed.
+///
+/// The network is written out with one line per module, such as
+/// `%ab -> cd, ef`, in the order the modules were added. [ModuleKind::Output]
+/// modules are not written out.
+///
+/// # Examples
+/// ```
+/// use proliferatr::circuit::{ModuleKind, PulseNetwork, BROADCASTER};
+///
+/// let mut network = PulseNetwork::new();
+/// network.add(ModuleKind::Broadcaster, BROADCASTER).unwrap();
+/// network.add(ModuleKind::FlipFlop, "a").unwrap();
+/// network.add(ModuleKind::Conjunction, "inv").unwrap();
+/// network.add(ModuleKind::FlipFlop, "b").unwrap();
+/// network.add(ModuleKind::Conjunction, "con").unwrap();
+/// network.add(ModuleKind::Output, "output").unwrap();
+///
+/// for (from, to) in [
+///     (BROADCASTER, "a"),
+///     ("a", "inv"),
+///     ("a", "con"),
+///     ("inv", "b"),
+///     ("b", "con"),
+///     ("con", "output"),
+/// ] {
+///     network.connect(from, to).unwrap();
+/// }
+///
+/// assert_eq!(
+///     network.to_string(),
+///     "broadcaster -> a\n%a -> inv, con\n&inv -> b\n%b -> con\n&con -> output"
+/// );
+///
+/// // the second example of 2023 day 20
+/// assert_eq!(network.pulse_counts(1000).unwrap().product(), 11687500);
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PulseNetwork {
+    modules: Vec<Module>,
+    index: HashMap<String, usize>,
+}
+
+impl PulseNetwork {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a module without any outputs, returning its index.
+    pub fn add(
+        &mut self,
+        kind: ModuleKind,
+        name: impl Into<String>,
+    ) -> Result<usize, CircuitError> {
+        let name = name.into();
+        if self.index.contains_key(&name) {
+            return Err(CircuitError::DuplicateName(name));
+        }
+
+        self.index.insert(name.clone(), self.modules.len());
+        self.modules.push(Module {
+            name,
+            kind,
+            outputs: Vec::new(),
+        });
+
+        Ok(self.modules.len() - 1)
+    }
+
+    /// Add `to` to the end of the outputs of `from`.
+    pub fn connect(&mut self, from: &str, to: &str) -> Result<(), CircuitError> {
+        if !self.index.contains_key(to) {
+            return Err(CircuitError::UnknownName(to.to_string()));
+        }
+
+        let idx = *self
+            .index
+            .get(from)
+            .ok_or_else(|| CircuitError::UnknownName(from.to_string()))?;
+        self.modules[idx].outputs.push(to.to_string());
+
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Module> {
+        self.index.get(name).map(|idx| &self.modules[*idx])
+    }
+
+    /// Every module, in the order they were added.
+    pub fn modules(&self) -> &[Module] {
+        &self.modules
+    }
+
+    /// The names of the modules with `name` as an output.
+    pub fn inputs(&self, name: &str) -> Vec<&str> {
+        self.modules
+            .iter()
+            .filter(|m| m.outputs.iter().any(|o| o == name))
+            .map(|m| m.name.as_str())
+            .collect()
+    }
+
+    /// Make a simulator with every module in its initial state.
+    pub fn simulator(&self) -> Result<Simulator<'_>, CircuitError> {
+        Simulator::new(self)
+    }
+
+    /// The total number of low and high pulses sent over `presses` presses
+    /// of the button.
+    pub fn pulse_counts(&self, presses: usize) -> Result<PulseCounts, CircuitError> {
+        let mut sim = self.simulator()?;
+        let mut counts = PulseCounts::default();
+        for _ in 0..presses {
+            counts += sim.press();
+        }
+
+        Ok(counts)
+    }
+
+    /// The fewest presses of the button before `target` receives a low pulse.
+    ///
+    /// Rather than pressing the button until that happens, which could take
+    /// trillions of presses, this expects `target` to have a single input,
+    /// which is a conjunction. Each of the inputs to that conjunction must
+    /// send it a high pulse every `N` presses, for some `N`, which is checked
+    /// by simulating two periods, up to `limit` presses in total. The answer
+    /// is the least common multiple of those periods.
+    pub fn presses_until_low(&self, target: &str, limit: usize) -> Result<usize, CircuitError> {
+        let feeder = match self.inputs(target).as_slice() {
+            [feeder] if self.get(feeder).map(|m| m.kind) == Some(ModuleKind::Conjunction) => {
+                feeder.to_string()
+            }
+            _ => {
+                return Err(CircuitError::UnsupportedStructure(format!(
+                    "{target} must have a single conjunction as an input"
+                )))
+            }
+        };
+
+        let sources = self.inputs(&feeder);
+        let mut highs: Vec<Vec<usize>> = vec![Vec::new(); sources.len()];
+        let mut sim = self.simulator()?;
+
+        while highs.iter().any(|h| h.len() < 2) {
+            if sim.presses() >= limit {
+                return Err(CircuitError::LimitReached(limit));
+            }
+
+            let press = sim.presses() + 1;
+            sim.press_with(|from, to, pulse| {
+                if pulse == Pulse::High && to == feeder {
+                    if let Some(idx) = sources.iter().position(|s| *s == from) {
+                        if highs[idx].last() != Some(&press) {
+                            highs[idx].push(press);
+                        }
+                    }
+                }
+            });
+        }
+
+        highs
+            .iter()
+            .zip(sources.iter())
+            .try_fold(1, |acc, (h, source)| {
+                if h[1] != h[0] * 2 {
+                    return Err(CircuitError::UnsupportedStructure(format!(
+                        "{source} does not send high pulses periodically"
+                    )));
+                }
+
+                Ok(num::integer::lcm(acc, h[0]))
+            })
+    }
+}
+
+impl Display for PulseNetwork {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut lines = self.modules.iter().filter(|m| m.kind != ModuleKind::Output);
+
+        if let Some(first) = lines.next() {
+            first.fmt(f)?;
+        }
+
+        for module in lines {
+            writeln!(f)?;
+            module.fmt(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Tracks the state of every module of a [PulseNetwork] while pressing the
+/// button.
+#[derive(Debug, Clone)]
+pub struct Simulator<'a> {
+    network: &'a PulseNetwork,
+    broadcaster: usize,
+    targets: Vec<Vec<usize>>,
+    on: Vec<bool>,
+    memory: Vec<Vec<(usize, Pulse)>>,
+    presses: usize,
+}
+
+impl<'a> Simulator<'a> {
+    fn new(network: &'a PulseNetwork) -> Result<Self, CircuitError> {
+        let broadcaster = *network
+            .index
+            .get(BROADCASTER)
+            .ok_or(CircuitError::MissingBroadcaster)?;
+
+        let targets: Vec<Vec<usize>> = network
+            .modules
+            .iter()
+            .map(|m| m.outputs.iter().map(|o| network.index[o]).collect())
+            .collect();
+
+        let mut memory = vec![Vec::new(); network.modules.len()];
+        for (from, outputs) in targets.iter().enumerate() {
+            for to in outputs {
+                memory[*to].push((from, Pulse::Low));
+            }
+        }
+
+        Ok(Self {
+            network,
+            broadcaster,
+            targets,
+            on: vec![false; network.modules.len()],
+            memory,
+            presses: 0,
+        })
+    }
+
+    /// The number of times the button has been pressed.
+    pub fn presses(&self) -> usize {
+        self.presses
+    }
+
+    /// Press the button, returning the pulses sent, including the one from
+    /// the button.
+    pub fn press(&mut self) -> PulseCounts {
+        self.press_with(|_, _, _| {})
+    }
+
+    /// Press the button, calling `observe` with the sender, receiver and kind
+    /// of every pulse sent by a module, in order.
+    pub fn press_with<F>(&mut self, mut observe: F) -> PulseCounts
+    where
+        F: FnMut(&str, &str, Pulse),
+    {
+        self.presses += 1;
+        let mut counts = PulseCounts { low: 1, high: 0 };
+        let mut queue = VecDeque::from([(usize::MAX, self.broadcaster, Pulse::Low)]);
+
+        let modules = &self.network.modules;
+
+        while let Some((from, to, pulse)) = queue.pop_front() {
+            let send = match modules[to].kind {
+                ModuleKind::Broadcaster => Some(pulse),
+                ModuleKind::FlipFlop if pulse == Pulse::Low => {
+                    self.on[to] = !self.on[to];
+                    Some(if self.on[to] { Pulse::High } else { Pulse::Low })
+                }
+                ModuleKind::FlipFlop | ModuleKind::Output => None,
+                ModuleKind::Conjunction => {
+                    let memory = &mut self.memory[to];
+                    if let Some(slot) = memory.iter_mut().find(|(input, _)| *input == from) {
+                        slot.1 = pulse;
+                    }
+
+                    if memory.iter().all(|(_, p)| *p == Pulse::High) {
+                        Some(Pulse::Low)
+                    } else {
+                        Some(Pulse::High)
+                    }
+                }
+            };
+
+            let Some(send) = send else {
+                continue;
+            };
+
+            for next in self.targets[to].iter() {
+                observe(&modules[to].name, &modules[*next].name, send);
+                match send {
+                    Pulse::Low => counts.low += 1,
+                    Pulse::High => counts.high += 1,
+                }
+                queue.push_back((to, *next, send));
+            }
+        }
+
+        counts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_example() {
+        let mut network = PulseNetwork::new();
+        network.add(ModuleKind::Broadcaster, BROADCASTER).unwrap();
+        for name in ["a", "b", "c"] {
+            network.add(ModuleKind::FlipFlop, name).unwrap();
+        }
+        network.add(ModuleKind::Conjunction, "inv").unwrap();
+
+        for (from, to) in [
+            (BROADCASTER, "a"),
+            (BROADCASTER, "b"),
+            (BROADCASTER, "c"),
+            ("a", "b"),
+            ("b", "c"),
+            ("c", "inv"),
+            ("inv", "a"),
+        ] {
+            network.connect(from, to).unwrap();
+        }
+
+        let mut sim = network.simulator().unwrap();
+        assert_eq!(sim.press(), PulseCounts { low: 8, high: 4 });
+        assert_eq!(network.pulse_counts(1000).unwrap().product(), 32000000);
+
+        assert_eq!(
+            network.add(ModuleKind::FlipFlop, "a"),
+            Err(CircuitError::DuplicateName("a".into()))
+        );
+        assert_eq!(
+            network.connect("a", "rx"),
+            Err(CircuitError::UnknownName("rx".into()))
+        );
+        assert_eq!(
+            PulseNetwork::new().pulse_counts(1),
+            Err(CircuitError::MissingBroadcaster)
+        );
+    }
+}
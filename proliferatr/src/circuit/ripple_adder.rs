@@ -0,0 +1,355 @@
+use derive_builder::Builder;
+use rand::{seq::SliceRandom, Rng};
+
+use crate::{
+    generic::{Charset, StringToken, UniqueTokens},
+    InputGenerator,
+};
+
+use super::{CircuitError, Gate, GateCircuit, GateKind};
+
+/// A circuit made by [RippleAdder].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RippleAdderCircuit {
+    pub circuit: GateCircuit,
+
+    /// The pairs of wires whose gate outputs were swapped.
+    pub swapped: Vec<(String, String)>,
+}
+
+impl RippleAdderCircuit {
+    /// The names of every swapped wire, sorted and joined by commas.
+    pub fn swapped_wires(&self) -> String {
+        let mut wires: Vec<&str> = self
+            .swapped
+            .iter()
+            .flat_map(|(a, b)| [a.as_str(), b.as_str()])
+            .collect();
+        wires.sort_unstable();
+        wires.join(",")
+    }
+
+    /// The number produced on the `z` wires by the circuit as generated,
+    /// including any swaps.
+    pub fn output(&self) -> u64 {
+        self.circuit
+            .number('z')
+            .expect("generated circuits are acyclic")
+    }
+}
+
+/// Generates circuits of logic gates that add the numbers on the `x` and `y`
+/// wires, placing the result on the `z` wires, optionally with the outputs of
+/// some pairs of gates swapped.
+///
+/// The adder has a half adder for the first bit and a full adder for each of
+/// the remaining `bits`, with the final carry going to the extra `z` wire.
+/// Each full adder is made of five gates:
+///
+/// ```text
+/// x XOR y -> s      s XOR carry -> z
+/// x AND y -> a      s AND carry -> b      a OR b -> carry'
+/// ```
+///
+/// Wires other than `x`, `y` and `z` get unique random names of `name_length`
+/// characters from the `charset`. The order of the gates, and of the inputs to
+/// each gate, is random, as are the initial values of `x` and `y`.
+///
+/// Each of the `swaps` is made within a different adder, and is only kept if
+/// the circuit stays free of loops and the swap changes the output for some
+/// pair of numbers that only set the bits of that adder and the one before it.
+///
+/// # Examples
+/// ```
+/// use rand::thread_rng;
+/// use proliferatr::circuit::RippleAdder;
+///
+/// let adder = RippleAdder::builder().bits(8).build().unwrap();
+/// let result = adder.generate(&mut thread_rng()).unwrap();
+///
+/// let x = result.circuit.number('x').unwrap();
+/// let y = result.circuit.number('y').unwrap();
+/// assert_eq!(result.output(), x + y);
+/// assert_eq!(result.circuit.gates.len(), 2 + 7 * 5);
+///
+/// let swapped = RippleAdder::builder()
+///     .bits(8)
+///     .swaps(2)
+///     .build()
+///     .unwrap()
+///     .generate(&mut thread_rng())
+///     .unwrap();
+/// assert_eq!(swapped.swapped_wires().split(',').count(), 4);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct RippleAdder {
+    bits: usize,
+
+    #[builder(default)]
+    swaps: usize,
+
+    #[builder(default = "3")]
+    name_length: usize,
+
+    #[builder(default = "Charset::lower_consonants()", setter(into))]
+    charset: Charset,
+}
+
+impl RippleAdderBuilder {
+    fn validate(&self) -> Result<(), String> {
+        if let Some(bits) = self.bits {
+            if !(1..64).contains(&bits) {
+                return Err("bits must be between 1 and 63.".into());
+            }
+
+            if self.swaps.unwrap_or_default() > bits {
+                return Err("There cannot be more swaps than bits.".into());
+            }
+        }
+
+        if self.name_length == Some(0) {
+            return Err("name_length must be greater than 0.".into());
+        }
+
+        if let Some(charset) = &self.charset {
+            if charset.is_empty() || charset.chars().iter().any(|ch| ch.is_ascii_digit()) {
+                return Err("charset must not be empty or contain digits.".into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl RippleAdder {
+    pub fn builder() -> RippleAdderBuilder {
+        RippleAdderBuilder::default()
+    }
+
+    /// Generate a new circuit.
+    pub fn generate<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> Result<RippleAdderCircuit, CircuitError> {
+        let token = StringToken::builder()
+            .length(self.name_length..=self.name_length)
+            .charset(self.charset.clone())
+            .build()
+            .expect("name_length is validated");
+        let mut names = UniqueTokens::builder()
+            .token(token)
+            .reserved(
+                ['x', 'y', 'z']
+                    .into_iter()
+                    .flat_map(|prefix| {
+                        (0..=self.bits).map(move |bit| GateCircuit::wire(prefix, bit))
+                    })
+                    .collect(),
+            )
+            .build()
+            .expect("there are no reserved affixes");
+
+        let mut circuit = GateCircuit::default();
+        let max = u64::MAX >> (64 - self.bits);
+        circuit.set_number('x', rng.gen_range(0..=max), self.bits);
+        circuit.set_number('y', rng.gen_range(0..=max), self.bits);
+
+        // the indexes of the gates of each adder
+        let mut adders = Vec::with_capacity(self.bits);
+        let mut carry = String::new();
+
+        for bit in 0..self.bits {
+            let x = GateCircuit::wire('x', bit);
+            let y = GateCircuit::wire('y', bit);
+            let z = GateCircuit::wire('z', bit);
+            let carry_out = if bit + 1 == self.bits {
+                GateCircuit::wire('z', self.bits)
+            } else {
+                names.next(rng)?
+            };
+            let start = circuit.gates.len();
+
+            if bit == 0 {
+                push_gate(&mut circuit, GateKind::Xor, &x, &y, z);
+                push_gate(&mut circuit, GateKind::And, &x, &y, carry_out.clone());
+            } else {
+                let s = names.next(rng)?;
+                let a = names.next(rng)?;
+                let b = names.next(rng)?;
+
+                push_gate(&mut circuit, GateKind::Xor, &x, &y, s.clone());
+                push_gate(&mut circuit, GateKind::Xor, &s, &carry, z);
+                push_gate(&mut circuit, GateKind::And, &x, &y, a.clone());
+                push_gate(&mut circuit, GateKind::And, &s, &carry, b.clone());
+                push_gate(&mut circuit, GateKind::Or, &a, &b, carry_out.clone());
+            }
+
+            adders.push(start..circuit.gates.len());
+            carry = carry_out;
+        }
+
+        let swapped = self.make_swaps(&mut circuit, &adders, rng)?;
+
+        // make sure the combination of swaps did not introduce a loop
+        circuit.evaluate()?;
+
+        circuit.gates.shuffle(rng);
+        for gate in circuit.gates.iter_mut() {
+            if rng.gen_bool(0.5) {
+                let (a, b) = &mut gate.inputs;
+                std::mem::swap(a, b);
+            }
+        }
+
+        Ok(RippleAdderCircuit { circuit, swapped })
+    }
+
+    /// Swap the outputs of pairs of gates in `swaps` different adders, each
+    /// chosen from the swaps that keep the adder free of loops and can be
+    /// detected.
+    fn make_swaps<R: Rng + ?Sized>(
+        &self,
+        circuit: &mut GateCircuit,
+        adders: &[std::ops::Range<usize>],
+        rng: &mut R,
+    ) -> Result<Vec<(String, String)>, CircuitError> {
+        let mut positions: Vec<usize> = (0..self.bits).collect();
+        positions.shuffle(rng);
+
+        let mut swapped = Vec::with_capacity(self.swaps);
+        for bit in positions {
+            if swapped.len() == self.swaps {
+                break;
+            }
+
+            let gates = adders[bit].clone();
+            let candidates: Vec<(usize, usize)> = gates
+                .clone()
+                .flat_map(|a| (a + 1..gates.end).map(move |b| (a, b)))
+                .filter(|(a, b)| is_detectable(circuit, *a, *b, bit, self.bits))
+                .collect();
+
+            if let Some((a, b)) = candidates.choose(rng) {
+                circuit.swap_outputs(*a, *b);
+                swapped.push((
+                    circuit.gates[*a].output.clone(),
+                    circuit.gates[*b].output.clone(),
+                ));
+            }
+        }
+
+        if swapped.len() < self.swaps {
+            return Err(CircuitError::UnsupportedStructure(format!(
+                "only {} of {} swaps could be made",
+                swapped.len(),
+                self.swaps
+            )));
+        }
+
+        Ok(swapped)
+    }
+}
+
+impl InputGenerator for RippleAdder {
+    type GeneratorError = CircuitError;
+    type Output = RippleAdderCircuit;
+
+    fn gen_input<R: Rng + Clone>(&self, rng: &mut R) -> Result<Self::Output, Self::GeneratorError> {
+        self.generate(rng)
+    }
+}
+
+fn push_gate(circuit: &mut GateCircuit, kind: GateKind, a: &str, b: &str, output: String) {
+    circuit.gates.push(Gate {
+        kind,
+        inputs: (a.to_string(), b.to_string()),
+        output,
+    });
+}
+
+/// Check whether swapping the outputs of gates `a` and `b`, in the adder for
+/// `bit`, keeps the circuit free of loops and changes its output for some pair
+/// of numbers using only that bit and the one before it.
+fn is_detectable(circuit: &GateCircuit, a: usize, b: usize, bit: usize, bits: usize) -> bool {
+    let mut base = circuit.clone();
+    let mut test = circuit.clone();
+    test.swap_outputs(a, b);
+
+    let mask = (1u64 << bit) | ((1u64 << bit) >> 1);
+    let values: Vec<u64> = (0..=mask).filter(|v| v & !mask == 0).collect();
+
+    for x in values.iter() {
+        for y in values.iter() {
+            for circuit in [&mut base, &mut test] {
+                circuit.set_number('x', *x, bits);
+                circuit.set_number('y', *y, bits);
+            }
+
+            match (base.number('z'), test.number('z')) {
+                (_, Err(_)) => return false,
+                (expected, Ok(sum)) if expected == Ok(sum) => {}
+                _ => return true,
+            }
+        }
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn swaps() {
+        let mut rng = StdRng::seed_from_u64(11);
+        let adder = RippleAdder::builder().bits(12).swaps(4).build().unwrap();
+
+        for _ in 0..5 {
+            let result = adder.generate(&mut rng).unwrap();
+            assert_eq!(result.swapped.len(), 4);
+
+            let wires: HashSet<&str> = result
+                .swapped
+                .iter()
+                .flat_map(|(a, b)| [a.as_str(), b.as_str()])
+                .collect();
+            assert_eq!(wires.len(), 8);
+
+            // undoing the swaps restores a working adder
+            let mut circuit = result.circuit.clone();
+            for gate in circuit.gates.iter_mut() {
+                for (a, b) in result.swapped.iter() {
+                    if gate.output == *a {
+                        gate.output = b.clone();
+                    } else if gate.output == *b {
+                        gate.output = a.clone();
+                    }
+                }
+            }
+
+            for (x, y) in [(0, 0), (4095, 1), (1234, 2345), (4095, 4095)] {
+                circuit.set_number('x', x, 12);
+                circuit.set_number('y', y, 12);
+                assert_eq!(circuit.number('z').unwrap(), x + y);
+            }
+        }
+    }
+
+    #[test]
+    fn validation() {
+        assert!(RippleAdder::builder().bits(0).build().is_err());
+        assert!(RippleAdder::builder().bits(64).build().is_err());
+        assert!(RippleAdder::builder().bits(4).swaps(5).build().is_err());
+        assert!(RippleAdder::builder()
+            .bits(4)
+            .charset(b"ab1")
+            .build()
+            .is_err());
+    }
+}
@@ -380,6 +380,29 @@ pub enum HorizHexDir {
     SouthWest = 128,
 }
 
+impl HorizHexDir {
+    /// Return the direction 180 degress opposite of ourself.
+    ///
+    /// Example:
+    /// ```
+    /// use proliferatr::direction::HorizHexDir;
+    ///
+    /// assert_eq!(HorizHexDir::North.opposite(), HorizHexDir::South);
+    /// assert_eq!(HorizHexDir::NorthEast.opposite(), HorizHexDir::SouthWest);
+    /// assert_eq!(HorizHexDir::SouthEast.opposite(), HorizHexDir::NorthWest);
+    /// ```
+    pub fn opposite(&self) -> Self {
+        match self {
+            Self::North => Self::South,
+            Self::South => Self::North,
+            Self::NorthEast => Self::SouthWest,
+            Self::SouthWest => Self::NorthEast,
+            Self::SouthEast => Self::NorthWest,
+            Self::NorthWest => Self::SouthEast,
+        }
+    }
+}
+
 impl FromStr for HorizHexDir {
     type Err = DirectionError;
 
@@ -448,6 +471,29 @@ pub enum VertHexDir {
     SouthWest = 32,
 }
 
+impl VertHexDir {
+    /// Return the direction 180 degress opposite of ourself.
+    ///
+    /// Example:
+    /// ```
+    /// use proliferatr::direction::VertHexDir;
+    ///
+    /// assert_eq!(VertHexDir::East.opposite(), VertHexDir::West);
+    /// assert_eq!(VertHexDir::NorthEast.opposite(), VertHexDir::SouthWest);
+    /// assert_eq!(VertHexDir::SouthEast.opposite(), VertHexDir::NorthWest);
+    /// ```
+    pub fn opposite(&self) -> Self {
+        match self {
+            Self::East => Self::West,
+            Self::West => Self::East,
+            Self::NorthEast => Self::SouthWest,
+            Self::SouthWest => Self::NorthEast,
+            Self::SouthEast => Self::NorthWest,
+            Self::NorthWest => Self::SouthEast,
+        }
+    }
+}
+
 impl FromStr for VertHexDir {
     type Err = DirectionError;
 
@@ -0,0 +1,138 @@
+use std::sync::{Arc, OnceLock};
+
+/// The words of the embedded blocklist, one per line.
+const EMBEDDED_WORDS: &str = include_str!("blocklist.txt");
+
+/// A list of words that should never appear in generated tokens or text.
+///
+/// Words are matched case-insensitively anywhere in the text, after replacing
+/// digits and symbols commonly used in place of letters, like `4` for `a` or
+/// `$` for `s`. The [embedded](Blocklist::embedded) list covers common
+/// profanity and slurs, and is used by [StringToken](super::StringToken) by
+/// default.
+///
+/// The words are shared between clones, so a blocklist is cheap to copy into
+/// each generator.
+///
+/// # Examples
+/// ```
+/// use proliferatr::generic::Blocklist;
+///
+/// let blocklist = Blocklist::embedded();
+/// assert!(blocklist.is_clean("bcdfg"));
+/// assert!(!blocklist.is_clean("xSHITx"));
+/// assert_eq!(blocklist.find("qq5h1tqq"), Some("shit"));
+///
+/// let mut custom = Blocklist::new(["abc"]);
+/// custom.extend(["xyz"]);
+/// assert!(!custom.is_clean("__XYZ__"));
+/// assert!(custom.is_clean("shit"));
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Blocklist {
+    words: Arc<Vec<String>>,
+}
+
+impl Blocklist {
+    pub fn new<I, S>(words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut blocklist = Self::default();
+        blocklist.extend(words);
+        blocklist
+    }
+
+    /// The blocklist shipped with this crate.
+    pub fn embedded() -> &'static Blocklist {
+        static EMBEDDED: OnceLock<Blocklist> = OnceLock::new();
+
+        EMBEDDED.get_or_init(|| {
+            Self::new(
+                EMBEDDED_WORDS
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty() && !line.starts_with('#')),
+            )
+        })
+    }
+
+    /// Add more `words` to the blocklist. Empty words are ignored.
+    pub fn extend<I, S>(&mut self, words: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        for word in words {
+            let word = normalize(word.as_ref());
+            if !word.is_empty() && !self.words.contains(&word) {
+                Arc::make_mut(&mut self.words).push(word);
+            }
+        }
+    }
+
+    /// The blocked words, in lowercase.
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+
+    /// Find the first blocked word that appears in `text`.
+    pub fn find(&self, text: &str) -> Option<&str> {
+        let text = normalize(text);
+        self.words
+            .iter()
+            .find(|word| text.contains(word.as_str()))
+            .map(|word| word.as_str())
+    }
+
+    /// Check that no blocked word appears in `text`.
+    pub fn is_clean(&self, text: &str) -> bool {
+        self.find(text).is_none()
+    }
+}
+
+/// Lowercase `text`, replacing the digits and symbols commonly used in place
+/// of letters.
+fn normalize(text: &str) -> String {
+    text.chars()
+        .map(|ch| match ch {
+            '0' => 'o',
+            '1' | '!' => 'i',
+            '3' => 'e',
+            '4' | '@' => 'a',
+            '5' | '$' => 's',
+            '7' => 't',
+            '8' => 'b',
+            '9' => 'g',
+            _ => ch.to_ascii_lowercase(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn embedded() {
+        let blocklist = Blocklist::embedded();
+        assert!(!blocklist.words().is_empty());
+        assert!(blocklist.words().iter().all(|w| !w.starts_with('#')));
+
+        assert!(!blocklist.is_clean("F4Rt"));
+        assert!(!blocklist.is_clean("zzpoozz"));
+        assert!(blocklist.is_clean("twoneight7"));
+    }
+
+    #[test]
+    fn custom() {
+        let mut blocklist = Blocklist::new(["", "Bad", "bad"]);
+        assert_eq!(blocklist.words(), &["bad".to_string()]);
+
+        blocklist.extend(["worse"]);
+        assert_eq!(blocklist.find("not B4D"), Some("bad"));
+        assert_eq!(blocklist.find("w0rse"), Some("worse"));
+        assert_eq!(blocklist.find("good"), None);
+    }
+}
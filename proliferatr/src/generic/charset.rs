@@ -0,0 +1,213 @@
+use rand::{seq::SliceRandom, Rng};
+
+use super::{
+    token::{LOWER_ALPHA_CHARS, UPPER_ALPHA_CHARS},
+    TokenError,
+};
+
+/// A set of characters that tokens are made from, optionally with a weight
+/// for each character.
+///
+/// Without weights, every character is equally likely to be chosen. A
+/// character that appears more than once is chosen proportionally more often.
+///
+/// # Examples
+/// ```
+/// use rand::thread_rng;
+/// use proliferatr::generic::Charset;
+///
+/// let hex = Charset::hex();
+/// assert_eq!(hex.len(), 16);
+/// assert!(hex.contains('f'));
+///
+/// // 'a' is chosen three times as often as 'b', and 'c' never is
+/// let weighted = Charset::weighted([('a', 3), ('b', 1), ('c', 0)]).unwrap();
+/// assert!(!weighted.contains('c'));
+///
+/// let ch = weighted.sample(&mut thread_rng()).unwrap();
+/// assert!(ch == 'a' || ch == 'b');
+///
+/// assert_eq!(Charset::from("xyz"), Charset::new(['x', 'y', 'z']));
+/// assert_eq!(Charset::from(b"xyz"), Charset::new(['x', 'y', 'z']));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Charset {
+    chars: Vec<char>,
+
+    /// The running total of the weights of the characters, if they were given
+    /// weights.
+    cumulative: Option<Vec<u64>>,
+}
+
+impl Charset {
+    pub fn new<I: IntoIterator<Item = char>>(chars: I) -> Self {
+        Self {
+            chars: chars.into_iter().collect(),
+            cumulative: None,
+        }
+    }
+
+    /// Make a charset where each character is chosen in proportion to its
+    /// weight. Characters with a weight of zero are left out.
+    pub fn weighted<I: IntoIterator<Item = (char, u64)>>(weights: I) -> Result<Self, TokenError> {
+        let mut chars = Vec::new();
+        let mut cumulative = Vec::new();
+        let mut total: u64 = 0;
+
+        for (ch, weight) in weights.into_iter().filter(|(_, w)| *w > 0) {
+            total = total
+                .checked_add(weight)
+                .ok_or_else(|| TokenError::InvalidCharset("the weights are too large".into()))?;
+            chars.push(ch);
+            cumulative.push(total);
+        }
+
+        if chars.is_empty() {
+            return Err(TokenError::InvalidCharset(
+                "at least one character needs a positive weight".into(),
+            ));
+        }
+
+        Ok(Self {
+            chars,
+            cumulative: Some(cumulative),
+        })
+    }
+
+    /// Lowercase consonants, which avoids accidentally spelling most words.
+    pub fn lower_consonants() -> Self {
+        Self::from(LOWER_ALPHA_CHARS)
+    }
+
+    /// Uppercase consonants, which avoids accidentally spelling most words.
+    pub fn upper_consonants() -> Self {
+        Self::from(UPPER_ALPHA_CHARS)
+    }
+
+    pub fn lowercase() -> Self {
+        Self::new('a'..='z')
+    }
+
+    pub fn uppercase() -> Self {
+        Self::new('A'..='Z')
+    }
+
+    pub fn digits() -> Self {
+        Self::new('0'..='9')
+    }
+
+    /// Lowercase hexadecimal digits.
+    pub fn hex() -> Self {
+        Self::new(('0'..='9').chain('a'..='f'))
+    }
+
+    /// Uppercase and lowercase letters and digits.
+    pub fn alphanumeric() -> Self {
+        Self::new(('A'..='Z').chain('a'..='z').chain('0'..='9'))
+    }
+
+    pub fn chars(&self) -> &[char] {
+        &self.chars
+    }
+
+    pub fn len(&self) -> usize {
+        self.chars.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.chars.is_empty()
+    }
+
+    pub fn contains(&self, ch: char) -> bool {
+        self.chars.contains(&ch)
+    }
+
+    /// The number of different characters in the charset.
+    pub fn distinct(&self) -> usize {
+        let mut chars = self.chars.clone();
+        chars.sort_unstable();
+        chars.dedup();
+        chars.len()
+    }
+
+    /// Choose a character, or `None` if the charset is empty.
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<char> {
+        match &self.cumulative {
+            Some(cumulative) => {
+                let pick = rng.gen_range(0..*cumulative.last()?);
+                let idx = cumulative.partition_point(|total| *total <= pick);
+                self.chars.get(idx).copied()
+            }
+            None => self.chars.choose(rng).copied(),
+        }
+    }
+}
+
+impl Default for Charset {
+    fn default() -> Self {
+        Self::lower_consonants()
+    }
+}
+
+impl From<&[u8]> for Charset {
+    fn from(value: &[u8]) -> Self {
+        Self::new(value.iter().map(|b| *b as char))
+    }
+}
+
+impl<const N: usize> From<&[u8; N]> for Charset {
+    fn from(value: &[u8; N]) -> Self {
+        Self::from(value.as_slice())
+    }
+}
+
+impl From<Vec<u8>> for Charset {
+    fn from(value: Vec<u8>) -> Self {
+        Self::from(value.as_slice())
+    }
+}
+
+impl From<&str> for Charset {
+    fn from(value: &str) -> Self {
+        Self::new(value.chars())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn weights() {
+        let mut rng = StdRng::seed_from_u64(50);
+        let charset = Charset::weighted([('a', 9), ('b', 0), ('c', 1)]).unwrap();
+        assert_eq!(charset.chars(), &['a', 'c']);
+
+        let counts = (0..1000).fold([0; 2], |mut counts, _| {
+            match charset.sample(&mut rng) {
+                Some('a') => counts[0] += 1,
+                Some('c') => counts[1] += 1,
+                other => panic!("unexpected {other:?}"),
+            }
+            counts
+        });
+        assert!(counts[0] > 800 && counts[1] > 50);
+
+        assert!(Charset::weighted([('a', 0)]).is_err());
+        assert!(Charset::weighted([('a', u64::MAX), ('b', 1)]).is_err());
+        assert_eq!(Charset::new([]).sample(&mut rng), None);
+    }
+
+    #[test]
+    fn presets() {
+        assert_eq!(Charset::lowercase().len(), 26);
+        assert_eq!(Charset::uppercase().len(), 26);
+        assert_eq!(Charset::digits().len(), 10);
+        assert_eq!(Charset::alphanumeric().len(), 62);
+        assert_eq!(Charset::lower_consonants().len(), 20);
+        assert!(!Charset::upper_consonants().contains('A'));
+        assert_eq!(Charset::from(b"aab").distinct(), 2);
+    }
+}
@@ -1,7 +1,15 @@
+mod blocklist;
+mod charset;
 mod int_list;
+mod pattern;
 mod point_list;
 pub mod token;
+mod unique;
 
+pub use blocklist::Blocklist;
+pub use charset::Charset;
 pub use int_list::{IntList, IntListError};
+pub use pattern::Pattern;
 pub use point_list::{Point2List, Point3List};
-pub use token::{StringToken, TokenError};
+pub use token::{StringToken, StringTokenBuilder, StringTokenBuilderError, TokenError};
+pub use unique::{UniqueTokens, UniqueTokensBuilder, UniqueTokensBuilderError};
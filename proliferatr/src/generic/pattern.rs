@@ -0,0 +1,376 @@
+use std::{fmt::Display, iter::Peekable, ops::RangeInclusive, str::Chars, str::FromStr};
+
+use rand::Rng;
+
+use super::{Charset, TokenError};
+
+/// One part of a [Pattern]: a character from `charset`, repeated a number of
+/// times in `repeat`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Segment {
+    charset: Charset,
+    repeat: RangeInclusive<usize>,
+}
+
+/// The shape of a token, described by a small pattern language.
+///
+/// A pattern is a sequence of:
+/// - a literal character, like `x`. The characters `[`, `]`, `{`, `}` and `\`
+///   have to be escaped with a `\`.
+/// - a character class, like `[A-Z]` or `[abc0-9]`, which matches any one of
+///   the listed characters or ranges. A `-` at the end of a class is a literal.
+///
+/// each optionally followed by a repetition: `{n}` for exactly `n` times, or
+/// `{n,m}` for between `n` and `m` times inclusive.
+///
+/// # Examples
+/// ```
+/// use rand::thread_rng;
+/// use proliferatr::generic::Pattern;
+///
+/// let pattern: Pattern = "[A-Z]{2}[0-9]".parse().unwrap();
+/// assert_eq!(pattern.length(), 3..=3);
+/// assert_eq!(pattern.capacity(), Some(26 * 26 * 10));
+/// assert!(pattern.matches("AB1"));
+/// assert!(!pattern.matches("ab1"));
+///
+/// let token = pattern.sample(&mut thread_rng()).unwrap();
+/// assert!(pattern.matches(&token));
+///
+/// let pattern: Pattern = "x[a-c]{1,2}\\{".parse().unwrap();
+/// assert!(pattern.matches("xab{"));
+/// assert_eq!(pattern.to_string(), "x[a-c]{1,2}\\{");
+///
+/// assert!("[z-a]".parse::<Pattern>().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Pattern {
+    source: String,
+    segments: Vec<Segment>,
+}
+
+impl Pattern {
+    /// The range of lengths of the tokens matching the pattern.
+    pub fn length(&self) -> RangeInclusive<usize> {
+        let (min, max) = self.segments.iter().fold((0, 0), |(min, max), s| {
+            (
+                min + s.repeat.start(),
+                usize::saturating_add(max, *s.repeat.end()),
+            )
+        });
+        min..=max
+    }
+
+    /// Generate a token matching the pattern.
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<String, TokenError> {
+        let mut token = String::new();
+        for segment in self.segments.iter() {
+            let count = if segment.repeat.start() == segment.repeat.end() {
+                *segment.repeat.start()
+            } else {
+                rng.gen_range(segment.repeat.clone())
+            };
+
+            push_chars(&segment.charset, count, rng, &mut token)?;
+        }
+
+        Ok(token)
+    }
+
+    /// The number of distinct tokens matching the pattern, or `None` if that
+    /// does not fit in a `u128`.
+    ///
+    /// Tokens that can be made in more than one way, like `aa` from `a{0,1}a{1,2}`,
+    /// are counted more than once.
+    pub fn capacity(&self) -> Option<u128> {
+        self.segments.iter().try_fold(1_u128, |total, segment| {
+            total.checked_mul(capacity(&segment.charset, &segment.repeat)?)
+        })
+    }
+
+    /// Every token matching the pattern, possibly with duplicates.
+    pub(super) fn expand(&self) -> Vec<String> {
+        self.segments
+            .iter()
+            .fold(vec![String::new()], |tokens, segment| {
+                expand(tokens, &segment.charset, &segment.repeat)
+            })
+    }
+
+    /// Check whether `token` matches the pattern.
+    pub fn matches(&self, token: &str) -> bool {
+        let chars: Vec<char> = token.chars().collect();
+
+        // reachable[i] is whether the segments so far can match the first i
+        // characters of the token
+        let mut reachable = vec![false; chars.len() + 1];
+        reachable[0] = true;
+
+        for segment in self.segments.iter() {
+            let mut next = vec![false; chars.len() + 1];
+            for start in (0..=chars.len()).filter(|i| reachable[*i]) {
+                let run = chars[start..]
+                    .iter()
+                    .take_while(|ch| segment.charset.contains(**ch))
+                    .count();
+
+                for count in segment.repeat.clone().take_while(|count| *count <= run) {
+                    next[start + count] = true;
+                }
+            }
+            reachable = next;
+        }
+
+        reachable[chars.len()]
+    }
+}
+
+impl FromStr for Pattern {
+    type Err = TokenError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: String| TokenError::InvalidPattern(format!("{reason} in {s:?}"));
+
+        let mut chars = s.chars().peekable();
+        let mut segments = Vec::new();
+
+        while let Some(ch) = chars.next() {
+            let charset = match ch {
+                '[' => parse_class(&mut chars).map_err(invalid)?,
+                '\\' => match chars.next() {
+                    Some(escaped) => Charset::new([escaped]),
+                    None => return Err(invalid("nothing to escape".into())),
+                },
+                ']' | '{' | '}' => return Err(invalid(format!("unexpected '{ch}'"))),
+                _ => Charset::new([ch]),
+            };
+
+            let repeat = if chars.next_if_eq(&'{').is_some() {
+                parse_repeat(&mut chars).map_err(invalid)?
+            } else {
+                1..=1
+            };
+
+            segments.push(Segment { charset, repeat });
+        }
+
+        if segments.is_empty() {
+            return Err(invalid("empty pattern".into()));
+        }
+
+        Ok(Self {
+            source: s.to_string(),
+            segments,
+        })
+    }
+}
+
+impl Display for Pattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+/// Append `count` characters chosen from `charset` to `token`.
+pub(super) fn push_chars<R: Rng + ?Sized>(
+    charset: &Charset,
+    count: usize,
+    rng: &mut R,
+    token: &mut String,
+) -> Result<(), TokenError> {
+    for _ in 0..count {
+        token.push(
+            charset
+                .sample(rng)
+                .ok_or(TokenError::FailedToSelectCharacter)?,
+        );
+    }
+
+    Ok(())
+}
+
+/// The number of distinct strings made from between `repeat` characters in
+/// `charset`, or `None` if that does not fit in a `u128`.
+pub(super) fn capacity(charset: &Charset, repeat: &RangeInclusive<usize>) -> Option<u128> {
+    let base = charset.distinct() as u128;
+
+    // every length gives the same count, so don't walk a possibly huge range
+    if base <= 1 {
+        let lengths = match base {
+            0 => u128::from(*repeat.start() == 0),
+            _ => (repeat.end().checked_sub(*repeat.start())? as u128).checked_add(1)?,
+        };
+        return Some(lengths);
+    }
+
+    repeat.clone().try_fold(0_u128, |total, len| {
+        let count = base.checked_pow(u32::try_from(len).ok()?)?;
+        total.checked_add(count)
+    })
+}
+
+/// Every string made from one of `prefixes` followed by between `repeat`
+/// characters in `charset`.
+pub(super) fn expand(
+    prefixes: Vec<String>,
+    charset: &Charset,
+    repeat: &RangeInclusive<usize>,
+) -> Vec<String> {
+    let mut chars = charset.chars().to_vec();
+    chars.sort_unstable();
+    chars.dedup();
+
+    let mut expanded = Vec::new();
+    let mut current = prefixes;
+    for len in 0..=*repeat.end() {
+        if len >= *repeat.start() {
+            expanded.extend(current.iter().cloned());
+        }
+
+        if len == *repeat.end() || current.is_empty() {
+            break;
+        }
+
+        current = current
+            .iter()
+            .flat_map(|prefix| chars.iter().map(move |ch| format!("{prefix}{ch}")))
+            .collect();
+    }
+
+    expanded
+}
+
+/// Parse a character class, after the opening `[`.
+fn parse_class(chars: &mut Peekable<Chars>) -> Result<Charset, String> {
+    let unclosed = || "unclosed '['".to_string();
+    let mut class = Vec::new();
+
+    loop {
+        let start = match chars.next().ok_or_else(unclosed)? {
+            ']' => break,
+            '\\' => chars.next().ok_or_else(unclosed)?,
+            ch => ch,
+        };
+
+        if chars.next_if_eq(&'-').is_none() {
+            class.push(start);
+            continue;
+        }
+
+        let end = match chars.next().ok_or_else(unclosed)? {
+            ']' => {
+                class.extend([start, '-']);
+                break;
+            }
+            '\\' => chars.next().ok_or_else(unclosed)?,
+            ch => ch,
+        };
+
+        if end < start {
+            return Err(format!("reversed range '{start}-{end}'"));
+        }
+        class.extend(start..=end);
+    }
+
+    if class.is_empty() {
+        return Err("empty character class".into());
+    }
+
+    class.sort_unstable();
+    class.dedup();
+    Ok(Charset::new(class))
+}
+
+/// Parse a repetition, after the opening `{`.
+fn parse_repeat(chars: &mut Peekable<Chars>) -> Result<RangeInclusive<usize>, String> {
+    let mut body = String::new();
+    loop {
+        match chars.next() {
+            Some('}') => break,
+            Some(ch) => body.push(ch),
+            None => return Err("unclosed '{'".into()),
+        }
+    }
+
+    let parse = |n: &str| {
+        n.trim()
+            .parse::<usize>()
+            .map_err(|_| format!("invalid repetition '{{{body}}}'"))
+    };
+
+    let (min, max) = match body.split_once(',') {
+        Some((min, max)) => (parse(min)?, parse(max)?),
+        None => (parse(&body)?, parse(&body)?),
+    };
+
+    if max < min {
+        return Err(format!("invalid repetition '{{{body}}}'"));
+    }
+
+    Ok(min..=max)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn parsing() {
+        let pattern: Pattern = "a[x-z\\]-]{0,2}b".parse().unwrap();
+        assert_eq!(pattern.segments.len(), 3);
+        assert_eq!(
+            pattern.segments[1].charset.chars(),
+            &['-', ']', 'x', 'y', 'z']
+        );
+        assert_eq!(pattern.segments[1].repeat, 0..=2);
+        assert_eq!(pattern.length(), 2..=4);
+
+        let pattern: Pattern = "[b-df-hj-np-tv-xz]".parse().unwrap();
+        assert_eq!(pattern.segments[0].charset, Charset::lower_consonants());
+
+        for invalid in ["", "[", "[]", "[a-", "a{", "a{2,1}", "a{x}", "}", "\\"] {
+            assert!(
+                matches!(
+                    invalid.parse::<Pattern>(),
+                    Err(TokenError::InvalidPattern(_))
+                ),
+                "{invalid:?} should not parse"
+            );
+        }
+    }
+
+    #[test]
+    fn sampling() {
+        let mut rng = StdRng::seed_from_u64(50);
+        let pattern: Pattern = "[a-z]{2,3}-[0-9a-f]{4}".parse().unwrap();
+
+        for _ in 0..100 {
+            let token = pattern.sample(&mut rng).unwrap();
+            assert!(pattern.matches(&token), "{token}");
+            assert!(pattern.length().contains(&token.len()));
+        }
+
+        assert!(!pattern.matches("ab-12345"));
+        assert!(!pattern.matches("a-1234"));
+        assert_eq!(
+            pattern.capacity(),
+            Some((26 * 26 + 26 * 26 * 26) * 16_u128.pow(4))
+        );
+    }
+
+    #[test]
+    fn optional_segments() {
+        let pattern: Pattern = "a{0,1}a{1,2}".parse().unwrap();
+        assert!(pattern.matches("a"));
+        assert!(pattern.matches("aaa"));
+        assert!(!pattern.matches(""));
+        assert!(!pattern.matches("aaaa"));
+
+        let mut tokens = pattern.expand();
+        tokens.sort();
+        tokens.dedup();
+        assert_eq!(tokens, vec!["a", "aa", "aaa"]);
+    }
+}
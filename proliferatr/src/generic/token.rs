@@ -1,20 +1,52 @@
+use std::{
+    collections::HashSet,
+    ops::{Bound, RangeBounds, RangeInclusive},
+};
+
 use derive_builder::Builder;
-use rand::{distributions::Uniform, prelude::Distribution, seq::SliceRandom};
+use rand::{distributions::Uniform, prelude::Distribution, Rng};
 use thiserror::Error;
 
 use crate::InputGenerator;
 
+use super::{
+    pattern::{capacity, expand, push_chars},
+    Blocklist, Charset, Pattern,
+};
+
+/// The number of consecutive blocked tokens tolerated before giving up.
+const MAX_BLOCKED: usize = 1000;
+
 pub const LOWER_ALPHA_CHARS: &[u8] = b"bcdfghjklmnpqrstvwxz";
 pub const UPPER_ALPHA_CHARS: &[u8] = b"BCDFGHJKLMNPQRSTVWXZ";
 
-#[derive(Debug, Clone, Error)]
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
 pub enum TokenError {
     #[error("Failed to select a character.")]
     FailedToSelectCharacter,
+
+    #[error("Gave up after {0} consecutive duplicate or forbidden tokens.")]
+    Exhausted(usize),
+
+    #[error("Requested {requested} unique tokens, but at most {available} can be made.")]
+    InsufficientCapacity { requested: usize, available: u128 },
+
+    #[error("Invalid charset: {0}.")]
+    InvalidCharset(String),
+
+    #[error("Invalid token pattern: {0}.")]
+    InvalidPattern(String),
 }
 
 /// A type that can generate a random token string.
 ///
+/// Tokens are either made of characters from the `charset`, with a length in
+/// the `length` range, or match the `pattern` if one is set.
+///
+/// Tokens containing a word from the `blocklist` are regenerated. By default
+/// this is the [embedded](Blocklist::embedded) blocklist, and it can be turned
+/// off by setting it to `None`.
+///
 /// # Examples
 /// ```
 /// use proliferatr::generic::StringToken;
@@ -28,29 +60,195 @@ pub enum TokenError {
 /// // LOWER_ALPHA_CHARS constant.
 /// assert_eq!(generator, StringToken::default());
 /// ```
-#[derive(Debug, Clone, PartialEq, Builder)]
-pub struct StringToken<'a> {
-    #[builder(setter(into))]
-    length: Uniform<usize>,
-    charset: &'a [u8],
+///
+/// Using a [Pattern]:
+/// ```
+/// use rand::thread_rng;
+/// use proliferatr::generic::StringToken;
+///
+/// let generator = StringToken::builder()
+///     .pattern("[A-Z]{2}[0-9]".parse().unwrap())
+///     .build()
+///     .unwrap();
+///
+/// let token = generator.generate(&mut thread_rng()).unwrap();
+/// assert_eq!(token.len(), 3);
+/// assert!(generator.can_generate(&token));
+/// assert!(!generator.can_generate("ABC"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct StringToken {
+    #[builder(default = "2..=3", setter(custom))]
+    length: RangeInclusive<usize>,
+
+    #[builder(default, setter(into))]
+    charset: Charset,
+
+    /// Replaces the `length` and `charset` when set.
+    #[builder(default, setter(strip_option))]
+    pattern: Option<Pattern>,
+
+    #[builder(default = "Some(Blocklist::embedded().clone())", setter(into))]
+    blocklist: Option<Blocklist>,
+}
+
+impl StringTokenBuilder {
+    /// Set the range of lengths of the generated tokens.
+    pub fn length<T: RangeBounds<usize>>(&mut self, length: T) -> &mut Self {
+        let start = match length.start_bound() {
+            Bound::Included(start) => *start,
+            Bound::Excluded(start) => start.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+        let end = match length.end_bound() {
+            Bound::Included(end) => Some(*end),
+            Bound::Excluded(end) => end.checked_sub(1),
+            Bound::Unbounded => Some(usize::MAX),
+        };
+
+        self.length = Some(match end {
+            Some(end) => start..=end,
+            // an empty range that is caught by validation
+            None => RangeInclusive::new(1, 0),
+        });
+        self
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if matches!(self.pattern, Some(Some(_))) {
+            if self.length.is_some() || self.charset.is_some() {
+                return Err("Set either a pattern, or a length and charset, not both.".into());
+            }
+
+            return Ok(());
+        }
+
+        if self.length.as_ref().map(|l| l.is_empty()) == Some(true) {
+            return Err("length must not be an empty range.".into());
+        }
+
+        if self.charset.as_ref().map(|c| c.is_empty()) == Some(true) {
+            return Err("charset must not be empty.".into());
+        }
+
+        Ok(())
+    }
 }
 
-impl<'a> Default for StringToken<'a> {
+impl Default for StringToken {
     fn default() -> Self {
         Self {
-            length: (2..4).into(),
-            charset: LOWER_ALPHA_CHARS,
+            length: 2..=3,
+            charset: Charset::from(LOWER_ALPHA_CHARS),
+            pattern: None,
+            blocklist: Some(Blocklist::embedded().clone()),
         }
     }
 }
 
-impl<'a> StringToken<'a> {
-    pub fn builder() -> StringTokenBuilder<'a> {
+impl StringToken {
+    pub fn builder() -> StringTokenBuilder {
         StringTokenBuilder::default()
     }
+
+    /// The range of lengths of the generated tokens.
+    pub fn length(&self) -> RangeInclusive<usize> {
+        match &self.pattern {
+            Some(pattern) => pattern.length(),
+            None => self.length.clone(),
+        }
+    }
+
+    /// The characters the generated tokens are made from, which is not used
+    /// if there is a pattern.
+    pub fn charset(&self) -> &Charset {
+        &self.charset
+    }
+
+    pub fn pattern(&self) -> Option<&Pattern> {
+        self.pattern.as_ref()
+    }
+
+    /// Generate a new token.
+    pub fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<String, TokenError> {
+        let length = Uniform::from(self.length.clone());
+
+        for _ in 0..=MAX_BLOCKED {
+            let token = match &self.pattern {
+                Some(pattern) => pattern.sample(rng)?,
+                None => {
+                    let mut token = String::new();
+                    push_chars(&self.charset, length.sample(rng), rng, &mut token)?;
+                    token
+                }
+            };
+
+            if self.blocklist.as_ref().map_or(true, |b| b.is_clean(&token)) {
+                return Ok(token);
+            }
+        }
+
+        Err(TokenError::Exhausted(MAX_BLOCKED))
+    }
+
+    /// The number of distinct tokens that can be made from the charset, or
+    /// `None` if that does not fit in a `u128`. This includes tokens that
+    /// would be rejected by the blocklist.
+    ///
+    /// # Examples
+    /// ```
+    /// use proliferatr::generic::StringToken;
+    ///
+    /// let token = StringToken::builder()
+    ///     .length(1..=2)
+    ///     .charset(b"abc")
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(token.capacity(), Some(3 + 9));
+    /// ```
+    pub fn capacity(&self) -> Option<u128> {
+        match &self.pattern {
+            Some(pattern) => pattern.capacity(),
+            None => capacity(&self.charset, &self.length),
+        }
+    }
+
+    /// Every token that can be generated, leaving out those rejected by the
+    /// blocklist, or `None` if the [capacity](StringToken::capacity) is more
+    /// than `limit`.
+    pub(super) fn all_tokens(&self, limit: u128) -> Option<HashSet<String>> {
+        if self.capacity()? > limit {
+            return None;
+        }
+
+        let tokens = match &self.pattern {
+            Some(pattern) => pattern.expand(),
+            None => expand(vec![String::new()], &self.charset, &self.length),
+        };
+
+        Some(
+            tokens
+                .into_iter()
+                .filter(|t| self.blocklist.as_ref().map_or(true, |b| b.is_clean(t)))
+                .collect(),
+        )
+    }
+
+    /// Check whether `token` is one of the tokens that can be generated.
+    pub fn can_generate(&self, token: &str) -> bool {
+        match &self.pattern {
+            Some(pattern) => pattern.matches(token),
+            None => {
+                self.length.contains(&token.chars().count())
+                    && token.chars().all(|ch| self.charset.contains(ch))
+            }
+        }
+    }
 }
 
-impl<'a> InputGenerator for StringToken<'a> {
+impl InputGenerator for StringToken {
     type GeneratorError = TokenError;
     type Output = String;
 
@@ -58,15 +256,72 @@ impl<'a> InputGenerator for StringToken<'a> {
         &self,
         rng: &mut R,
     ) -> Result<Self::Output, Self::GeneratorError> {
-        let len = self.length.sample(rng);
-        (0..len)
-            .map(|_| {
-                self.charset
-                    .choose(rng)
-                    .copied()
-                    .map(|v| v as char)
-                    .ok_or(TokenError::FailedToSelectCharacter)
-            })
-            .collect::<Result<String, _>>()
+        self.generate(rng)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn blocked_tokens() {
+        let mut rng = StdRng::seed_from_u64(49);
+        let blocklist = Blocklist::new(["bb"]);
+        let token = StringToken::builder()
+            .length(2..=2)
+            .charset(b"ab")
+            .blocklist(blocklist)
+            .build()
+            .unwrap();
+
+        for _ in 0..50 {
+            assert_ne!(token.generate(&mut rng).unwrap(), "bb");
+        }
+
+        let blocklist = Blocklist::new(["a", "b"]);
+        let token = StringToken::builder()
+            .length(1..=1)
+            .charset(b"ab")
+            .blocklist(blocklist)
+            .build()
+            .unwrap();
+        assert_eq!(
+            token.generate(&mut rng),
+            Err(TokenError::Exhausted(MAX_BLOCKED))
+        );
+
+        let token = StringToken::builder()
+            .length(1..=1)
+            .charset(b"a")
+            .blocklist(None)
+            .build()
+            .unwrap();
+        assert_eq!(token.generate(&mut rng).unwrap(), "a");
+    }
+
+    #[test]
+    fn patterns() {
+        let mut rng = StdRng::seed_from_u64(50);
+        let token = StringToken::builder()
+            .pattern("[a-z]{2}".parse().unwrap())
+            .build()
+            .unwrap();
+
+        assert_eq!(token.length(), 2..=2);
+        assert_eq!(token.capacity(), Some(26 * 26));
+        for _ in 0..50 {
+            let name = token.generate(&mut rng).unwrap();
+            assert!(token.can_generate(&name));
+        }
+
+        assert!(StringToken::builder()
+            .pattern("[a-z]".parse().unwrap())
+            .length(1..=1)
+            .build()
+            .is_err());
+        assert!(StringToken::builder().charset("").build().is_err());
     }
 }
@@ -0,0 +1,412 @@
+use std::collections::HashSet;
+
+use derive_builder::Builder;
+use rand::Rng;
+
+use crate::InputGenerator;
+
+use super::{StringToken, TokenError};
+
+/// The largest number of distinct tokens that are counted exactly by
+/// [UniqueTokens::available].
+const MAX_EXACT_COUNT: u128 = 1 << 16;
+
+/// Where a fixed affix is placed on a generated token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Affix<'a> {
+    None,
+    Prefix(&'a str),
+    Suffix(&'a str),
+}
+
+/// An allocator of unique tokens, generated by a [StringToken].
+///
+/// Every token is only handed out once. The `excluded` tokens are never
+/// handed out, and the `reserved` tokens are treated as if they were already
+/// handed out, which is useful for names with a special meaning that are
+/// added separately, like `in` or `AAA`.
+///
+/// Tokens starting with one of the `reserved_prefixes` or ending in one of
+/// the `reserved_suffixes` are kept out of the general pool, and are only
+/// made by [UniqueTokens::next_with_prefix] and
+/// [UniqueTokens::next_with_suffix], which replace the start or end of a
+/// generated token with the affix. This makes it possible to, for instance,
+/// have only the entry points of a network end in `A`.
+///
+/// Asking for more tokens than could possibly be generated fails without
+/// generating anything, and generation gives up after `max_collisions`
+/// consecutive tokens that could not be used. A token with a reserved affix is
+/// only handed out when that affix was asked for.
+///
+/// # Examples
+/// ```
+/// use rand::thread_rng;
+/// use proliferatr::generic::{StringToken, UniqueTokens};
+///
+/// let token = StringToken::builder()
+///     .length(3..=3)
+///     .charset(b"ABCZ")
+///     .build()
+///     .unwrap();
+///
+/// let mut tokens = UniqueTokens::builder()
+///     .token(token)
+///     .reserve("AAA")
+///     .reserve("ZZZ")
+///     .reserve_suffix("A")
+///     .reserve_suffix("Z")
+///     .build()
+///     .unwrap();
+///
+/// let rng = &mut thread_rng();
+/// let start = tokens.next_with_suffix("A", rng).unwrap();
+/// assert!(start.ends_with('A'));
+/// assert_ne!(start, "AAA");
+///
+/// let names = tokens.take(10, rng).unwrap();
+/// assert!(names.iter().all(|n| !n.ends_with('A') && !n.ends_with('Z')));
+///
+/// // only 32 of the 64 tokens made from 4 characters don't end in A or Z
+/// assert_eq!(tokens.available(), 32 - 10);
+/// assert!(tokens.take(23, rng).is_err());
+/// assert_eq!(tokens.available(), 22);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct UniqueTokens {
+    #[builder(default)]
+    token: StringToken,
+
+    #[builder(default, setter(each(name = "exclude", into)))]
+    excluded: Vec<String>,
+
+    #[builder(default, setter(each(name = "reserve", into)))]
+    reserved: Vec<String>,
+
+    #[builder(default, setter(each(name = "reserve_prefix", into)))]
+    reserved_prefixes: Vec<String>,
+
+    #[builder(default, setter(each(name = "reserve_suffix", into)))]
+    reserved_suffixes: Vec<String>,
+
+    #[builder(default = "1000")]
+    max_collisions: usize,
+
+    #[builder(setter(skip))]
+    used: HashSet<String>,
+}
+
+impl UniqueTokensBuilder {
+    fn validate(&self) -> Result<(), String> {
+        let affixes = self
+            .reserved_prefixes
+            .iter()
+            .chain(self.reserved_suffixes.iter())
+            .flatten();
+
+        for affix in affixes {
+            if affix.is_empty() {
+                return Err("Reserved prefixes and suffixes must not be empty.".into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl UniqueTokens {
+    pub fn builder() -> UniqueTokensBuilder {
+        UniqueTokensBuilder::default()
+    }
+
+    /// Check whether `token` has been handed out, or is reserved or excluded.
+    pub fn is_taken(&self, token: &str) -> bool {
+        self.used.contains(token)
+            || self.reserved.iter().any(|t| t == token)
+            || self.excluded.iter().any(|t| t == token)
+    }
+
+    /// Mark a specific `token` as handed out, returning `false` if it was
+    /// already taken.
+    pub fn claim(&mut self, token: impl Into<String>) -> bool {
+        let token = token.into();
+        !self.is_taken(&token) && self.used.insert(token)
+    }
+
+    /// The number of tokens without a reserved affix that can still be handed
+    /// out.
+    ///
+    /// This is exact if the token can make at most 65536 distinct tokens.
+    /// Otherwise it's an upper bound, which does not account for the reserved
+    /// prefixes and suffixes or the blocklist.
+    pub fn available(&self) -> u128 {
+        if let Some(tokens) = self.token.all_tokens(MAX_EXACT_COUNT) {
+            return tokens
+                .iter()
+                .filter(|t| !self.is_taken(t) && !self.has_reserved_affix(t, Affix::None))
+                .count() as u128;
+        }
+
+        let taken: HashSet<&str> = self
+            .used
+            .iter()
+            .chain(self.reserved.iter())
+            .chain(self.excluded.iter())
+            .map(|t| t.as_str())
+            .filter(|t| self.token.can_generate(t))
+            .collect();
+
+        self.token
+            .capacity()
+            .unwrap_or(u128::MAX)
+            .saturating_sub(taken.len() as u128)
+    }
+
+    /// Hand out a new token that does not have a reserved prefix or suffix.
+    pub fn next<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Result<String, TokenError> {
+        self.next_with(Affix::None, rng)
+    }
+
+    /// Hand out a new token that starts with `prefix`.
+    pub fn next_with_prefix<R: Rng + ?Sized>(
+        &mut self,
+        prefix: &str,
+        rng: &mut R,
+    ) -> Result<String, TokenError> {
+        self.next_with(Affix::Prefix(prefix), rng)
+    }
+
+    /// Hand out a new token that ends with `suffix`.
+    pub fn next_with_suffix<R: Rng + ?Sized>(
+        &mut self,
+        suffix: &str,
+        rng: &mut R,
+    ) -> Result<String, TokenError> {
+        self.next_with(Affix::Suffix(suffix), rng)
+    }
+
+    /// Hand out `count` new tokens that do not have a reserved prefix or
+    /// suffix.
+    ///
+    /// Fails immediately if more tokens are requested than are
+    /// [available](UniqueTokens::available). If generation fails part way,
+    /// the tokens generated so far are not handed out.
+    pub fn take<R: Rng + ?Sized>(
+        &mut self,
+        count: usize,
+        rng: &mut R,
+    ) -> Result<Vec<String>, TokenError> {
+        let available = self.available();
+        if count as u128 > available {
+            return Err(TokenError::InsufficientCapacity {
+                requested: count,
+                available,
+            });
+        }
+
+        let mut tokens = Vec::with_capacity(count);
+        for _ in 0..count {
+            match self.next(rng) {
+                Ok(token) => tokens.push(token),
+                Err(e) => {
+                    for token in tokens.iter() {
+                        self.used.remove(token);
+                    }
+                    return Err(e);
+                }
+            }
+        }
+
+        Ok(tokens)
+    }
+
+    fn next_with<R: Rng + ?Sized>(
+        &mut self,
+        affix: Affix,
+        rng: &mut R,
+    ) -> Result<String, TokenError> {
+        let token = self.candidate(affix, rng)?;
+        self.used.insert(token.clone());
+        Ok(token)
+    }
+
+    /// Generate a token with the given `affix` that is not taken, without
+    /// handing it out.
+    fn candidate<R: Rng + ?Sized>(&self, affix: Affix, rng: &mut R) -> Result<String, TokenError> {
+        for _ in 0..=self.max_collisions {
+            let token = self.token.generate(rng)?;
+            let token = match affix {
+                Affix::None => token,
+                Affix::Prefix(prefix) => {
+                    let rest: String = token.chars().skip(prefix.chars().count()).collect();
+                    format!("{prefix}{rest}")
+                }
+                Affix::Suffix(suffix) => {
+                    let keep = token.chars().count().saturating_sub(suffix.chars().count());
+                    let rest: String = token.chars().take(keep).collect();
+                    format!("{rest}{suffix}")
+                }
+            };
+
+            if !self.has_reserved_affix(&token, affix) && !self.is_taken(&token) {
+                return Ok(token);
+            }
+        }
+
+        Err(TokenError::Exhausted(self.max_collisions))
+    }
+
+    /// Check whether `token` has a reserved prefix or suffix, other than
+    /// those that are part of the requested `affix`.
+    fn has_reserved_affix(&self, token: &str, affix: Affix) -> bool {
+        let prefixes = self.reserved_prefixes.iter().filter(|p| match affix {
+            Affix::Prefix(prefix) => !prefix.starts_with(p.as_str()),
+            _ => true,
+        });
+        let suffixes = self.reserved_suffixes.iter().filter(|s| match affix {
+            Affix::Suffix(suffix) => !suffix.ends_with(s.as_str()),
+            _ => true,
+        });
+
+        prefixes.map(String::as_str).any(|p| token.starts_with(p))
+            || suffixes.map(String::as_str).any(|s| token.ends_with(s))
+    }
+}
+
+impl InputGenerator for UniqueTokens {
+    type GeneratorError = TokenError;
+    type Output = String;
+
+    /// Generate a token that has not been handed out, without marking it as
+    /// handed out.
+    fn gen_input<R: Rng + Clone>(&self, rng: &mut R) -> Result<Self::Output, Self::GeneratorError> {
+        self.candidate(Affix::None, rng)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::generic::Blocklist;
+
+    use super::*;
+
+    fn token(charset: &[u8], length: usize) -> StringToken {
+        StringToken::builder()
+            .length(length..=length)
+            .charset(charset)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn exhaustion() {
+        let mut rng = StdRng::seed_from_u64(8);
+        let mut tokens = UniqueTokens::builder()
+            .token(token(b"ab", 2))
+            .exclude("aa")
+            .reserve("bb")
+            .build()
+            .unwrap();
+
+        assert_eq!(tokens.available(), 2);
+        assert_eq!(
+            tokens.take(3, &mut rng),
+            Err(TokenError::InsufficientCapacity {
+                requested: 3,
+                available: 2
+            })
+        );
+
+        let mut names = tokens.take(2, &mut rng).unwrap();
+        names.sort();
+        assert_eq!(names, vec!["ab", "ba"]);
+        assert_eq!(tokens.available(), 0);
+        assert_eq!(tokens.next(&mut rng), Err(TokenError::Exhausted(1000)));
+
+        assert!(!tokens.claim("ab"));
+        assert!(tokens.claim("abc"));
+        assert!(tokens.is_taken("abc"));
+    }
+
+    #[test]
+    fn affixes() {
+        let mut rng = StdRng::seed_from_u64(8);
+        let mut tokens = UniqueTokens::builder()
+            .token(token(b"abc", 3))
+            .reserve_prefix("a")
+            .reserve_suffix("bc")
+            .build()
+            .unwrap();
+
+        // only three tokens end in "bc", and one of those starts with "a"
+        for _ in 0..2 {
+            let name = tokens.next_with_prefix("a", &mut rng).unwrap();
+            assert!(name.starts_with('a') && name.len() == 3);
+
+            let name = tokens.next_with_suffix("bc", &mut rng).unwrap();
+            assert!(name.ends_with("bc") && name.len() == 3);
+
+            let name = tokens.next(&mut rng).unwrap();
+            assert!(!name.starts_with('a') && !name.ends_with("bc"));
+        }
+
+        assert!(UniqueTokens::builder().reserve_suffix("").build().is_err());
+    }
+
+    #[test]
+    fn other_affixes() {
+        let mut rng = StdRng::seed_from_u64(8);
+        let mut tokens = UniqueTokens::builder()
+            .token(token(b"ab", 2))
+            .reserve_prefix("a")
+            .reserve_suffix("b")
+            .build()
+            .unwrap();
+
+        // "ab" would also end in the reserved "b"
+        assert_eq!(tokens.next_with_prefix("a", &mut rng).unwrap(), "aa");
+        assert_eq!(tokens.next_with_suffix("b", &mut rng).unwrap(), "bb");
+        assert_eq!(tokens.next(&mut rng).unwrap(), "ba");
+        assert_eq!(
+            tokens.next_with_prefix("a", &mut rng),
+            Err(TokenError::Exhausted(1000))
+        );
+    }
+
+    #[test]
+    fn exact_availability() {
+        let mut rng = StdRng::seed_from_u64(8);
+        let mut tokens = UniqueTokens::builder()
+            .token(token(b"abc", 3))
+            .reserve_suffix("c")
+            .build()
+            .unwrap();
+
+        assert_eq!(tokens.available(), 18);
+        assert!(matches!(
+            tokens.take(20, &mut rng),
+            Err(TokenError::InsufficientCapacity { .. })
+        ));
+
+        // nothing is handed out when generation fails part way
+        tokens.max_collisions = 0;
+        assert_eq!(tokens.take(18, &mut rng), Err(TokenError::Exhausted(0)));
+        assert_eq!(tokens.available(), 18);
+
+        let blocklist = Blocklist::new(["ab"]);
+        let tokens = UniqueTokens::builder()
+            .token(
+                StringToken::builder()
+                    .length(2..=2)
+                    .charset(b"ab")
+                    .blocklist(blocklist)
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap();
+        assert_eq!(tokens.available(), 3);
+    }
+}
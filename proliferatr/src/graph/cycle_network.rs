@@ -0,0 +1,423 @@
+use std::collections::HashMap;
+
+use derive_builder::Builder;
+use rand::{seq::SliceRandom, Rng};
+use thiserror::Error;
+
+use crate::{
+    generic::{Charset, StringToken, TokenError, UniqueTokens},
+    InputGenerator,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum CycleNetworkError {
+    #[error(transparent)]
+    Token(#[from] TokenError),
+
+    #[error("Expected {start} to reach {end} every {expected} steps, but it did not.")]
+    CycleMismatch {
+        start: String,
+        end: String,
+        expected: usize,
+    },
+}
+
+/// A node in a [CycleNetworkGraph], with the names of the nodes reached by
+/// following the `L` and `R` instructions.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ChoiceNode {
+    pub name: String,
+    pub left: String,
+    pub right: String,
+}
+
+/// A network made by [CycleNetwork].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleNetworkGraph {
+    /// Every node of the network, in a random order.
+    pub nodes: Vec<ChoiceNode>,
+
+    /// The names of the start and end node of each cycle, in the order the
+    /// cycles were given.
+    pub endpoints: Vec<(String, String)>,
+
+    /// The number of steps between each start node and its end node, which is
+    /// also the number of steps between visits to the end node.
+    pub cycle_lengths: Vec<usize>,
+}
+
+impl CycleNetworkGraph {
+    /// The number of steps until every start node simultaneously reaches an
+    /// end node, which is the least common multiple of the cycle lengths.
+    pub fn answer(&self) -> usize {
+        self.cycle_lengths
+            .iter()
+            .fold(1, |acc, len| num::integer::lcm(acc, *len))
+    }
+}
+
+/// Generates networks of nodes with left and right exits, where following a
+/// given sequence of `L`/`R` `instructions` (repeating as needed) from each
+/// start node reaches a matching end node after a chosen number of steps, and
+/// then returns to that end node with the same period.
+///
+/// Each of the `cycles` is a loop of that many layers of two nodes, where the
+/// left node of a layer is reached by an `L` and the right node by an `R`.
+/// Every step advances one layer, regardless of the instruction, and the end
+/// node is the right node of the last layer. The left nodes of the
+/// `key_length - 1` layers before the last one lead only to left nodes, so the
+/// end node can only be reached by a run of `key_length` `R` instructions.
+/// The instructions must end with such a run, and have no other, so the end
+/// node of a cycle of `N` layers is reached exactly every `lcm(N, K)` steps,
+/// where `K` is the number of instructions.
+///
+/// Start node names end with `start_suffix`, end node names end with
+/// `end_suffix`, and the other names are made from the `charset`, which may
+/// not contain either suffix. The first cycles can be given fixed start and
+/// end names with `named_cycle`. After generating, every cycle is verified by
+/// following the instructions.
+///
+/// # Examples
+/// ```
+/// use rand::thread_rng;
+/// use proliferatr::graph::CycleNetwork;
+///
+/// let network = CycleNetwork::builder()
+///     .instructions("LRLLRRLRRRR")
+///     .cycle(5)
+///     .cycle(7)
+///     .named_cycle(("AAA".into(), "ZZZ".into()))
+///     .build()
+///     .unwrap()
+///     .generate(&mut thread_rng())
+///     .unwrap();
+///
+/// assert_eq!(network.cycle_lengths, vec![55, 77]);
+/// assert_eq!(network.endpoints[0], ("AAA".into(), "ZZZ".into()));
+/// assert_eq!(network.answer(), 385);
+///
+/// let starts = network.nodes.iter().filter(|n| n.name.ends_with('A')).count();
+/// assert_eq!(starts, 2);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct CycleNetwork {
+    #[builder(setter(into))]
+    instructions: String,
+
+    #[builder(setter(each(name = "cycle")))]
+    cycles: Vec<usize>,
+
+    #[builder(default = "4")]
+    key_length: usize,
+
+    #[builder(default = "3")]
+    name_length: usize,
+
+    #[builder(default = "Charset::from(b\"BCDFGHJKLMNPQRSTVWX\")", setter(into))]
+    charset: Charset,
+
+    #[builder(default = "'A'")]
+    start_suffix: char,
+
+    #[builder(default = "'Z'")]
+    end_suffix: char,
+
+    #[builder(default, setter(each(name = "named_cycle")))]
+    named: Vec<(String, String)>,
+}
+
+impl CycleNetworkBuilder {
+    fn validate(&self) -> Result<(), String> {
+        let key_length = self.key_length.unwrap_or(4);
+        if key_length == 0 {
+            return Err("key_length must be greater than 0.".into());
+        }
+
+        if let Some(instructions) = &self.instructions {
+            if instructions.chars().any(|ch| ch != 'L' && ch != 'R') {
+                return Err("instructions may only contain 'L' and 'R'.".into());
+            }
+
+            if !instructions.starts_with('L') {
+                return Err("instructions must start with 'L'.".into());
+            }
+
+            let runs: Vec<usize> = instructions
+                .split('L')
+                .map(|run| run.len())
+                .filter(|len| *len > 0)
+                .collect();
+            let ends_with_key = instructions.len() > key_length
+                && instructions.ends_with(&"R".repeat(key_length))
+                && runs.last() == Some(&key_length);
+
+            if !ends_with_key || runs.iter().filter(|len| **len >= key_length).count() != 1 {
+                return Err(format!(
+                    "instructions must end with the only run of {key_length} or more 'R's."
+                ));
+            }
+        }
+
+        if let Some(cycles) = &self.cycles {
+            if cycles.is_empty() || cycles.iter().any(|c| *c <= key_length) {
+                return Err("Every cycle must be longer than key_length.".into());
+            }
+
+            if self.named.as_ref().map(|n| n.len()).unwrap_or_default() > cycles.len() {
+                return Err("There are more named cycles than cycles.".into());
+            }
+        }
+
+        let start_suffix = self.start_suffix.unwrap_or('A');
+        let end_suffix = self.end_suffix.unwrap_or('Z');
+
+        if start_suffix == end_suffix {
+            return Err("start_suffix and end_suffix must differ.".into());
+        }
+
+        if self.name_length == Some(0) {
+            return Err("name_length must be greater than 0.".into());
+        }
+
+        if let Some(charset) = &self.charset {
+            if charset.is_empty() || charset.contains(start_suffix) || charset.contains(end_suffix)
+            {
+                return Err("charset must not be empty or contain either suffix.".into());
+            }
+        }
+
+        for (start, end) in self.named.iter().flatten() {
+            if !start.ends_with(start_suffix) || !end.ends_with(end_suffix) {
+                return Err(format!("{start} and {end} do not have the right suffixes."));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl CycleNetwork {
+    pub fn builder() -> CycleNetworkBuilder {
+        CycleNetworkBuilder::default()
+    }
+
+    /// Generate a new network.
+    pub fn generate<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> Result<CycleNetworkGraph, CycleNetworkError> {
+        let token = StringToken::builder()
+            .length(self.name_length..=self.name_length)
+            .charset(self.charset.clone())
+            .build()
+            .expect("name_length is validated");
+        let mut names = UniqueTokens::builder()
+            .token(token)
+            .reserved(
+                self.named
+                    .iter()
+                    .flat_map(|(start, end)| [start.clone(), end.clone()])
+                    .collect(),
+            )
+            .reserve_suffix(self.start_suffix)
+            .reserve_suffix(self.end_suffix)
+            .build()
+            .expect("reserved suffixes are not empty");
+        let mut nodes = Vec::new();
+        let mut endpoints = Vec::with_capacity(self.cycles.len());
+
+        for (i, layers) in self.cycles.iter().enumerate() {
+            let (start, end) = match self.named.get(i) {
+                Some(names) => names.clone(),
+                None => (
+                    names.next_with_suffix(&self.start_suffix.to_string(), rng)?,
+                    names.next_with_suffix(&self.end_suffix.to_string(), rng)?,
+                ),
+            };
+
+            self.make_cycle(*layers, &start, &end, &mut names, &mut nodes, rng)?;
+            endpoints.push((start, end));
+        }
+
+        nodes.shuffle(rng);
+
+        let cycle_lengths = self
+            .cycles
+            .iter()
+            .map(|layers| num::integer::lcm(*layers, self.instructions.len()))
+            .collect();
+
+        let network = CycleNetworkGraph {
+            nodes,
+            endpoints,
+            cycle_lengths,
+        };
+        self.verify(&network)?;
+
+        Ok(network)
+    }
+
+    /// Add the start node and the layers of a single cycle to `nodes`.
+    fn make_cycle<R: Rng + ?Sized>(
+        &self,
+        layers: usize,
+        start: &str,
+        end: &str,
+        tokens: &mut UniqueTokens,
+        nodes: &mut Vec<ChoiceNode>,
+        rng: &mut R,
+    ) -> Result<(), CycleNetworkError> {
+        let mut names = Vec::with_capacity(layers);
+        for layer in 0..layers {
+            let left = tokens.next(rng)?;
+            let right = if layer == layers - 1 {
+                end.to_string()
+            } else {
+                tokens.next(rng)?
+            };
+            names.push((left, right));
+        }
+
+        nodes.push(ChoiceNode {
+            name: start.to_string(),
+            left: names[0].0.clone(),
+            right: names[0].1.clone(),
+        });
+
+        for (layer, (left, right)) in names.iter().enumerate() {
+            let (next_left, next_right) = &names[(layer + 1) % layers];
+            let funnel = layer + self.key_length >= layers && layer + 1 < layers;
+
+            nodes.push(ChoiceNode {
+                name: left.clone(),
+                left: next_left.clone(),
+                right: if funnel { next_left } else { next_right }.clone(),
+            });
+            nodes.push(ChoiceNode {
+                name: right.clone(),
+                left: next_left.clone(),
+                right: next_right.clone(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Follow the instructions from every start node for two cycles, making
+    /// sure the only nodes ending in the end suffix are visited exactly when
+    /// expected.
+    fn verify(&self, network: &CycleNetworkGraph) -> Result<(), CycleNetworkError> {
+        let lookup: HashMap<&str, &ChoiceNode> =
+            network.nodes.iter().map(|n| (n.name.as_str(), n)).collect();
+        let instructions = self.instructions.as_bytes();
+
+        for ((start, end), expected) in network.endpoints.iter().zip(&network.cycle_lengths) {
+            let mismatch = || CycleNetworkError::CycleMismatch {
+                start: start.clone(),
+                end: end.clone(),
+                expected: *expected,
+            };
+
+            let mut cur = lookup.get(start.as_str()).ok_or_else(mismatch)?;
+            for step in 1..=(expected * 2) {
+                let next = if instructions[(step - 1) % instructions.len()] == b'L' {
+                    &cur.left
+                } else {
+                    &cur.right
+                };
+                cur = lookup.get(next.as_str()).ok_or_else(mismatch)?;
+
+                let at_end = cur.name.ends_with(self.end_suffix);
+                if at_end != (step % expected == 0) || (at_end && cur.name != *end) {
+                    return Err(mismatch());
+                }
+
+                if cur.name.ends_with(self.start_suffix) {
+                    return Err(mismatch());
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl InputGenerator for CycleNetwork {
+    type GeneratorError = CycleNetworkError;
+    type Output = CycleNetworkGraph;
+
+    fn gen_input<R: Rng + Clone>(&self, rng: &mut R) -> Result<Self::Output, Self::GeneratorError> {
+        self.generate(rng)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn cycles() {
+        let network = CycleNetwork::builder()
+            .instructions("LLRLRLLRR")
+            .cycles(vec![4, 6, 9, 3])
+            .key_length(2)
+            .charset(b"bcdfg")
+            .build()
+            .unwrap()
+            .generate(&mut StdRng::seed_from_u64(8))
+            .unwrap();
+
+        // 22 layers of 2 nodes, plus the start nodes
+        assert_eq!(network.nodes.len(), 48);
+        assert_eq!(network.cycle_lengths, vec![36, 18, 9, 9]);
+        assert_eq!(network.answer(), 36);
+
+        let names: HashSet<_> = network.nodes.iter().map(|n| &n.name).collect();
+        assert_eq!(names.len(), 48);
+
+        let ends: Vec<_> = network
+            .nodes
+            .iter()
+            .filter(|n| n.name.ends_with('Z'))
+            .collect();
+        assert_eq!(ends.len(), 4);
+    }
+
+    #[test]
+    fn validation() {
+        let valid = || {
+            let mut builder = CycleNetwork::builder();
+            builder.instructions("LRLRRRR").cycle(5);
+            builder
+        };
+
+        assert!(valid().build().is_ok());
+        assert!(valid().instructions("LRRRRLRRRR").build().is_err());
+        assert!(valid().instructions("RLRLRRRR").build().is_err());
+        assert!(valid().instructions("LRLRRRRR").build().is_err());
+        assert!(valid().instructions("LRLRRR").build().is_err());
+        assert!(valid().instructions("LRXRRRR").build().is_err());
+        assert!(valid().cycle(4).build().is_err());
+        assert!(valid().charset(b"BCA").build().is_err());
+        assert!(valid()
+            .named_cycle(("AAB".into(), "ZZZ".into()))
+            .build()
+            .is_err());
+
+        let exhausted = valid()
+            .name_length(1)
+            .charset(b"B")
+            .build()
+            .unwrap()
+            .generate(&mut StdRng::seed_from_u64(1));
+        assert_eq!(
+            exhausted,
+            Err(CycleNetworkError::Token(TokenError::Exhausted(1000)))
+        );
+    }
+}
@@ -0,0 +1,257 @@
+use derive_builder::Builder;
+use rand::{seq::SliceRandom, Rng};
+
+use crate::InputGenerator;
+
+use super::{Graph, GraphError, NodeId};
+
+/// Generates random graphs in the Erdős–Rényi model, where every possible
+/// edge between `nodes` nodes exists with the same `probability`.
+///
+/// Edges are considered in a random order, and an edge is skipped if either
+/// of its nodes already has `max_degree` edges. Afterwards, random edges are
+/// added to any node with fewer than `min_degree` edges and, if `connected`
+/// is set, between random nodes of separate components until the graph is
+/// connected.
+///
+/// # Examples
+/// ```
+/// use rand::thread_rng;
+/// use proliferatr::graph::ErdosRenyi;
+///
+/// let graph = ErdosRenyi::builder()
+///     .nodes(200)
+///     .probability(0.01)
+///     .min_degree(2)
+///     .max_degree(6)
+///     .connected(true)
+///     .build()
+///     .unwrap()
+///     .generate(&mut thread_rng())
+///     .unwrap();
+///
+/// assert!(graph.is_connected());
+/// assert!(graph.nodes().all(|id| (2..=6).contains(&graph.degree(id))));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct ErdosRenyi {
+    nodes: usize,
+
+    probability: f64,
+
+    #[builder(default)]
+    directed: bool,
+
+    #[builder(default)]
+    min_degree: usize,
+
+    #[builder(default, setter(strip_option))]
+    max_degree: Option<usize>,
+
+    #[builder(default)]
+    connected: bool,
+}
+
+impl ErdosRenyiBuilder {
+    fn validate(&self) -> Result<(), String> {
+        if let Some(p) = self.probability {
+            if !(0.0..=1.0).contains(&p) {
+                return Err("probability must be between 0 and 1.".into());
+            }
+        }
+
+        let min_degree = self.min_degree.unwrap_or_default();
+        let max_degree = self.max_degree.flatten().unwrap_or(usize::MAX);
+
+        if min_degree > max_degree {
+            return Err("min_degree must not be greater than max_degree.".into());
+        }
+
+        if let Some(nodes) = self.nodes {
+            if nodes > 0 && min_degree >= nodes {
+                return Err("min_degree must be less than the number of nodes.".into());
+            }
+
+            if self.connected == Some(true) && nodes > 2 && max_degree < 2 {
+                return Err("A connected graph needs a max_degree of at least 2.".into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl ErdosRenyi {
+    pub fn builder() -> ErdosRenyiBuilder {
+        ErdosRenyiBuilder::default()
+    }
+
+    /// Generate a new graph, with nodes named after their index.
+    ///
+    /// Fails if the degree constraints cannot be met by the edges chosen so
+    /// far.
+    pub fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<Graph, GraphError> {
+        let mut graph = Graph::with_nodes(self.nodes, self.directed);
+        let max_degree = self.max_degree.unwrap_or(usize::MAX);
+
+        let mut pairs: Vec<(NodeId, NodeId)> = (0..self.nodes)
+            .flat_map(|a| (0..self.nodes).map(move |b| (a, b)))
+            .filter(|(a, b)| if self.directed { a != b } else { a < b })
+            .collect();
+        pairs.shuffle(rng);
+
+        for (a, b) in pairs {
+            if rng.gen_bool(self.probability)
+                && graph.degree(a) < max_degree
+                && graph.degree(b) < max_degree
+            {
+                graph.add_edge(a, b)?;
+            }
+        }
+
+        let mut order: Vec<NodeId> = graph.nodes().collect();
+        order.shuffle(rng);
+
+        for id in order {
+            while graph.degree(id) < self.min_degree {
+                let other = graph
+                    .nodes()
+                    .filter(|other| {
+                        *other != id
+                            && graph.degree(*other) < max_degree
+                            && !graph.has_edge(id, *other)
+                            && !graph.has_edge(*other, id)
+                    })
+                    .collect::<Vec<_>>()
+                    .choose(rng)
+                    .copied()
+                    .ok_or(GraphError::DegreeConstraint(id))?;
+
+                self.join(&mut graph, id, other, rng)?;
+            }
+        }
+
+        if self.connected {
+            let components = graph.components();
+            for (i, component) in components.iter().enumerate().skip(1) {
+                let open = |nodes: &[NodeId]| {
+                    nodes
+                        .iter()
+                        .copied()
+                        .filter(|id| graph.degree(*id) < max_degree)
+                        .collect::<Vec<_>>()
+                };
+
+                let a = *open(component)
+                    .choose(rng)
+                    .ok_or(GraphError::DegreeConstraint(component[0]))?;
+                let b = *open(&components[..i].concat())
+                    .choose(rng)
+                    .ok_or(GraphError::DegreeConstraint(a))?;
+
+                self.join(&mut graph, a, b, rng)?;
+            }
+        }
+
+        Ok(graph)
+    }
+
+    /// Add an edge between `a` and `b`, in a random direction for directed
+    /// graphs.
+    fn join<R: Rng + ?Sized>(
+        &self,
+        graph: &mut Graph,
+        a: NodeId,
+        b: NodeId,
+        rng: &mut R,
+    ) -> Result<(), GraphError> {
+        if self.directed && rng.gen_bool(0.5) {
+            graph.add_edge(b, a)?;
+        } else {
+            graph.add_edge(a, b)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl InputGenerator for ErdosRenyi {
+    type GeneratorError = GraphError;
+    type Output = Graph;
+
+    fn gen_input<R: Rng + Clone>(&self, rng: &mut R) -> Result<Self::Output, Self::GeneratorError> {
+        self.generate(rng)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn density() {
+        let graph = ErdosRenyi::builder()
+            .nodes(100)
+            .probability(0.2)
+            .build()
+            .unwrap()
+            .generate(&mut StdRng::seed_from_u64(3))
+            .unwrap();
+
+        // 4950 possible edges
+        assert!((850..1130).contains(&graph.edge_count()));
+        assert!(!graph.is_directed());
+
+        let graph = ErdosRenyi::builder()
+            .nodes(20)
+            .probability(1.0)
+            .directed(true)
+            .build()
+            .unwrap()
+            .generate(&mut StdRng::seed_from_u64(3))
+            .unwrap();
+        assert_eq!(graph.edge_count(), 380);
+    }
+
+    #[test]
+    fn constraints() {
+        for seed in 0..5 {
+            let graph = ErdosRenyi::builder()
+                .nodes(50)
+                .probability(0.0)
+                .directed(true)
+                .min_degree(1)
+                .max_degree(3)
+                .connected(true)
+                .build()
+                .unwrap()
+                .generate(&mut StdRng::seed_from_u64(seed))
+                .unwrap();
+
+            assert!(graph.is_connected());
+            assert!(graph.nodes().all(|id| (1..=3).contains(&graph.degree(id))));
+        }
+
+        assert!(ErdosRenyi::builder()
+            .nodes(5)
+            .probability(0.5)
+            .min_degree(5)
+            .build()
+            .is_err());
+        assert!(ErdosRenyi::builder()
+            .nodes(5)
+            .probability(0.5)
+            .min_degree(3)
+            .max_degree(2)
+            .build()
+            .is_err());
+        assert!(ErdosRenyi::builder()
+            .nodes(5)
+            .probability(1.5)
+            .build()
+            .is_err());
+    }
+}
@@ -0,0 +1,189 @@
+use derive_builder::Builder;
+use itertools::Itertools;
+
+use super::Graph;
+
+/// Renders a [Graph] as lines of adjacency lists, where each line holds the
+/// name of a node, the `separator`, and the names of its neighbors joined by
+/// the `delimiter`.
+///
+/// By default, each edge of an undirected graph appears on the lines of both
+/// of its nodes. If `once` is set, it only appears on the line of the node it
+/// was added from. Lines of nodes without any listed neighbors are skipped
+/// unless `skip_empty` is unset.
+///
+/// # Examples
+/// ```
+/// use proliferatr::graph::{AdjacencyList, Graph};
+///
+/// let mut graph = Graph::new();
+/// let jqt = graph.add_node("jqt");
+/// let rhn = graph.add_node("rhn");
+/// let xhk = graph.add_node("xhk");
+/// graph.add_edge(jqt, rhn).unwrap();
+/// graph.add_edge(jqt, xhk).unwrap();
+/// graph.add_edge(xhk, rhn).unwrap();
+///
+/// let list = AdjacencyList::builder().once(true).build().unwrap();
+/// assert_eq!(list.render(&graph), "jqt: rhn xhk\nxhk: rhn");
+///
+/// let list = AdjacencyList::builder()
+///     .separator(" -> ")
+///     .delimiter(", ")
+///     .build()
+///     .unwrap();
+/// assert_eq!(
+///     list.render(&graph),
+///     "jqt -> rhn, xhk\nrhn -> jqt, xhk\nxhk -> jqt, rhn"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Builder)]
+pub struct AdjacencyList {
+    #[builder(default = "\": \".into()", setter(into))]
+    separator: String,
+
+    #[builder(default = "\" \".into()", setter(into))]
+    delimiter: String,
+
+    #[builder(default)]
+    once: bool,
+
+    #[builder(default = "true")]
+    skip_empty: bool,
+}
+
+impl Default for AdjacencyList {
+    fn default() -> Self {
+        Self {
+            separator: ": ".into(),
+            delimiter: " ".into(),
+            once: false,
+            skip_empty: true,
+        }
+    }
+}
+
+impl AdjacencyList {
+    pub fn builder() -> AdjacencyListBuilder {
+        AdjacencyListBuilder::default()
+    }
+
+    /// Render the `graph`, with one line per node, in the order of the nodes.
+    pub fn render(&self, graph: &Graph) -> String {
+        let mut lists = vec![Vec::new(); graph.node_count()];
+
+        if self.once || graph.is_directed() {
+            for edge in graph.edges() {
+                lists[edge.from].push(edge.to);
+            }
+        } else {
+            for (id, list) in lists.iter_mut().enumerate() {
+                list.extend_from_slice(graph.neighbors(id));
+            }
+        }
+
+        lists
+            .into_iter()
+            .enumerate()
+            .filter(|(_, list)| !(self.skip_empty && list.is_empty()))
+            .map(|(id, list)| {
+                format!(
+                    "{}{}{}",
+                    graph.name(id),
+                    self.separator,
+                    list.iter().map(|n| graph.name(*n)).join(&self.delimiter)
+                )
+            })
+            .join("\n")
+    }
+}
+
+impl Graph {
+    /// Render the graph in the Graphviz DOT language, which is useful for
+    /// visualizing generated graphs while debugging.
+    ///
+    /// # Examples
+    /// ```
+    /// use proliferatr::graph::Graph;
+    ///
+    /// let mut graph = Graph::new_directed();
+    /// let a = graph.add_node("in");
+    /// let b = graph.add_node("px");
+    /// graph.add_node("lone");
+    /// graph.add_edge(a, b).unwrap();
+    ///
+    /// let expected = [
+    ///     "digraph {",
+    ///     "    \"in\";",
+    ///     "    \"px\";",
+    ///     "    \"lone\";",
+    ///     "    \"in\" -> \"px\";",
+    ///     "}",
+    /// ]
+    /// .join("\n");
+    ///
+    /// assert_eq!(graph.to_dot(), expected);
+    /// ```
+    pub fn to_dot(&self) -> String {
+        let quote = |id| {
+            format!(
+                "\"{}\"",
+                self.name(id).replace('\\', "\\\\").replace('"', "\\\"")
+            )
+        };
+        let (kind, arrow) = if self.is_directed() {
+            ("digraph", "->")
+        } else {
+            ("graph", "--")
+        };
+
+        let nodes = self.nodes().map(|id| format!("    {};", quote(id)));
+        let edges = self
+            .edges()
+            .iter()
+            .map(|e| format!("    {} {} {};", quote(e.from), arrow, quote(e.to)));
+
+        std::iter::once(format!("{kind} {{"))
+            .chain(nodes)
+            .chain(edges)
+            .chain(std::iter::once("}".to_string()))
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn directed_lists() {
+        let mut graph = Graph::with_nodes(3, true);
+        graph.add_edge(2, 0).unwrap();
+        graph.add_edge(0, 1).unwrap();
+        graph.add_edge(2, 1).unwrap();
+
+        assert_eq!(AdjacencyList::default().render(&graph), "0: 1\n2: 0 1");
+
+        let list = AdjacencyList::builder().skip_empty(false).build().unwrap();
+        assert_eq!(list.render(&graph), "0: 1\n1: \n2: 0 1");
+    }
+
+    #[test]
+    fn dot_quoting() {
+        let mut graph = Graph::new();
+        let a = graph.add_node("say \"hi\"");
+        let b = graph.add_node("a\\b");
+        graph.add_edge(a, b).unwrap();
+
+        let expected = [
+            "graph {",
+            r#"    "say \"hi\"";"#,
+            r#"    "a\\b";"#,
+            r#"    "say \"hi\"" -- "a\\b";"#,
+            "}",
+        ]
+        .join("\n");
+
+        assert_eq!(graph.to_dot(), expected);
+    }
+}
@@ -0,0 +1,236 @@
+use std::collections::VecDeque;
+
+use super::{Graph, NodeId};
+
+/// A cut of a [Graph]: a set of nodes, and the number of edges between those
+/// nodes and the rest of the graph.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MinCut {
+    /// The number of edges crossing the cut.
+    pub size: usize,
+
+    /// The nodes on one side of the cut, in ascending order.
+    pub side: Vec<NodeId>,
+}
+
+/// A unit capacity flow network over the edges of a graph. Edge `e` becomes
+/// the arc `2 * e` and its reverse `2 * e + 1`.
+struct Network {
+    heads: Vec<NodeId>,
+    capacity: Vec<i32>,
+    flow: Vec<i32>,
+    arcs: Vec<Vec<usize>>,
+}
+
+impl Network {
+    fn new(graph: &Graph, directed: bool) -> Self {
+        let mut network = Self {
+            heads: Vec::with_capacity(graph.edge_count() * 2),
+            capacity: Vec::with_capacity(graph.edge_count() * 2),
+            flow: vec![0; graph.edge_count() * 2],
+            arcs: vec![Vec::new(); graph.node_count()],
+        };
+
+        for (idx, edge) in graph.edges().iter().enumerate() {
+            network.heads.extend([edge.to, edge.from]);
+            network.capacity.extend([1, i32::from(!directed)]);
+            network.arcs[edge.from].push(idx * 2);
+            network.arcs[edge.to].push(idx * 2 + 1);
+        }
+
+        network
+    }
+
+    fn reset(&mut self) {
+        self.flow.fill(0);
+    }
+
+    /// Push flow from `source` to `sink` along shortest augmenting paths until
+    /// there are none left or the flow reaches `limit`.
+    fn max_flow(&mut self, source: NodeId, sink: NodeId, limit: usize) -> usize {
+        let mut total = 0;
+
+        while total < limit {
+            let mut via = vec![usize::MAX; self.arcs.len()];
+            let mut seen = vec![false; self.arcs.len()];
+            seen[source] = true;
+            let mut queue = VecDeque::from([source]);
+
+            while let Some(cur) = queue.pop_front() {
+                if cur == sink {
+                    break;
+                }
+
+                for arc in self.arcs[cur].iter() {
+                    let next = self.heads[*arc];
+                    if !seen[next] && self.residual(*arc) > 0 {
+                        seen[next] = true;
+                        via[next] = *arc;
+                        queue.push_back(next);
+                    }
+                }
+            }
+
+            if !seen[sink] {
+                break;
+            }
+
+            let mut cur = sink;
+            while cur != source {
+                let arc = via[cur];
+                self.flow[arc] += 1;
+                self.flow[arc ^ 1] -= 1;
+                cur = self.heads[arc ^ 1];
+            }
+
+            total += 1;
+        }
+
+        total
+    }
+
+    fn residual(&self, arc: usize) -> i32 {
+        self.capacity[arc] - self.flow[arc]
+    }
+
+    /// The nodes reachable from `source` in the residual network.
+    fn reachable(&self, source: NodeId) -> Vec<NodeId> {
+        let mut seen = vec![false; self.arcs.len()];
+        seen[source] = true;
+        let mut stack = vec![source];
+
+        while let Some(cur) = stack.pop() {
+            for arc in self.arcs[cur].iter() {
+                let next = self.heads[*arc];
+                if !seen[next] && self.residual(*arc) > 0 {
+                    seen[next] = true;
+                    stack.push(next);
+                }
+            }
+        }
+
+        (0..seen.len()).filter(|id| seen[*id]).collect()
+    }
+}
+
+impl Graph {
+    /// The maximum flow from `source` to `sink` when every edge has a capacity
+    /// of one, which is the number of paths between them that share no edges.
+    ///
+    /// # Panics
+    /// Panics if either node is not in the graph.
+    ///
+    /// # Examples
+    /// ```
+    /// use proliferatr::graph::Graph;
+    ///
+    /// let mut graph = Graph::with_nodes(4, true);
+    /// graph.add_edge(0, 1).unwrap();
+    /// graph.add_edge(0, 2).unwrap();
+    /// graph.add_edge(1, 3).unwrap();
+    /// graph.add_edge(2, 3).unwrap();
+    /// graph.add_edge(2, 1).unwrap();
+    ///
+    /// assert_eq!(graph.max_flow(0, 3), 2);
+    /// assert_eq!(graph.max_flow(3, 0), 0);
+    /// ```
+    pub fn max_flow(&self, source: NodeId, sink: NodeId) -> usize {
+        assert!(source < self.node_count() && sink < self.node_count());
+        if source == sink {
+            return 0;
+        }
+
+        Network::new(self, self.is_directed()).max_flow(source, sink, usize::MAX)
+    }
+
+    /// A global minimum cut of the graph, ignoring the direction of edges, or
+    /// `None` if the graph has fewer than two nodes.
+    ///
+    /// The cut is found by computing the maximum flow from the first node to
+    /// every other node. The returned side contains the first node.
+    ///
+    /// # Examples
+    /// ```
+    /// use proliferatr::graph::Graph;
+    ///
+    /// // two triangles joined by a single edge
+    /// let mut graph = Graph::with_nodes(6, false);
+    /// for (a, b) in [(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3), (2, 3)] {
+    ///     graph.add_edge(a, b).unwrap();
+    /// }
+    ///
+    /// let cut = graph.min_cut().unwrap();
+    /// assert_eq!(cut.size, 1);
+    /// assert_eq!(cut.side, vec![0, 1, 2]);
+    /// assert_eq!(graph.edge_connectivity(), 1);
+    /// ```
+    pub fn min_cut(&self) -> Option<MinCut> {
+        if self.node_count() < 2 {
+            return None;
+        }
+
+        let mut network = Network::new(self, false);
+        let mut best: Option<MinCut> = None;
+
+        for sink in 1..self.node_count() {
+            let limit = best.as_ref().map(|b| b.size).unwrap_or(usize::MAX);
+            network.reset();
+            let size = network.max_flow(0, sink, limit);
+
+            if size < limit {
+                best = Some(MinCut {
+                    size,
+                    side: network.reachable(0),
+                });
+
+                if size == 0 {
+                    break;
+                }
+            }
+        }
+
+        best
+    }
+
+    /// The smallest number of edges whose removal disconnects the graph,
+    /// ignoring the direction of edges. Graphs with fewer than two nodes have
+    /// an edge connectivity of zero.
+    pub fn edge_connectivity(&self) -> usize {
+        self.min_cut().map(|c| c.size).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cuts() {
+        // a 4-cycle of 4-cliques, joined by single edges
+        let mut graph = Graph::with_nodes(16, false);
+        for clique in 0..4 {
+            let base = clique * 4;
+            for a in 0..4 {
+                for b in (a + 1)..4 {
+                    graph.add_edge(base + a, base + b).unwrap();
+                }
+            }
+            graph.add_edge(base + 3, (base + 4) % 16).unwrap();
+        }
+
+        let cut = graph.min_cut().unwrap();
+        assert_eq!(cut.size, 2);
+        assert_eq!(cut.side.len() % 4, 0);
+        assert!(cut.side.contains(&0));
+        assert_eq!(graph.max_flow(1, 9), 2);
+
+        let mut graph = Graph::with_nodes(3, false);
+        graph.add_edge(0, 1).unwrap();
+        let cut = graph.min_cut().unwrap();
+        assert_eq!(cut.size, 0);
+        assert_eq!(cut.side, vec![0, 1]);
+
+        assert_eq!(Graph::with_nodes(1, false).min_cut(), None);
+        assert_eq!(Graph::new().edge_connectivity(), 0);
+    }
+}
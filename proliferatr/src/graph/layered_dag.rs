@@ -0,0 +1,217 @@
+use std::ops::Range;
+
+use derive_builder::Builder;
+use rand::{seq::SliceRandom, Rng};
+
+use crate::InputGenerator;
+
+use super::{Graph, GraphError, NodeId};
+
+/// Generates random directed acyclic graphs whose nodes are arranged in
+/// layers, with every edge pointing from a layer to a later one.
+///
+/// Nodes are numbered layer by layer, so the first `layers[0]` ids are the
+/// first layer (see [LayeredDag::layer_ranges]). Every node outside of the
+/// first layer has an edge from the layer before it, and every node outside
+/// of the last layer has an edge to the layer after it. Beyond that, each
+/// possible edge to the next layer (or to any later layer, if `skip_layers`
+/// is set) exists with the given `probability`. No node has more than
+/// `max_out_degree` outgoing edges.
+///
+/// # Examples
+/// ```
+/// use rand::thread_rng;
+/// use proliferatr::graph::LayeredDag;
+///
+/// let dag = LayeredDag::builder()
+///     .layer(1)
+///     .layer(3)
+///     .layer(8)
+///     .layer(20)
+///     .max_out_degree(3)
+///     .build()
+///     .unwrap();
+///
+/// let graph = dag.generate(&mut thread_rng()).unwrap();
+///
+/// assert!(graph.is_acyclic());
+/// assert!(graph.is_connected());
+/// assert_eq!(dag.layer_ranges()[3], 12..32);
+/// assert!(graph.nodes().all(|id| graph.out_degree(id) <= 3));
+/// ```
+#[derive(Debug, Clone, PartialEq, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct LayeredDag {
+    #[builder(setter(each(name = "layer")))]
+    layers: Vec<usize>,
+
+    #[builder(default = "0.2")]
+    probability: f64,
+
+    #[builder(default)]
+    skip_layers: bool,
+
+    #[builder(default, setter(strip_option))]
+    max_out_degree: Option<usize>,
+}
+
+impl LayeredDagBuilder {
+    fn validate(&self) -> Result<(), String> {
+        if let Some(p) = self.probability {
+            if !(0.0..=1.0).contains(&p) {
+                return Err("probability must be between 0 and 1.".into());
+            }
+        }
+
+        if let Some(layers) = &self.layers {
+            if layers.is_empty() || layers.contains(&0) {
+                return Err("There must be at least one layer, and no empty layers.".into());
+            }
+
+            if let Some(Some(max)) = self.max_out_degree {
+                if max == 0 && layers.len() > 1 {
+                    return Err("max_out_degree must be greater than 0.".into());
+                }
+
+                if layers.windows(2).any(|w| w[0] * max < w[1]) {
+                    return Err(
+                        "Every layer must be reachable within max_out_degree edges per node."
+                            .into(),
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl LayeredDag {
+    pub fn builder() -> LayeredDagBuilder {
+        LayeredDagBuilder::default()
+    }
+
+    /// The ids of the nodes in each layer.
+    pub fn layer_ranges(&self) -> Vec<Range<NodeId>> {
+        self.layers
+            .iter()
+            .scan(0, |start, size| {
+                let range = *start..(*start + size);
+                *start += size;
+                Some(range)
+            })
+            .collect()
+    }
+
+    /// Generate a new graph, with nodes named after their index.
+    pub fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<Graph, GraphError> {
+        let ranges = self.layer_ranges();
+        let total = ranges.last().map(|r| r.end).unwrap_or_default();
+        let max_out = self.max_out_degree.unwrap_or(usize::MAX);
+        let mut graph = Graph::with_nodes(total, true);
+
+        for pair in ranges.windows(2) {
+            let mut children: Vec<NodeId> = pair[1].clone().collect();
+            children.shuffle(rng);
+
+            for child in children {
+                let parent = pair[0]
+                    .clone()
+                    .filter(|p| graph.out_degree(*p) < max_out)
+                    .collect::<Vec<_>>()
+                    .choose(rng)
+                    .copied()
+                    .ok_or(GraphError::DegreeConstraint(child))?;
+
+                graph.add_edge(parent, child)?;
+            }
+
+            for parent in pair[0].clone() {
+                if graph.out_degree(parent) == 0 {
+                    graph.add_edge(parent, rng.gen_range(pair[1].clone()))?;
+                }
+            }
+        }
+
+        for (i, layer) in ranges.iter().enumerate().skip(1) {
+            let sources = if self.skip_layers {
+                0..layer.start
+            } else {
+                ranges[i - 1].clone()
+            };
+
+            for from in sources {
+                for to in layer.clone() {
+                    if graph.out_degree(from) < max_out
+                        && !graph.has_edge(from, to)
+                        && rng.gen_bool(self.probability)
+                    {
+                        graph.add_edge(from, to)?;
+                    }
+                }
+            }
+        }
+
+        Ok(graph)
+    }
+}
+
+impl InputGenerator for LayeredDag {
+    type GeneratorError = GraphError;
+    type Output = Graph;
+
+    fn gen_input<R: Rng + Clone>(&self, rng: &mut R) -> Result<Self::Output, Self::GeneratorError> {
+        self.generate(rng)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn layers() {
+        let dag = LayeredDag::builder()
+            .layers(vec![2, 5, 3, 8])
+            .probability(0.5)
+            .skip_layers(true)
+            .build()
+            .unwrap();
+        let ranges = dag.layer_ranges();
+
+        for seed in 0..5 {
+            let graph = dag.generate(&mut StdRng::seed_from_u64(seed)).unwrap();
+            let layer = |id| ranges.iter().position(|r| r.contains(&id)).unwrap();
+
+            assert!(graph.is_acyclic());
+            assert!(graph.edges().iter().all(|e| layer(e.from) < layer(e.to)));
+            assert!(graph.nodes().skip(2).all(|id| graph
+                .predecessors(id)
+                .iter()
+                .any(|p| layer(*p) + 1 == layer(id))));
+            assert!(graph.nodes().take(10).all(|id| graph
+                .neighbors(id)
+                .iter()
+                .any(|n| layer(*n) == layer(id) + 1)));
+        }
+
+        // without random edges, only the required ones are made
+        let graph = LayeredDag::builder()
+            .layers(vec![3, 1, 2])
+            .probability(0.0)
+            .build()
+            .unwrap()
+            .generate(&mut StdRng::seed_from_u64(1))
+            .unwrap();
+        assert_eq!(graph.edge_count(), 5);
+
+        assert!(LayeredDag::builder()
+            .layers(vec![1, 3])
+            .max_out_degree(2)
+            .build()
+            .is_err());
+        assert!(LayeredDag::builder().layers(vec![1, 0]).build().is_err());
+    }
+}
@@ -0,0 +1,456 @@
+//! Graphs of named nodes, along with random generators and text exporters.
+use std::{collections::VecDeque, ops::Range};
+
+use rand::Rng;
+use thiserror::Error;
+
+use crate::generic::{TokenError, UniqueTokens};
+
+mod cycle_network;
+mod erdos_renyi;
+mod export;
+mod flow;
+mod layered_dag;
+mod planted_cut;
+mod random_tree;
+mod regular;
+
+pub use cycle_network::{
+    ChoiceNode, CycleNetwork, CycleNetworkBuilder, CycleNetworkBuilderError, CycleNetworkError,
+    CycleNetworkGraph,
+};
+pub use erdos_renyi::{ErdosRenyi, ErdosRenyiBuilder, ErdosRenyiBuilderError};
+pub use export::{AdjacencyList, AdjacencyListBuilder, AdjacencyListBuilderError};
+pub use flow::MinCut;
+pub use layered_dag::{LayeredDag, LayeredDagBuilder, LayeredDagBuilderError};
+pub use planted_cut::{PlantedCut, PlantedCutBuilder, PlantedCutBuilderError, PlantedCutGraph};
+pub use random_tree::{RandomTree, RandomTreeBuilder, RandomTreeBuilderError};
+pub use regular::{RegularGraph, RegularGraphBuilder, RegularGraphBuilderError};
+
+/// The index of a node in a [Graph].
+pub type NodeId = usize;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum GraphError {
+    #[error("Node {0} is not in the graph.")]
+    UnknownNode(NodeId),
+
+    #[error("Node {0} cannot have an edge to itself.")]
+    SelfLoop(NodeId),
+
+    #[error("The edge from node {0} to node {1} already exists.")]
+    DuplicateEdge(NodeId, NodeId),
+
+    #[error("The degree constraints cannot be satisfied for node {0}.")]
+    DegreeConstraint(NodeId),
+
+    #[error("Failed to generate a graph after {0} attempts.")]
+    AttemptsExhausted(usize),
+
+    #[error(transparent)]
+    Token(#[from] TokenError),
+}
+
+/// An edge between two nodes of a [Graph]. For undirected graphs, `from` and
+/// `to` are simply the order in which the nodes were given.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Edge {
+    pub from: NodeId,
+    pub to: NodeId,
+}
+
+/// A directed or undirected graph of named nodes, identified by their index.
+///
+/// Edges are also indexed, in the order they were added. Self loops and
+/// duplicate edges are not allowed.
+///
+/// # Examples
+/// ```
+/// use proliferatr::graph::Graph;
+///
+/// let mut graph = Graph::new();
+/// let a = graph.add_node("a");
+/// let b = graph.add_node("b");
+/// let c = graph.add_node("c");
+///
+/// graph.add_edge(a, b).unwrap();
+/// assert!(graph.add_edge(b, a).is_err());
+///
+/// assert!(graph.has_edge(b, a));
+/// assert_eq!(graph.degree(b), 1);
+/// assert!(!graph.is_connected());
+///
+/// graph.add_edge(c, b).unwrap();
+/// assert!(graph.is_connected());
+/// assert_eq!(graph.find("c"), Some(c));
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Graph {
+    directed: bool,
+    names: Vec<String>,
+    edges: Vec<Edge>,
+    outgoing: Vec<Vec<NodeId>>,
+    incoming: Vec<Vec<NodeId>>,
+}
+
+impl Graph {
+    /// Make a new, empty, undirected graph.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Make a new, empty, directed graph.
+    pub fn new_directed() -> Self {
+        Self {
+            directed: true,
+            ..Default::default()
+        }
+    }
+
+    /// Make a new graph of `count` nodes without edges. The nodes are named
+    /// after their index.
+    pub fn with_nodes(count: usize, directed: bool) -> Self {
+        let mut graph = if directed {
+            Self::new_directed()
+        } else {
+            Self::new()
+        };
+
+        for i in 0..count {
+            graph.add_node(i.to_string());
+        }
+
+        graph
+    }
+
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// The ids of every node in the graph.
+    pub fn nodes(&self) -> Range<NodeId> {
+        0..self.names.len()
+    }
+
+    /// The edges of the graph, in the order they were added.
+    pub fn edges(&self) -> &[Edge] {
+        &self.edges
+    }
+
+    /// Add a node with the given `name`, returning its id.
+    pub fn add_node(&mut self, name: impl Into<String>) -> NodeId {
+        self.names.push(name.into());
+        self.outgoing.push(Vec::new());
+        self.incoming.push(Vec::new());
+        self.names.len() - 1
+    }
+
+    /// Add an edge between `from` and `to`, returning the index of the edge.
+    pub fn add_edge(&mut self, from: NodeId, to: NodeId) -> Result<usize, GraphError> {
+        for id in [from, to] {
+            if id >= self.node_count() {
+                return Err(GraphError::UnknownNode(id));
+            }
+        }
+
+        if from == to {
+            return Err(GraphError::SelfLoop(from));
+        }
+
+        if self.has_edge(from, to) {
+            return Err(GraphError::DuplicateEdge(from, to));
+        }
+
+        self.edges.push(Edge { from, to });
+        self.outgoing[from].push(to);
+        if self.directed {
+            self.incoming[to].push(from);
+        } else {
+            self.outgoing[to].push(from);
+        }
+
+        Ok(self.edges.len() - 1)
+    }
+
+    /// Returns `true` if there is an edge from `from` to `to`. For undirected
+    /// graphs, the order of the nodes does not matter.
+    pub fn has_edge(&self, from: NodeId, to: NodeId) -> bool {
+        self.outgoing
+            .get(from)
+            .map(|n| n.contains(&to))
+            .unwrap_or(false)
+    }
+
+    /// The name of the node `id`.
+    ///
+    /// # Panics
+    /// Panics if `id` is not in the graph.
+    pub fn name(&self, id: NodeId) -> &str {
+        &self.names[id]
+    }
+
+    pub fn set_name(&mut self, id: NodeId, name: impl Into<String>) -> Result<(), GraphError> {
+        let slot = self.names.get_mut(id).ok_or(GraphError::UnknownNode(id))?;
+        *slot = name.into();
+        Ok(())
+    }
+
+    /// The id of the first node with the given `name`, if any.
+    pub fn find(&self, name: &str) -> Option<NodeId> {
+        self.names.iter().position(|n| n == name)
+    }
+
+    /// The nodes reachable from `id` by following a single edge.
+    ///
+    /// # Panics
+    /// Panics if `id` is not in the graph.
+    pub fn neighbors(&self, id: NodeId) -> &[NodeId] {
+        &self.outgoing[id]
+    }
+
+    /// The nodes with an edge to `id`. For undirected graphs, this is the same
+    /// as [Graph::neighbors].
+    ///
+    /// # Panics
+    /// Panics if `id` is not in the graph.
+    pub fn predecessors(&self, id: NodeId) -> &[NodeId] {
+        if self.directed {
+            &self.incoming[id]
+        } else {
+            &self.outgoing[id]
+        }
+    }
+
+    /// The number of edges touching `id`, in either direction.
+    pub fn degree(&self, id: NodeId) -> usize {
+        if self.directed {
+            self.outgoing[id].len() + self.incoming[id].len()
+        } else {
+            self.outgoing[id].len()
+        }
+    }
+
+    pub fn out_degree(&self, id: NodeId) -> usize {
+        self.outgoing[id].len()
+    }
+
+    pub fn in_degree(&self, id: NodeId) -> usize {
+        self.predecessors(id).len()
+    }
+
+    /// Replace the name of every node with a unique name handed out by
+    /// `tokens`.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::collections::HashSet;
+    ///
+    /// use rand::thread_rng;
+    /// use proliferatr::{generic::UniqueTokens, graph::Graph};
+    ///
+    /// let mut tokens = UniqueTokens::builder().build().unwrap();
+    /// let mut graph = Graph::with_nodes(100, false);
+    /// graph.assign_names(&mut tokens, &mut thread_rng()).unwrap();
+    ///
+    /// let names: HashSet<_> = graph.nodes().map(|id| graph.name(id)).collect();
+    /// assert_eq!(names.len(), 100);
+    /// ```
+    pub fn assign_names<R: Rng + ?Sized>(
+        &mut self,
+        tokens: &mut UniqueTokens,
+        rng: &mut R,
+    ) -> Result<(), GraphError> {
+        self.names = tokens.take(self.node_count(), rng)?;
+        Ok(())
+    }
+
+    /// The groups of nodes connected to one another, ignoring the direction of
+    /// edges. Each group is sorted, and groups are ordered by their smallest
+    /// node.
+    pub fn components(&self) -> Vec<Vec<NodeId>> {
+        let mut seen = vec![false; self.node_count()];
+        let mut components = Vec::new();
+
+        for start in self.nodes() {
+            if seen[start] {
+                continue;
+            }
+
+            seen[start] = true;
+            let mut component = vec![start];
+            let mut queue = VecDeque::from([start]);
+
+            while let Some(cur) = queue.pop_front() {
+                for next in self.neighbors(cur).iter().chain(self.predecessors(cur)) {
+                    if !seen[*next] {
+                        seen[*next] = true;
+                        component.push(*next);
+                        queue.push_back(*next);
+                    }
+                }
+            }
+
+            component.sort_unstable();
+            components.push(component);
+        }
+
+        components
+    }
+
+    /// Returns `true` if every node can be reached from every other node,
+    /// ignoring the direction of edges. An empty graph is connected.
+    pub fn is_connected(&self) -> bool {
+        self.components().len() <= 1
+    }
+
+    /// Returns `true` if every node can be reached from every other node by
+    /// following edges in their direction. For undirected graphs, this is the
+    /// same as [Graph::is_connected].
+    pub fn is_strongly_connected(&self) -> bool {
+        if self.node_count() == 0 {
+            return true;
+        }
+
+        let reaches_all = |edges: &[Vec<NodeId>]| {
+            let mut seen = vec![false; self.node_count()];
+            seen[0] = true;
+            let mut stack = vec![0];
+            while let Some(cur) = stack.pop() {
+                for next in edges[cur].iter() {
+                    if !seen[*next] {
+                        seen[*next] = true;
+                        stack.push(*next);
+                    }
+                }
+            }
+            seen.into_iter().all(|s| s)
+        };
+
+        reaches_all(&self.outgoing) && (!self.directed || reaches_all(&self.incoming))
+    }
+
+    /// An ordering of the nodes of a directed graph in which every edge points
+    /// from an earlier node to a later one, or `None` if the graph has a
+    /// cycle. Undirected graphs with any edges have no such ordering.
+    pub fn topological_order(&self) -> Option<Vec<NodeId>> {
+        if !self.directed {
+            return (self.edge_count() == 0).then(|| self.nodes().collect());
+        }
+
+        let mut remaining: Vec<usize> = self.nodes().map(|id| self.in_degree(id)).collect();
+        let mut queue: VecDeque<NodeId> = self.nodes().filter(|id| remaining[*id] == 0).collect();
+        let mut order = Vec::with_capacity(self.node_count());
+
+        while let Some(cur) = queue.pop_front() {
+            order.push(cur);
+            for next in self.neighbors(cur) {
+                remaining[*next] -= 1;
+                if remaining[*next] == 0 {
+                    queue.push_back(*next);
+                }
+            }
+        }
+
+        (order.len() == self.node_count()).then_some(order)
+    }
+
+    /// Returns `true` if the graph is directed and has no cycles.
+    pub fn is_acyclic(&self) -> bool {
+        self.directed && self.topological_order().is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::generic::StringToken;
+
+    use super::*;
+
+    #[test]
+    fn edges() {
+        let mut graph = Graph::with_nodes(3, true);
+        assert_eq!(graph.add_edge(0, 1), Ok(0));
+        assert_eq!(graph.add_edge(1, 0), Ok(1));
+        assert_eq!(graph.add_edge(1, 2), Ok(2));
+        assert_eq!(graph.add_edge(1, 2), Err(GraphError::DuplicateEdge(1, 2)));
+        assert_eq!(graph.add_edge(2, 2), Err(GraphError::SelfLoop(2)));
+        assert_eq!(graph.add_edge(2, 3), Err(GraphError::UnknownNode(3)));
+
+        assert_eq!(graph.neighbors(1), &[0, 2]);
+        assert_eq!(graph.predecessors(2), &[1]);
+        assert_eq!(graph.degree(1), 3);
+        assert_eq!(graph.out_degree(2), 0);
+        assert_eq!(graph.in_degree(0), 1);
+        assert_eq!(graph.name(2), "2");
+        assert!(graph.set_name(4, "x").is_err());
+    }
+
+    #[test]
+    fn connectivity() {
+        let mut graph = Graph::with_nodes(5, true);
+        graph.add_edge(0, 1).unwrap();
+        graph.add_edge(1, 2).unwrap();
+        graph.add_edge(4, 3).unwrap();
+
+        assert_eq!(graph.components(), vec![vec![0, 1, 2], vec![3, 4]]);
+        assert!(!graph.is_connected());
+
+        graph.add_edge(3, 2).unwrap();
+        assert!(graph.is_connected());
+        assert!(!graph.is_strongly_connected());
+        assert!(graph.is_acyclic());
+
+        let order = graph.topological_order().unwrap();
+        for edge in graph.edges() {
+            let pos = |id| order.iter().position(|n| *n == id).unwrap();
+            assert!(pos(edge.from) < pos(edge.to));
+        }
+
+        graph.add_edge(2, 0).unwrap();
+        assert!(!graph.is_acyclic());
+
+        graph.add_edge(2, 4).unwrap();
+        assert!(graph.is_strongly_connected());
+
+        let mut graph = Graph::with_nodes(3, false);
+        graph.add_edge(0, 1).unwrap();
+        graph.add_edge(2, 1).unwrap();
+        assert!(graph.is_strongly_connected());
+        assert!(!graph.is_acyclic());
+    }
+
+    #[test]
+    fn names_exhausted() {
+        let token = StringToken::builder()
+            .length(1..=1)
+            .charset(b"ab")
+            .build()
+            .unwrap();
+        let tokens = UniqueTokens::builder().token(token).build().unwrap();
+
+        let mut graph = Graph::with_nodes(2, false);
+        graph
+            .assign_names(&mut tokens.clone(), &mut StdRng::seed_from_u64(1))
+            .unwrap();
+        assert_ne!(graph.name(0), graph.name(1));
+
+        let mut graph = Graph::with_nodes(3, false);
+        assert_eq!(
+            graph.assign_names(&mut tokens.clone(), &mut StdRng::seed_from_u64(1)),
+            Err(GraphError::Token(TokenError::InsufficientCapacity {
+                requested: 3,
+                available: 2
+            }))
+        );
+    }
+}
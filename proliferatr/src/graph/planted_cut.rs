@@ -0,0 +1,275 @@
+use std::ops::Range;
+
+use derive_builder::Builder;
+use rand::{seq::IteratorRandom, Rng};
+
+use crate::InputGenerator;
+
+use super::{Edge, ErdosRenyi, Graph, GraphError, NodeId};
+
+/// A graph made by [PlantedCut], along with the structure that was planted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlantedCutGraph {
+    pub graph: Graph,
+
+    /// The ids of the nodes in each part.
+    pub parts: Vec<Range<NodeId>>,
+
+    /// The edges joining the parts.
+    pub cut_edges: Vec<Edge>,
+}
+
+impl PlantedCutGraph {
+    /// The number of nodes in each part.
+    pub fn part_sizes(&self) -> Vec<usize> {
+        self.parts.iter().map(|p| p.len()).collect()
+    }
+}
+
+/// Generates undirected graphs made of several densely connected parts, where
+/// the only way to disconnect the graph by removing `cut` edges is to remove
+/// the edges joining two of the parts.
+///
+/// Each part is a random graph where every node has at least `min_degree`
+/// edges (`cut + 1` by default), plus any extra edges chosen with the given
+/// `probability`, and is regenerated until it cannot be disconnected by
+/// removing `cut` or fewer edges. The parts are then joined in a random tree,
+/// with each pair of joined parts sharing exactly `cut` edges between distinct
+/// nodes. Finally, the minimum cut of the whole graph is verified to be of
+/// size `cut`.
+///
+/// Nodes are numbered part by part, and named after their index.
+///
+/// # Examples
+/// ```
+/// use rand::thread_rng;
+/// use proliferatr::graph::PlantedCut;
+///
+/// let planted = PlantedCut::builder()
+///     .part(40)
+///     .part(25)
+///     .cut(3)
+///     .min_degree(4)
+///     .build()
+///     .unwrap()
+///     .generate(&mut thread_rng())
+///     .unwrap();
+///
+/// assert_eq!(planted.part_sizes(), vec![40, 25]);
+/// assert_eq!(planted.cut_edges.len(), 3);
+///
+/// let cut = planted.graph.min_cut().unwrap();
+/// assert_eq!(cut.size, 3);
+/// assert_eq!(cut.side, (0..40).collect::<Vec<_>>());
+/// ```
+#[derive(Debug, Clone, PartialEq, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct PlantedCut {
+    #[builder(setter(each(name = "part")))]
+    parts: Vec<usize>,
+
+    cut: usize,
+
+    #[builder(default, setter(strip_option))]
+    min_degree: Option<usize>,
+
+    #[builder(default, setter(strip_option))]
+    max_degree: Option<usize>,
+
+    #[builder(default = "0.0")]
+    probability: f64,
+
+    #[builder(default = "10")]
+    attempts: usize,
+}
+
+impl PlantedCutBuilder {
+    fn validate(&self) -> Result<(), String> {
+        let Some(cut) = self.cut else {
+            return Ok(());
+        };
+
+        if cut == 0 {
+            return Err("cut must be greater than 0.".into());
+        }
+
+        let min_degree = self.min_degree.flatten().unwrap_or(cut + 1);
+        if min_degree <= cut {
+            return Err("min_degree must be greater than cut.".into());
+        }
+
+        if let Some(Some(max_degree)) = self.max_degree {
+            if max_degree < min_degree {
+                return Err("max_degree must not be less than min_degree.".into());
+            }
+        }
+
+        if let Some(parts) = &self.parts {
+            if parts.len() < 2 {
+                return Err("There must be at least two parts.".into());
+            }
+
+            if parts.iter().any(|p| *p <= min_degree) {
+                return Err("Every part must have more than min_degree nodes.".into());
+            }
+        }
+
+        if let Some(p) = self.probability {
+            if !(0.0..=1.0).contains(&p) {
+                return Err("probability must be between 0 and 1.".into());
+            }
+        }
+
+        if self.attempts == Some(0) {
+            return Err("attempts must be greater than 0.".into());
+        }
+
+        Ok(())
+    }
+}
+
+impl PlantedCut {
+    pub fn builder() -> PlantedCutBuilder {
+        PlantedCutBuilder::default()
+    }
+
+    /// Generate a new graph.
+    pub fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<PlantedCutGraph, GraphError> {
+        for _ in 0..self.attempts {
+            if let Some(planted) = self.attempt(rng)? {
+                return Ok(planted);
+            }
+        }
+
+        Err(GraphError::AttemptsExhausted(self.attempts))
+    }
+
+    fn attempt<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<Option<PlantedCutGraph>, GraphError> {
+        let total = self.parts.iter().sum();
+        let mut graph = Graph::with_nodes(total, false);
+        let mut parts: Vec<Range<NodeId>> = Vec::with_capacity(self.parts.len());
+
+        for size in self.parts.iter() {
+            let Some(part) = self.part(*size, rng)? else {
+                return Ok(None);
+            };
+
+            let start = parts.last().map(|p| p.end).unwrap_or_default();
+            for edge in part.edges() {
+                graph.add_edge(start + edge.from, start + edge.to)?;
+            }
+
+            parts.push(start..(start + size));
+        }
+
+        let mut cut_edges = Vec::with_capacity(self.cut * (parts.len() - 1));
+        for (i, part) in parts.iter().enumerate().skip(1) {
+            let other = &parts[rng.gen_range(0..i)];
+            let ours = part.clone().choose_multiple(rng, self.cut);
+            let theirs = other.clone().choose_multiple(rng, self.cut);
+
+            for (to, from) in ours.into_iter().zip(theirs) {
+                graph.add_edge(from, to)?;
+                cut_edges.push(Edge { from, to });
+            }
+        }
+
+        if graph.edge_connectivity() != self.cut {
+            return Ok(None);
+        }
+
+        Ok(Some(PlantedCutGraph {
+            graph,
+            parts,
+            cut_edges,
+        }))
+    }
+
+    /// Generate a single part, or `None` if it was too easy to disconnect.
+    fn part<R: Rng + ?Sized>(&self, size: usize, rng: &mut R) -> Result<Option<Graph>, GraphError> {
+        let mut builder = ErdosRenyi::builder();
+        builder
+            .nodes(size)
+            .probability(self.probability)
+            .min_degree(self.min_degree.unwrap_or(self.cut + 1))
+            .connected(true);
+        if let Some(max_degree) = self.max_degree {
+            builder.max_degree(max_degree);
+        }
+
+        let part = match builder
+            .build()
+            .expect("the part configuration was validated")
+            .generate(rng)
+        {
+            Ok(part) => part,
+            Err(GraphError::DegreeConstraint(_)) => return Ok(None),
+            Err(e) => return Err(e),
+        };
+
+        Ok((part.edge_connectivity() > self.cut).then_some(part))
+    }
+}
+
+impl InputGenerator for PlantedCut {
+    type GeneratorError = GraphError;
+    type Output = PlantedCutGraph;
+
+    fn gen_input<R: Rng + Clone>(&self, rng: &mut R) -> Result<Self::Output, Self::GeneratorError> {
+        self.generate(rng)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn parts() {
+        let planted = PlantedCut::builder()
+            .parts(vec![20, 15, 30, 12])
+            .cut(2)
+            .probability(0.05)
+            .build()
+            .unwrap()
+            .generate(&mut StdRng::seed_from_u64(4))
+            .unwrap();
+
+        let graph = &planted.graph;
+        assert_eq!(graph.node_count(), 77);
+        assert_eq!(planted.cut_edges.len(), 6);
+        assert_eq!(graph.edge_connectivity(), 2);
+
+        // removing the cut edges leaves exactly the parts
+        let mut remaining = Graph::with_nodes(graph.node_count(), false);
+        for edge in graph.edges() {
+            if !planted.cut_edges.contains(edge) {
+                remaining.add_edge(edge.from, edge.to).unwrap();
+            }
+        }
+        let components: Vec<_> = remaining
+            .components()
+            .into_iter()
+            .map(|c| c[0]..(c[c.len() - 1] + 1))
+            .collect();
+        assert_eq!(components, planted.parts);
+    }
+
+    #[test]
+    fn validation() {
+        let valid = || {
+            let mut builder = PlantedCut::builder();
+            builder.parts(vec![10, 10]).cut(3);
+            builder
+        };
+
+        assert!(valid().build().is_ok());
+        assert!(valid().part(4).build().is_err());
+        assert!(valid().min_degree(3).build().is_err());
+        assert!(valid().max_degree(3).build().is_err());
+        assert!(valid().cut(0).build().is_err());
+        assert!(valid().parts(vec![10]).build().is_err());
+    }
+}
@@ -0,0 +1,145 @@
+use derive_builder::Builder;
+use rand::{seq::SliceRandom, Rng};
+
+use crate::InputGenerator;
+
+use super::{Graph, GraphError, NodeId};
+
+/// Generates random undirected trees of `nodes` nodes.
+///
+/// Nodes are added to the tree in a random order, each attaching to a random
+/// node already in the tree that has fewer than `max_degree` edges.
+///
+/// # Examples
+/// ```
+/// use rand::thread_rng;
+/// use proliferatr::graph::RandomTree;
+///
+/// let tree = RandomTree::builder()
+///     .nodes(50)
+///     .max_degree(3)
+///     .build()
+///     .unwrap()
+///     .generate(&mut thread_rng())
+///     .unwrap();
+///
+/// assert_eq!(tree.edge_count(), 49);
+/// assert!(tree.is_connected());
+/// assert!(tree.nodes().all(|id| tree.degree(id) <= 3));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct RandomTree {
+    nodes: usize,
+
+    #[builder(default, setter(strip_option))]
+    max_degree: Option<usize>,
+}
+
+impl RandomTreeBuilder {
+    fn validate(&self) -> Result<(), String> {
+        if let (Some(nodes), Some(Some(max_degree))) = (self.nodes, self.max_degree) {
+            if (max_degree < 2 && nodes > 2) || (max_degree == 0 && nodes > 1) {
+                return Err("max_degree is too small to connect the nodes.".into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl RandomTree {
+    pub fn builder() -> RandomTreeBuilder {
+        RandomTreeBuilder::default()
+    }
+
+    /// Generate a new tree, with nodes named after their index.
+    pub fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<Graph, GraphError> {
+        let mut graph = Graph::with_nodes(self.nodes, false);
+        let max_degree = self.max_degree.unwrap_or(usize::MAX);
+
+        let mut order: Vec<NodeId> = graph.nodes().collect();
+        order.shuffle(rng);
+
+        // nodes in the tree that can still take another edge
+        let mut open: Vec<NodeId> = order.iter().copied().take(1).collect();
+
+        for id in order.into_iter().skip(1) {
+            let idx = rng.gen_range(0..open.len());
+            let parent = open[idx];
+            graph.add_edge(parent, id)?;
+
+            if graph.degree(parent) >= max_degree {
+                open.swap_remove(idx);
+            }
+
+            if max_degree > 1 {
+                open.push(id);
+            }
+        }
+
+        Ok(graph)
+    }
+}
+
+impl InputGenerator for RandomTree {
+    type GeneratorError = GraphError;
+    type Output = Graph;
+
+    fn gen_input<R: Rng + Clone>(&self, rng: &mut R) -> Result<Self::Output, Self::GeneratorError> {
+        self.generate(rng)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn trees() {
+        let mut rng = StdRng::seed_from_u64(11);
+
+        for (nodes, max_degree) in [(1, 1), (2, 1), (10, 2), (500, 4)] {
+            let tree = RandomTree::builder()
+                .nodes(nodes)
+                .max_degree(max_degree)
+                .build()
+                .unwrap()
+                .generate(&mut rng)
+                .unwrap();
+
+            assert_eq!(tree.edge_count(), nodes - 1);
+            assert!(tree.is_connected());
+            assert!(tree.nodes().all(|id| tree.degree(id) <= max_degree));
+        }
+
+        // with a max degree of 2, the tree is a path
+        let path = RandomTree::builder()
+            .nodes(10)
+            .max_degree(2)
+            .build()
+            .unwrap()
+            .generate(&mut rng)
+            .unwrap();
+        let ends = path.nodes().filter(|id| path.degree(*id) == 1).count();
+        assert_eq!(ends, 2);
+
+        assert!(RandomTree::builder()
+            .nodes(3)
+            .max_degree(1)
+            .build()
+            .is_err());
+        assert_eq!(
+            RandomTree::builder()
+                .nodes(0)
+                .build()
+                .unwrap()
+                .generate(&mut rng)
+                .unwrap()
+                .node_count(),
+            0
+        );
+    }
+}
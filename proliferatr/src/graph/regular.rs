@@ -0,0 +1,153 @@
+use derive_builder::Builder;
+use rand::Rng;
+
+use crate::InputGenerator;
+
+use super::{Graph, GraphError, NodeId};
+
+/// The number of random pairs tried before checking whether any pair of the
+/// remaining stubs can still be joined.
+const PAIR_TRIES: usize = 100;
+
+/// Generates random undirected graphs where every one of the `nodes` nodes has
+/// exactly `degree` edges.
+///
+/// Each node starts with `degree` unpaired stubs, and random pairs of stubs
+/// are joined into edges, skipping pairs that would make a self loop or a
+/// duplicate edge. If the remaining stubs cannot be paired, the attempt is
+/// abandoned and another is made, up to `attempts` times.
+///
+/// # Examples
+/// ```
+/// use rand::thread_rng;
+/// use proliferatr::graph::RegularGraph;
+///
+/// let graph = RegularGraph::builder()
+///     .nodes(30)
+///     .degree(3)
+///     .build()
+///     .unwrap()
+///     .generate(&mut thread_rng())
+///     .unwrap();
+///
+/// assert_eq!(graph.edge_count(), 45);
+/// assert!(graph.nodes().all(|id| graph.degree(id) == 3));
+///
+/// // there is no 3-regular graph with an odd number of nodes
+/// assert!(RegularGraph::builder().nodes(5).degree(3).build().is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct RegularGraph {
+    nodes: usize,
+
+    degree: usize,
+
+    #[builder(default = "100")]
+    attempts: usize,
+}
+
+impl RegularGraphBuilder {
+    fn validate(&self) -> Result<(), String> {
+        if let (Some(nodes), Some(degree)) = (self.nodes, self.degree) {
+            if degree > 0 && degree >= nodes {
+                return Err("degree must be less than the number of nodes.".into());
+            }
+
+            if (nodes * degree) % 2 != 0 {
+                return Err("nodes * degree must be even.".into());
+            }
+        }
+
+        if self.attempts == Some(0) {
+            return Err("attempts must be greater than 0.".into());
+        }
+
+        Ok(())
+    }
+}
+
+impl RegularGraph {
+    pub fn builder() -> RegularGraphBuilder {
+        RegularGraphBuilder::default()
+    }
+
+    /// Generate a new graph, with nodes named after their index.
+    pub fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<Graph, GraphError> {
+        for _ in 0..self.attempts {
+            if let Some(graph) = self.attempt(rng)? {
+                return Ok(graph);
+            }
+        }
+
+        Err(GraphError::AttemptsExhausted(self.attempts))
+    }
+
+    fn attempt<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<Option<Graph>, GraphError> {
+        let mut graph = Graph::with_nodes(self.nodes, false);
+        let mut stubs: Vec<NodeId> = (0..self.nodes)
+            .flat_map(|id| std::iter::repeat(id).take(self.degree))
+            .collect();
+
+        let joinable = |graph: &Graph, a: NodeId, b: NodeId| a != b && !graph.has_edge(a, b);
+
+        while !stubs.is_empty() {
+            let mut pair = (0..PAIR_TRIES)
+                .map(|_| (rng.gen_range(0..stubs.len()), rng.gen_range(0..stubs.len())))
+                .find(|(i, j)| joinable(&graph, stubs[*i], stubs[*j]));
+
+            if pair.is_none() {
+                pair = (0..stubs.len())
+                    .flat_map(|i| (0..stubs.len()).map(move |j| (i, j)))
+                    .find(|(i, j)| joinable(&graph, stubs[*i], stubs[*j]));
+            }
+
+            let Some((i, j)) = pair else {
+                return Ok(None);
+            };
+
+            graph.add_edge(stubs[i], stubs[j])?;
+            stubs.swap_remove(i.max(j));
+            stubs.swap_remove(i.min(j));
+        }
+
+        Ok(Some(graph))
+    }
+}
+
+impl InputGenerator for RegularGraph {
+    type GeneratorError = GraphError;
+    type Output = Graph;
+
+    fn gen_input<R: Rng + Clone>(&self, rng: &mut R) -> Result<Self::Output, Self::GeneratorError> {
+        self.generate(rng)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn degrees() {
+        let mut rng = StdRng::seed_from_u64(5);
+
+        for (nodes, degree) in [(10, 9), (50, 4), (7, 0), (200, 7)] {
+            let graph = RegularGraph::builder()
+                .nodes(nodes)
+                .degree(degree)
+                .build()
+                .unwrap()
+                .generate(&mut rng)
+                .unwrap();
+
+            assert_eq!(graph.node_count(), nodes);
+            assert_eq!(graph.edge_count(), nodes * degree / 2);
+            assert!(graph.nodes().all(|id| graph.degree(id) == degree));
+        }
+
+        assert!(RegularGraph::builder().nodes(4).degree(4).build().is_err());
+    }
+}
@@ -0,0 +1,592 @@
+//! Cellular automata for generating organic shapes, such as caves and blobs.
+use std::{collections::VecDeque, convert::Infallible, fmt, str::FromStr};
+
+use derive_builder::Builder;
+use rand::Rng;
+use thiserror::Error;
+
+use crate::{point::Point, InputGenerator};
+
+use super::Grid;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum AutomataError {
+    #[error("Cannot parse rule from {0:?}; expected something like \"B5678/S45678\".")]
+    InvalidRule(String),
+}
+
+/// The rule of a cellular automaton, in the usual `B.../S...` notation: a dead
+/// cell becomes alive if it has one of the birth counts of live neighbors, and
+/// a live cell survives if it has one of the survival counts. Every cell has
+/// eight neighbors.
+///
+/// The default rule, `B5678/S45678`, smooths random noise into caves.
+///
+/// # Examples
+/// ```
+/// use std::str::FromStr;
+/// use proliferatr::grid::automata::Rule;
+///
+/// let life = Rule::from_str("B3/S23").unwrap();
+/// assert!(life.is_born(3));
+/// assert!(!life.is_born(2));
+/// assert!(life.survives(2));
+/// assert!(!life.survives(4));
+///
+/// assert_eq!(life.to_string(), "B3/S23");
+/// assert!(Rule::from_str("B9/S1").is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rule {
+    birth: [bool; 9],
+    survival: [bool; 9],
+}
+
+impl Default for Rule {
+    fn default() -> Self {
+        Self::new(&[5, 6, 7, 8], &[4, 5, 6, 7, 8])
+    }
+}
+
+impl Rule {
+    /// Make a rule from the neighbor counts for birth and survival. Counts
+    /// above 8 are ignored.
+    pub fn new(birth: &[usize], survival: &[usize]) -> Self {
+        let mut rule = Self {
+            birth: [false; 9],
+            survival: [false; 9],
+        };
+
+        for b in birth.iter().filter(|b| **b <= 8) {
+            rule.birth[*b] = true;
+        }
+
+        for s in survival.iter().filter(|s| **s <= 8) {
+            rule.survival[*s] = true;
+        }
+
+        rule
+    }
+
+    /// Returns `true` if a dead cell with `neighbors` live neighbors becomes
+    /// alive.
+    pub fn is_born(&self, neighbors: usize) -> bool {
+        self.birth.get(neighbors).copied().unwrap_or(false)
+    }
+
+    /// Returns `true` if a live cell with `neighbors` live neighbors stays
+    /// alive.
+    pub fn survives(&self, neighbors: usize) -> bool {
+        self.survival.get(neighbors).copied().unwrap_or(false)
+    }
+}
+
+impl FromStr for Rule {
+    type Err = AutomataError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || AutomataError::InvalidRule(s.to_string());
+
+        let (birth, survival) = s.split_once('/').ok_or_else(err)?;
+        let birth = birth
+            .strip_prefix('B')
+            .or_else(|| birth.strip_prefix('b'))
+            .ok_or_else(err)?;
+        let survival = survival
+            .strip_prefix('S')
+            .or_else(|| survival.strip_prefix('s'))
+            .ok_or_else(err)?;
+
+        let counts = |part: &str| {
+            part.chars()
+                .map(|ch| match ch.to_digit(10) {
+                    Some(d) if d <= 8 => Ok(d as usize),
+                    _ => Err(err()),
+                })
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        Ok(Self::new(&counts(birth)?, &counts(survival)?))
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let counts = |set: &[bool; 9]| {
+            (0..9)
+                .filter(|i| set[*i])
+                .map(|i| i.to_string())
+                .collect::<String>()
+        };
+
+        write!(f, "B{}/S{}", counts(&self.birth), counts(&self.survival))
+    }
+}
+
+/// How cells beyond the edge of the grid are counted as neighbors.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Edge {
+    /// Cells beyond the edge are alive, which closes shapes off at the edges.
+    #[default]
+    Alive,
+
+    /// Cells beyond the edge are dead.
+    Dead,
+
+    /// The grid wraps around, so cells on opposite edges are neighbors.
+    Wrap,
+}
+
+/// Generates a `Grid<bool>` of organic shapes by seeding a grid with random
+/// live (`true`) cells and running a cellular automaton over it.
+///
+/// Each cell starts alive with a probability of `fill`, then the [Rule] is
+/// applied `steps` times. Afterwards, connected regions (of orthogonally
+/// adjacent cells) of dead cells smaller than `min_gap` are filled in.
+///
+/// Every point to `connect` is then made dead, and dead passages are carved
+/// between them as needed, through as few live cells as possible, so they are
+/// all connected through dead cells. Finally, regions of live cells smaller
+/// than `min_region` are killed. With the default rule, live cells are the
+/// walls of a cave, and the points are guaranteed to be in the same cave.
+///
+/// # Examples
+/// ```
+/// use rand::thread_rng;
+/// use proliferatr::{grid::automata::CellularAutomaton, point::Point};
+///
+/// let caves = CellularAutomaton::builder()
+///     .width(60)
+///     .height(30)
+///     .min_gap(10)
+///     .connect(Point::new(1, 1))
+///     .connect(Point::new(58, 28))
+///     .build()
+///     .unwrap();
+///
+/// let grid = caves.generate(&mut thread_rng());
+///
+/// assert!(!grid[Point::new(1, 1)]);
+/// assert!(!grid[Point::new(58, 28)]);
+///
+/// let walls = grid.map(|alive| if *alive { '#' } else { '.' });
+/// assert_eq!(walls.to_string().lines().count(), 30);
+/// ```
+#[derive(Debug, Clone, PartialEq, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct CellularAutomaton {
+    width: usize,
+
+    height: usize,
+
+    /// The probability that each cell starts alive.
+    #[builder(default = "0.45")]
+    fill: f64,
+
+    #[builder(default)]
+    rule: Rule,
+
+    #[builder(default = "4")]
+    steps: usize,
+
+    #[builder(default)]
+    edge: Edge,
+
+    /// Live regions smaller than this are removed.
+    #[builder(default)]
+    min_region: usize,
+
+    /// Dead regions smaller than this are filled in.
+    #[builder(default)]
+    min_gap: usize,
+
+    /// Points that must be dead and connected to one another.
+    #[builder(default, setter(each(name = "connect")))]
+    connected: Vec<Point>,
+}
+
+impl CellularAutomatonBuilder {
+    fn validate(&self) -> Result<(), String> {
+        if let Some(fill) = self.fill {
+            if !(0.0..=1.0).contains(&fill) {
+                return Err("fill must be between 0 and 1.".into());
+            }
+        }
+
+        if self.width == Some(0) || self.height == Some(0) {
+            return Err("width and height must be greater than 0.".into());
+        }
+
+        if let (Some(width), Some(height), Some(points)) =
+            (self.width, self.height, &self.connected)
+        {
+            for p in points {
+                if p.x < 0 || p.y < 0 || p.x >= width as i64 || p.y >= height as i64 {
+                    return Err(format!("Point {p:?} is outside of the grid."));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl CellularAutomaton {
+    pub fn builder() -> CellularAutomatonBuilder {
+        CellularAutomatonBuilder::default()
+    }
+
+    /// Generate a new grid.
+    pub fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> Grid<bool> {
+        let mut cells: Vec<bool> = (0..(self.width * self.height))
+            .map(|_| rng.gen_bool(self.fill))
+            .collect();
+
+        for _ in 0..self.steps {
+            cells = self.step(&cells);
+        }
+
+        let keep: Vec<usize> = self
+            .connected
+            .iter()
+            .map(|p| p.y as usize * self.width + p.x as usize)
+            .collect();
+
+        self.remove_regions(&mut cells, false, self.min_gap, &keep);
+
+        for idx in keep.iter() {
+            cells[*idx] = false;
+        }
+
+        self.connect(&mut cells, &keep);
+
+        // carving can split live regions, so they are removed last; killing
+        // cells never disconnects or shrinks a dead region
+        self.remove_regions(&mut cells, true, self.min_region, &[]);
+
+        let mut out = Grid::new(self.width, self.height, false);
+        for (idx, alive) in cells.into_iter().enumerate() {
+            out[idx / self.width][idx % self.width] = alive;
+        }
+
+        out
+    }
+
+    fn step(&self, cells: &[bool]) -> Vec<bool> {
+        let w = self.width as i64;
+        let h = self.height as i64;
+
+        let alive = |x: i64, y: i64| {
+            if (0..w).contains(&x) && (0..h).contains(&y) {
+                cells[(y * w + x) as usize]
+            } else {
+                match self.edge {
+                    Edge::Alive => true,
+                    Edge::Dead => false,
+                    Edge::Wrap => cells[(y.rem_euclid(h) * w + x.rem_euclid(w)) as usize],
+                }
+            }
+        };
+
+        (0..(w * h))
+            .map(|idx| {
+                let (x, y) = (idx % w, idx / w);
+                let neighbors = (-1..=1)
+                    .flat_map(|dy| (-1..=1).map(move |dx| (dx, dy)))
+                    .filter(|(dx, dy)| (*dx, *dy) != (0, 0) && alive(x + dx, y + dy))
+                    .count();
+
+                if cells[idx as usize] {
+                    self.rule.survives(neighbors)
+                } else {
+                    self.rule.is_born(neighbors)
+                }
+            })
+            .collect()
+    }
+
+    /// The orthogonal neighbors of `idx` within the grid.
+    fn neighbors(&self, idx: usize) -> impl Iterator<Item = usize> {
+        let (w, h) = (self.width, self.height);
+        let (x, y) = (idx % w, idx / w);
+
+        [
+            (y > 0).then(|| idx - w),
+            (x + 1 < w).then(|| idx + 1),
+            (y + 1 < h).then(|| idx + w),
+            (x > 0).then(|| idx - 1),
+        ]
+        .into_iter()
+        .flatten()
+    }
+
+    /// Flip every region of cells with the given `state` that is smaller than
+    /// `min_size`, unless it contains one of the `keep` cells.
+    fn remove_regions(&self, cells: &mut [bool], state: bool, min_size: usize, keep: &[usize]) {
+        if min_size <= 1 {
+            return;
+        }
+
+        let mut seen = vec![false; cells.len()];
+
+        for start in 0..cells.len() {
+            if seen[start] || cells[start] != state {
+                continue;
+            }
+
+            seen[start] = true;
+            let mut region = vec![start];
+            let mut queue = VecDeque::from([start]);
+
+            while let Some(idx) = queue.pop_front() {
+                for next in self.neighbors(idx) {
+                    if !seen[next] && cells[next] == state {
+                        seen[next] = true;
+                        region.push(next);
+                        queue.push_back(next);
+                    }
+                }
+            }
+
+            if region.len() < min_size && !region.iter().any(|idx| keep.contains(idx)) {
+                for idx in region {
+                    cells[idx] = !state;
+                }
+            }
+        }
+    }
+
+    /// Carve dead passages so every one of the `points` is connected to the
+    /// first through dead cells.
+    fn connect(&self, cells: &mut [bool], points: &[usize]) {
+        let Some(first) = points.first() else {
+            return;
+        };
+
+        let mut connected = vec![false; cells.len()];
+        self.flood(cells, &mut connected, *first);
+
+        for point in points.iter().skip(1) {
+            if connected[*point] {
+                continue;
+            }
+
+            // a 0-1 breadth-first search, where entering a live cell costs 1
+            let mut cost = vec![usize::MAX; cells.len()];
+            let mut prev = vec![usize::MAX; cells.len()];
+            let mut queue = VecDeque::from([*point]);
+            cost[*point] = 0;
+
+            let end = loop {
+                let idx = queue.pop_front().expect("the first point is reachable");
+                if connected[idx] {
+                    break idx;
+                }
+
+                for next in self.neighbors(idx) {
+                    let step = usize::from(cells[next]);
+                    if cost[idx] + step < cost[next] {
+                        cost[next] = cost[idx] + step;
+                        prev[next] = idx;
+                        if step == 0 {
+                            queue.push_front(next);
+                        } else {
+                            queue.push_back(next);
+                        }
+                    }
+                }
+            };
+
+            let mut idx = end;
+            while idx != *point {
+                cells[idx] = false;
+                idx = prev[idx];
+            }
+
+            self.flood(cells, &mut connected, *point);
+        }
+    }
+
+    /// Mark every dead cell reachable from `start` as connected.
+    fn flood(&self, cells: &[bool], connected: &mut [bool], start: usize) {
+        connected[start] = true;
+        let mut queue = VecDeque::from([start]);
+
+        while let Some(idx) = queue.pop_front() {
+            for next in self.neighbors(idx) {
+                if !connected[next] && !cells[next] {
+                    connected[next] = true;
+                    queue.push_back(next);
+                }
+            }
+        }
+    }
+}
+
+impl InputGenerator for CellularAutomaton {
+    type GeneratorError = Infallible;
+    type Output = Grid<bool>;
+
+    fn gen_input<R: Rng + Clone>(&self, rng: &mut R) -> Result<Self::Output, Self::GeneratorError> {
+        Ok(self.generate(rng))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    fn regions(grid: &Grid<bool>, state: bool) -> Vec<usize> {
+        let (w, h) = (grid.width(), grid.height());
+        let mut seen = vec![vec![false; w]; h];
+        let mut sizes = Vec::new();
+
+        for row in 0..h {
+            for col in 0..w {
+                if seen[row][col] || grid[row][col] != state {
+                    continue;
+                }
+
+                seen[row][col] = true;
+                let mut stack = vec![(row, col)];
+                let mut size = 0;
+                while let Some((r, c)) = stack.pop() {
+                    size += 1;
+                    let next = [
+                        (r.wrapping_sub(1), c),
+                        (r + 1, c),
+                        (r, c.wrapping_sub(1)),
+                        (r, c + 1),
+                    ];
+                    for (nr, nc) in next {
+                        if nr < h && nc < w && !seen[nr][nc] && grid[nr][nc] == state {
+                            seen[nr][nc] = true;
+                            stack.push((nr, nc));
+                        }
+                    }
+                }
+                sizes.push(size);
+            }
+        }
+
+        sizes
+    }
+
+    #[test]
+    fn life() {
+        // a blinker oscillates with a period of two
+        let blinker = CellularAutomaton::builder()
+            .width(5)
+            .height(5)
+            .rule("B3/S23".parse().unwrap())
+            .edge(Edge::Dead)
+            .build()
+            .unwrap();
+
+        let mut cells = vec![false; 25];
+        cells[11..14].fill(true);
+
+        let once = blinker.step(&cells);
+        assert_eq!(
+            (0..25).filter(|i| once[*i]).collect::<Vec<_>>(),
+            vec![7, 12, 17]
+        );
+        assert_eq!(blinker.step(&once), cells);
+    }
+
+    #[test]
+    fn edges() {
+        let cells = vec![false; 9];
+        let rule = Rule::new(&[3], &[]);
+
+        let build = |edge| {
+            CellularAutomaton::builder()
+                .width(3)
+                .height(3)
+                .rule(rule)
+                .edge(edge)
+                .build()
+                .unwrap()
+        };
+
+        // corners have five neighbors beyond the edge, others have three
+        let alive = build(Edge::Alive).step(&cells);
+        assert_eq!(alive.iter().filter(|c| **c).count(), 4);
+        assert!(build(Edge::Dead).step(&cells).iter().all(|c| !c));
+        assert!(build(Edge::Wrap).step(&cells).iter().all(|c| !c));
+    }
+
+    #[test]
+    fn regions_and_connections() {
+        let points = [
+            Point::new(0, 0),
+            Point::new(79, 0),
+            Point::new(40, 25),
+            Point::new(79, 49),
+        ];
+
+        for seed in 0..5 {
+            let mut builder = CellularAutomaton::builder();
+            builder
+                .width(80)
+                .height(50)
+                .fill(0.5)
+                .min_region(6)
+                .min_gap(12);
+            for p in points {
+                builder.connect(p);
+            }
+            let grid = builder
+                .build()
+                .unwrap()
+                .generate(&mut StdRng::seed_from_u64(seed));
+
+            assert!(regions(&grid, true).iter().all(|size| *size >= 6));
+
+            // the points all lie in a single dead region, and no other dead
+            // region is too small
+            assert!(points.iter().all(|p| !grid[p]));
+            let gaps = regions(&grid, false);
+            assert!(gaps.iter().all(|size| *size >= 12));
+
+            let mut connected = grid.clone();
+            let mut stack = vec![(0, 0)];
+            while let Some((r, c)) = stack.pop() {
+                if r >= 50 || c >= 80 || connected[r][c] {
+                    continue;
+                }
+                connected[r][c] = true;
+                stack.extend([
+                    (r.wrapping_sub(1), c),
+                    (r + 1, c),
+                    (r, c.wrapping_sub(1)),
+                    (r, c + 1),
+                ]);
+            }
+            assert!(points.iter().all(|p| connected[p]));
+        }
+    }
+
+    #[test]
+    fn validation() {
+        assert!(CellularAutomaton::builder()
+            .width(10)
+            .height(10)
+            .fill(1.5)
+            .build()
+            .is_err());
+        assert!(CellularAutomaton::builder()
+            .width(10)
+            .height(10)
+            .connect(Point::new(10, 3))
+            .build()
+            .is_err());
+        assert!(CellularAutomaton::builder()
+            .width(0)
+            .height(10)
+            .build()
+            .is_err());
+    }
+}
@@ -0,0 +1,108 @@
+use derive_builder::Builder;
+use rand::Rng;
+
+use crate::grid::Grid;
+
+use super::{lerp, Blend, FieldLayer};
+
+/// A layer whose values change at a constant rate across the field, from
+/// `from` at one edge to `to` at the opposite edge.
+///
+/// The `angle` is the direction of increase in degrees, measured clockwise
+/// from east. Because rows grow downward, an angle of `90` ramps from the top
+/// row to the bottom row.
+///
+/// # Examples
+/// ```
+/// use rand::thread_rng;
+/// use proliferatr::grid::field::{FieldGenerator, LinearRamp};
+///
+/// let grid = FieldGenerator::new(5, 2)
+///     .range(1, 9)
+///     .layer(LinearRamp::builder().build().unwrap())
+///     .generate(&mut thread_rng())
+///     .unwrap();
+///
+/// assert_eq!(grid.to_string(), "13579\n13579");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Builder)]
+pub struct LinearRamp {
+    #[builder(default = "0.0")]
+    angle: f64,
+
+    #[builder(default = "0.0")]
+    from: f64,
+
+    #[builder(default = "1.0")]
+    to: f64,
+
+    #[builder(default)]
+    blend: Blend,
+}
+
+impl LinearRamp {
+    pub fn builder() -> LinearRampBuilder {
+        LinearRampBuilder::default()
+    }
+}
+
+impl FieldLayer for LinearRamp {
+    fn apply<R: Rng + ?Sized>(&self, field: &mut Grid<f64>, _rng: &mut R) {
+        let (dy, dx) = self.angle.to_radians().sin_cos();
+        let project = |x: f64, y: f64| x * dx + y * dy;
+
+        let max_x = field.width().saturating_sub(1) as f64;
+        let max_y = field.height().saturating_sub(1) as f64;
+        let corners =
+            [(0.0, 0.0), (max_x, 0.0), (0.0, max_y), (max_x, max_y)].map(|(x, y)| project(x, y));
+        let lo = corners.iter().copied().fold(f64::INFINITY, f64::min);
+        let hi = corners.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+        for row in 0..field.height() {
+            for col in 0..field.width() {
+                let t = if hi - lo > f64::EPSILON {
+                    (project(col as f64, row as f64) - lo) / (hi - lo)
+                } else {
+                    0.0
+                };
+
+                let value = lerp(self.from, self.to, t);
+                field[row][col] = self.blend.combine(field[row][col], value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::thread_rng;
+
+    use super::*;
+
+    #[test]
+    fn directions() {
+        let mut field = Grid::new(2, 3, 0.0);
+        LinearRamp::builder()
+            .angle(90.0)
+            .to(4.0)
+            .build()
+            .unwrap()
+            .apply(&mut field, &mut thread_rng());
+
+        let rows: Vec<_> = (0..3).map(|row| field[row][1].round()).collect();
+        assert_eq!(rows, vec![0.0, 2.0, 4.0]);
+        assert_eq!(field[2][0].round(), 4.0);
+
+        let mut field = Grid::new(3, 3, 0.0);
+        LinearRamp::builder()
+            .angle(225.0)
+            .build()
+            .unwrap()
+            .apply(&mut field, &mut thread_rng());
+
+        // increasing towards the top left
+        assert!((field[0][0] - 1.0).abs() < 1e-9);
+        assert!(field[2][2].abs() < 1e-9);
+        assert!((field[1][1] - 0.5).abs() < 1e-9);
+    }
+}
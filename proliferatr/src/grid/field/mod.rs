@@ -0,0 +1,322 @@
+//! Generators for grids of weights, such as heat loss or terrain costs, built
+//! up from composable layers.
+use std::fmt;
+
+use rand::{Rng, RngCore};
+use thiserror::Error;
+
+use crate::InputGenerator;
+
+use super::{DigitGrid, Grid};
+
+mod linear_ramp;
+mod noise;
+mod plateaus;
+mod radial_gradient;
+mod white_noise;
+
+pub use linear_ramp::{LinearRamp, LinearRampBuilder, LinearRampBuilderError};
+pub use noise::{Noise, NoiseBuilder, NoiseBuilderError, NoiseKind};
+pub use plateaus::{Plateaus, PlateausBuilder, PlateausBuilderError};
+pub use radial_gradient::{
+    Falloff, Metric, RadialGradient, RadialGradientBuilder, RadialGradientBuilderError,
+};
+pub use white_noise::{WhiteNoise, WhiteNoiseBuilder, WhiteNoiseBuilderError};
+
+#[derive(Debug, Clone, Error)]
+pub enum FieldError {
+    #[error("Invalid range: {min} is greater than {max}.")]
+    InvalidRange { min: u32, max: u32 },
+
+    #[error("Values up to {0} cannot be represented as digits.")]
+    NotDigits(u32),
+}
+
+/// How the values produced by a layer are combined with the existing values
+/// of the field.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Blend {
+    /// Add the layer's value to the field.
+    #[default]
+    Add,
+
+    /// Multiply the field by the layer's value.
+    Multiply,
+
+    /// Keep the larger of the two values.
+    Max,
+
+    /// Keep the smaller of the two values.
+    Min,
+
+    /// Replace the field's value with the layer's value.
+    Replace,
+}
+
+impl Blend {
+    /// Combine the `existing` field value with a layer's `value`.
+    pub fn combine(&self, existing: f64, value: f64) -> f64 {
+        match self {
+            Self::Add => existing + value,
+            Self::Multiply => existing * value,
+            Self::Max => existing.max(value),
+            Self::Min => existing.min(value),
+            Self::Replace => value,
+        }
+    }
+}
+
+/// A single layer of a [FieldGenerator], which alters the raw values of a
+/// field.
+pub trait FieldLayer {
+    /// Apply this layer to the `field`, using `rng` for any random decisions.
+    fn apply<R: Rng + ?Sized>(&self, field: &mut Grid<f64>, rng: &mut R);
+}
+
+/// An object-safe version of [FieldLayer], so that layers of different types
+/// can be stored together.
+trait DynFieldLayer: fmt::Debug {
+    fn apply_dyn(&self, field: &mut Grid<f64>, rng: &mut dyn RngCore);
+}
+
+impl<L: FieldLayer + fmt::Debug> DynFieldLayer for L {
+    fn apply_dyn(&self, field: &mut Grid<f64>, rng: &mut dyn RngCore) {
+        self.apply(field, rng)
+    }
+}
+
+/// Generates a grid of weights by applying a sequence of [FieldLayer] to a
+/// field of zeroes, in order, then mapping the result to a range of integers.
+///
+/// By default, the raw values are rescaled so that the lowest value in the
+/// field becomes the minimum of the range and the highest becomes the maximum.
+/// If normalization is disabled, the raw values are rounded and clamped to the
+/// range instead.
+///
+/// # Examples
+/// ```
+/// use rand::thread_rng;
+/// use proliferatr::{
+///     grid::field::{FieldGenerator, Metric, Noise, RadialGradient},
+///     point::Point,
+/// };
+///
+/// let field = FieldGenerator::new(40, 30)
+///     .range(1, 9)
+///     .layer(
+///         RadialGradient::builder()
+///             .metric(Metric::Manhattan)
+///             .inner(3.0)
+///             .build()
+///             .unwrap(),
+///     )
+///     .layer(Noise::builder().scale(6.0).octaves(3).build().unwrap());
+///
+/// let grid = field.generate_digits(&mut thread_rng()).unwrap();
+///
+/// assert_eq!(grid.width(), 40);
+/// assert_eq!(grid.height(), 30);
+///
+/// // the center is higher than the corners
+/// assert!(grid[Point::new(20, 15)] > grid[Point::new(0, 0)]);
+/// assert!((0..30).all(|row| grid[row].iter().all(|v| (1..=9).contains(v))));
+/// ```
+#[derive(Debug)]
+pub struct FieldGenerator {
+    width: usize,
+    height: usize,
+    min: u32,
+    max: u32,
+    normalize: bool,
+    layers: Vec<Box<dyn DynFieldLayer>>,
+}
+
+impl FieldGenerator {
+    /// Make a new generator for fields of the given dimensions, with values
+    /// from 0 to 9 (inclusive).
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            min: 0,
+            max: 9,
+            normalize: true,
+            layers: Vec::new(),
+        }
+    }
+
+    /// Set the inclusive range of the generated values.
+    pub fn range(mut self, min: u32, max: u32) -> Self {
+        self.min = min;
+        self.max = max;
+        self
+    }
+
+    /// Set whether the raw values are rescaled to the range (the default) or
+    /// rounded and clamped to it.
+    pub fn normalize(mut self, normalize: bool) -> Self {
+        self.normalize = normalize;
+        self
+    }
+
+    /// Append a layer to this generator.
+    pub fn layer<L: FieldLayer + fmt::Debug + 'static>(mut self, layer: L) -> Self {
+        self.layers.push(Box::new(layer));
+        self
+    }
+
+    /// Append a layer to this generator.
+    pub fn push<L: FieldLayer + fmt::Debug + 'static>(&mut self, layer: L) {
+        self.layers.push(Box::new(layer));
+    }
+
+    /// Generate the raw values of a field by applying every layer.
+    pub fn generate_raw<R: Rng + ?Sized>(&self, mut rng: &mut R) -> Grid<f64> {
+        let mut field = Grid::new(self.width, self.height, 0.0);
+
+        for layer in self.layers.iter() {
+            layer.apply_dyn(&mut field, &mut rng);
+        }
+
+        field
+    }
+
+    /// Generate a field, with values mapped to the configured range.
+    pub fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<Grid<u32>, FieldError> {
+        if self.min > self.max {
+            return Err(FieldError::InvalidRange {
+                min: self.min,
+                max: self.max,
+            });
+        }
+
+        let raw = self.generate_raw(rng);
+        let (lo, hi) = (0..raw.height())
+            .flat_map(|row| raw[row].iter())
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| {
+                (lo.min(*v), hi.max(*v))
+            });
+
+        let min = self.min as f64;
+        let max = self.max as f64;
+        let mut out = Grid::new(self.width, self.height, self.min);
+
+        for row in 0..self.height {
+            for col in 0..self.width {
+                let v = raw[row][col];
+                let v = if !self.normalize {
+                    v
+                } else if hi > lo {
+                    min + (v - lo) / (hi - lo) * (max - min)
+                } else {
+                    min
+                };
+
+                out[row][col] = v.round().clamp(min, max) as u32;
+            }
+        }
+
+        Ok(out)
+    }
+
+    /// Generate a field of digits. The configured range must not exceed 9.
+    pub fn generate_digits<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<DigitGrid, FieldError> {
+        if self.max > 9 {
+            return Err(FieldError::NotDigits(self.max));
+        }
+
+        let field = self.generate(rng)?;
+        let mut out = DigitGrid::new(self.width, self.height, 0);
+
+        for row in 0..self.height {
+            for col in 0..self.width {
+                out[row][col] = field[row][col] as u8;
+            }
+        }
+
+        Ok(out)
+    }
+}
+
+impl InputGenerator for FieldGenerator {
+    type GeneratorError = FieldError;
+    type Output = Grid<u32>;
+
+    fn gen_input<R: Rng + Clone>(&self, rng: &mut R) -> Result<Self::Output, Self::GeneratorError> {
+        self.generate(rng)
+    }
+}
+
+/// Linearly interpolate from `a` to `b` by `t`.
+fn lerp(a: f64, b: f64, t: f64) -> f64 {
+    a + (b - a) * t
+}
+
+/// The smoothstep easing of `t`, which has zero slope at 0 and 1.
+fn smoothstep(t: f64) -> f64 {
+    t * t * (3.0 - 2.0 * t)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn ranges() {
+        let mut rng = StdRng::seed_from_u64(3);
+
+        let field = FieldGenerator::new(10, 10).range(5, 2);
+        assert!(matches!(
+            field.generate(&mut rng),
+            Err(FieldError::InvalidRange { min: 5, max: 2 })
+        ));
+
+        let field = FieldGenerator::new(10, 10).range(0, 20);
+        assert!(matches!(
+            field.generate_digits(&mut rng),
+            Err(FieldError::NotDigits(20))
+        ));
+
+        // a flat field maps to the minimum
+        let field = FieldGenerator::new(4, 3).range(2, 7);
+        assert_eq!(field.generate(&mut rng).unwrap(), Grid::new(4, 3, 2));
+
+        // without normalization, values are clamped
+        let field = FieldGenerator::new(4, 3)
+            .range(2, 7)
+            .normalize(false)
+            .layer(WhiteNoise::builder().min(10.0).max(20.0).build().unwrap());
+        assert_eq!(field.generate(&mut rng).unwrap(), Grid::new(4, 3, 7));
+
+        // with normalization, the whole range is used
+        let field = FieldGenerator::new(20, 20)
+            .range(1, 9)
+            .layer(WhiteNoise::builder().min(10.0).max(20.0).build().unwrap());
+        let grid = field.generate(&mut rng).unwrap();
+        let values: Vec<_> = (0..20).flat_map(|row| grid[row].clone()).collect();
+        assert_eq!(values.iter().min(), Some(&1));
+        assert_eq!(values.iter().max(), Some(&9));
+    }
+
+    #[test]
+    fn reproducible() {
+        let field = FieldGenerator::new(30, 30)
+            .layer(Noise::builder().octaves(2).build().unwrap())
+            .layer(Plateaus::builder().count(3).build().unwrap());
+
+        let a = field.generate(&mut StdRng::seed_from_u64(11)).unwrap();
+        let b = field.generate(&mut StdRng::seed_from_u64(11)).unwrap();
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn blending() {
+        assert_eq!(Blend::Add.combine(2.0, 3.0), 5.0);
+        assert_eq!(Blend::Multiply.combine(2.0, 3.0), 6.0);
+        assert_eq!(Blend::Max.combine(2.0, 3.0), 3.0);
+        assert_eq!(Blend::Min.combine(2.0, 3.0), 2.0);
+        assert_eq!(Blend::Replace.combine(2.0, 3.0), 3.0);
+    }
+}
@@ -0,0 +1,244 @@
+use std::f64::consts::{FRAC_1_SQRT_2, TAU};
+
+use derive_builder::Builder;
+use rand::Rng;
+
+use crate::grid::Grid;
+
+use super::{lerp, smoothstep, Blend, FieldLayer};
+
+/// The kind of coherent noise produced by a [Noise] layer.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NoiseKind {
+    /// Random values at the lattice points, smoothly interpolated. This tends
+    /// to produce blobby features aligned with the lattice.
+    #[default]
+    Value,
+
+    /// Random gradients at the lattice points (Perlin noise). This produces
+    /// more natural-looking ridges and valleys.
+    Perlin,
+}
+
+/// A layer of smooth, coherent noise with values between `0` and `amplitude`.
+///
+/// `scale` is the distance, in cells, between the lattice points of the first
+/// octave, so larger scales produce larger features. Each additional octave
+/// halves the distance between lattice points and scales its contribution by
+/// `persistence`, adding finer detail.
+///
+/// # Examples
+/// ```
+/// use rand::thread_rng;
+/// use proliferatr::grid::field::{FieldGenerator, Noise, NoiseKind};
+///
+/// let noise = Noise::builder()
+///     .kind(NoiseKind::Perlin)
+///     .scale(10.0)
+///     .octaves(4)
+///     .build()
+///     .unwrap();
+///
+/// let grid = FieldGenerator::new(50, 50)
+///     .layer(noise)
+///     .generate(&mut thread_rng())
+///     .unwrap();
+///
+/// // neighboring cells rarely differ by much
+/// let jumps = (0..50)
+///     .flat_map(|row| grid[row].windows(2).map(|w| w[0].abs_diff(w[1])))
+///     .filter(|d| *d > 3)
+///     .count();
+/// assert!(jumps < 50);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct Noise {
+    #[builder(default)]
+    kind: NoiseKind,
+
+    #[builder(default = "8.0")]
+    scale: f64,
+
+    #[builder(default = "1")]
+    octaves: usize,
+
+    #[builder(default = "0.5")]
+    persistence: f64,
+
+    #[builder(default = "1.0")]
+    amplitude: f64,
+
+    #[builder(default)]
+    blend: Blend,
+}
+
+impl NoiseBuilder {
+    fn validate(&self) -> Result<(), String> {
+        if let Some(scale) = self.scale {
+            if scale < 1.0 {
+                return Err("scale must be at least 1.".into());
+            }
+        }
+
+        if self.octaves == Some(0) {
+            return Err("octaves must be greater than 0.".into());
+        }
+
+        if let Some(persistence) = self.persistence {
+            if persistence <= 0.0 {
+                return Err("persistence must be greater than 0.".into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Noise {
+    pub fn builder() -> NoiseBuilder {
+        NoiseBuilder::default()
+    }
+}
+
+impl FieldLayer for Noise {
+    fn apply<R: Rng + ?Sized>(&self, field: &mut Grid<f64>, rng: &mut R) {
+        let width = field.width();
+        let height = field.height();
+        let mut total = vec![0.0; width * height];
+        let mut weight = 1.0;
+        let mut weights = 0.0;
+        let mut spacing = self.scale;
+
+        for _ in 0..self.octaves {
+            let lattice = Lattice::new(self.kind, width, height, spacing, rng);
+
+            for row in 0..height {
+                for col in 0..width {
+                    total[row * width + col] +=
+                        weight * lattice.sample(col as f64 / spacing, row as f64 / spacing);
+                }
+            }
+
+            weights += weight;
+            weight *= self.persistence;
+            spacing = (spacing / 2.0).max(1.0);
+        }
+
+        for row in 0..height {
+            for col in 0..width {
+                let value = self.amplitude * total[row * width + col] / weights;
+                field[row][col] = self.blend.combine(field[row][col], value);
+            }
+        }
+    }
+}
+
+/// The random values or gradients at the lattice points of one octave.
+struct Lattice {
+    kind: NoiseKind,
+    width: usize,
+    points: Vec<(f64, f64)>,
+}
+
+impl Lattice {
+    fn new<R: Rng + ?Sized>(
+        kind: NoiseKind,
+        width: usize,
+        height: usize,
+        spacing: f64,
+        rng: &mut R,
+    ) -> Self {
+        let lattice_width = (width as f64 / spacing).ceil() as usize + 2;
+        let lattice_height = (height as f64 / spacing).ceil() as usize + 2;
+
+        let points = (0..(lattice_width * lattice_height))
+            .map(|_| match kind {
+                NoiseKind::Value => (rng.gen::<f64>(), 0.0),
+                NoiseKind::Perlin => {
+                    let (y, x) = rng.gen_range(0.0..TAU).sin_cos();
+                    (x, y)
+                }
+            })
+            .collect();
+
+        Self {
+            kind,
+            width: lattice_width,
+            points,
+        }
+    }
+
+    /// The noise at `(x, y)` in lattice coordinates, between 0 and 1.
+    fn sample(&self, x: f64, y: f64) -> f64 {
+        let x0 = x.floor() as usize;
+        let y0 = y.floor() as usize;
+        let fx = x - x0 as f64;
+        let fy = y - y0 as f64;
+
+        let corner = |dx: usize, dy: usize| {
+            let (a, b) = self.points[(y0 + dy) * self.width + x0 + dx];
+            match self.kind {
+                NoiseKind::Value => a,
+                NoiseKind::Perlin => a * (fx - dx as f64) + b * (fy - dy as f64),
+            }
+        };
+
+        let (tx, ty) = match self.kind {
+            NoiseKind::Value => (smoothstep(fx), smoothstep(fy)),
+            NoiseKind::Perlin => (fade(fx), fade(fy)),
+        };
+
+        let top = lerp(corner(0, 0), corner(1, 0), tx);
+        let bottom = lerp(corner(0, 1), corner(1, 1), tx);
+        let value = lerp(top, bottom, ty);
+
+        match self.kind {
+            NoiseKind::Value => value,
+            // gradient noise falls between -sqrt(1/2) and sqrt(1/2)
+            NoiseKind::Perlin => (value * FRAC_1_SQRT_2 + 0.5).clamp(0.0, 1.0),
+        }
+    }
+}
+
+/// Perlin's quintic fade curve, which also has zero second derivative at 0
+/// and 1.
+fn fade(t: f64) -> f64 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn bounded() {
+        let mut rng = StdRng::seed_from_u64(5);
+
+        for kind in [NoiseKind::Value, NoiseKind::Perlin] {
+            let mut field = Grid::new(37, 23, 0.0);
+            Noise::builder()
+                .kind(kind)
+                .scale(5.0)
+                .octaves(3)
+                .amplitude(2.0)
+                .build()
+                .unwrap()
+                .apply(&mut field, &mut rng);
+
+            let values: Vec<f64> = (0..23).flat_map(|row| field[row].clone()).collect();
+            assert!(values.iter().all(|v| (0.0..=2.0).contains(v)));
+
+            // not flat
+            let lo = values.iter().copied().fold(f64::INFINITY, f64::min);
+            let hi = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+            assert!(hi - lo > 0.5);
+        }
+
+        assert!(Noise::builder().scale(0.5).build().is_err());
+        assert!(Noise::builder().octaves(0).build().is_err());
+        assert!(Noise::builder().persistence(0.0).build().is_err());
+    }
+}
@@ -0,0 +1,145 @@
+use derive_builder::Builder;
+use rand::Rng;
+
+use crate::grid::Grid;
+
+use super::FieldLayer;
+
+/// Flattens `count` randomly placed rectangular regions of the field to the
+/// average of their existing values, then raises each by `offset`.
+///
+/// Region dimensions are chosen between `min_size` and `max_size`
+/// (inclusive), and regions may overlap. A negative `offset` produces basins
+/// instead of plateaus.
+///
+/// # Examples
+/// ```
+/// use rand::thread_rng;
+/// use proliferatr::grid::field::{FieldGenerator, Plateaus, WhiteNoise};
+///
+/// let plateau = Plateaus::builder()
+///     .count(1)
+///     .min_size(4)
+///     .max_size(4)
+///     .offset(100.0)
+///     .build()
+///     .unwrap();
+///
+/// let grid = FieldGenerator::new(10, 10)
+///     .layer(WhiteNoise::builder().build().unwrap())
+///     .layer(plateau)
+///     .generate(&mut thread_rng())
+///     .unwrap();
+///
+/// // only the plateau reaches the top of the range
+/// let top = (0..10).flat_map(|row| grid[row].clone()).filter(|v| *v == 9).count();
+/// assert_eq!(top, 16);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct Plateaus {
+    count: usize,
+
+    #[builder(default = "2")]
+    min_size: usize,
+
+    #[builder(default = "5")]
+    max_size: usize,
+
+    #[builder(default = "0.0")]
+    offset: f64,
+}
+
+impl PlateausBuilder {
+    fn validate(&self) -> Result<(), String> {
+        let min_size = self.min_size.unwrap_or(2);
+        let max_size = self.max_size.unwrap_or(5);
+
+        if min_size == 0 {
+            return Err("min_size must be greater than 0.".into());
+        }
+
+        if min_size > max_size {
+            return Err("min_size must not be greater than max_size.".into());
+        }
+
+        Ok(())
+    }
+}
+
+impl Plateaus {
+    pub fn builder() -> PlateausBuilder {
+        PlateausBuilder::default()
+    }
+}
+
+impl FieldLayer for Plateaus {
+    fn apply<R: Rng + ?Sized>(&self, field: &mut Grid<f64>, rng: &mut R) {
+        if field.width() < self.min_size || field.height() < self.min_size {
+            return;
+        }
+
+        for _ in 0..self.count {
+            let w = rng.gen_range(self.min_size..=self.max_size.min(field.width()));
+            let h = rng.gen_range(self.min_size..=self.max_size.min(field.height()));
+            let col = rng.gen_range(0..=(field.width() - w));
+            let row = rng.gen_range(0..=(field.height() - h));
+
+            let sum: f64 = (row..(row + h))
+                .flat_map(|r| field[r][col..(col + w)].iter())
+                .sum();
+            let level = sum / (w * h) as f64 + self.offset;
+
+            for r in row..(row + h) {
+                field[r][col..(col + w)].fill(level);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn flattens() {
+        let mut rng = StdRng::seed_from_u64(9);
+        let mut field = Grid::new(6, 6, 0.0);
+        for row in 0..6 {
+            for col in 0..6 {
+                field[row][col] = (row * 6 + col) as f64;
+            }
+        }
+        let before: f64 = (0..6).flat_map(|row| field[row].clone()).sum();
+
+        Plateaus::builder()
+            .count(1)
+            .min_size(3)
+            .max_size(3)
+            .build()
+            .unwrap()
+            .apply(&mut field, &mut rng);
+
+        // flattening to the mean keeps the total
+        let values: Vec<f64> = (0..6).flat_map(|row| field[row].clone()).collect();
+        assert_eq!(values.iter().sum::<f64>(), before);
+
+        let (row, col) = (0..6)
+            .flat_map(|r| (0..6).map(move |c| (r, c)))
+            .find(|(r, c)| field[*r][*c] != (r * 6 + c) as f64)
+            .unwrap();
+        let level = field[row][col];
+        let flat = values.iter().filter(|v| **v == level).count();
+        assert_eq!(flat, 9);
+
+        assert!(Plateaus::builder().count(1).min_size(0).build().is_err());
+        assert!(Plateaus::builder()
+            .count(1)
+            .min_size(4)
+            .max_size(3)
+            .build()
+            .is_err());
+    }
+}
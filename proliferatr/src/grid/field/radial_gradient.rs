@@ -0,0 +1,197 @@
+use derive_builder::Builder;
+use rand::Rng;
+
+use crate::{grid::Grid, point::Point};
+
+use super::{lerp, smoothstep, Blend, FieldLayer};
+
+/// How distance is measured from the center of a [RadialGradient].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Metric {
+    /// Straight-line distance, producing circular contours.
+    #[default]
+    Euclidean,
+
+    /// Sum of the horizontal and vertical distances, producing diamond
+    /// contours.
+    Manhattan,
+
+    /// The larger of the horizontal and vertical distances, producing square
+    /// contours.
+    Chebyshev,
+}
+
+impl Metric {
+    /// The distance between `(x1, y1)` and `(x2, y2)` under this metric.
+    pub fn distance(&self, x1: f64, y1: f64, x2: f64, y2: f64) -> f64 {
+        let dx = (x1 - x2).abs();
+        let dy = (y1 - y2).abs();
+
+        match self {
+            Self::Euclidean => dx.hypot(dy),
+            Self::Manhattan => dx + dy,
+            Self::Chebyshev => dx.max(dy),
+        }
+    }
+}
+
+/// How the value of a [RadialGradient] changes from the center to its radius.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Falloff {
+    /// Change at a constant rate.
+    #[default]
+    Linear,
+
+    /// Change slowly near the center and the radius, and quickly in between.
+    Smooth,
+
+    /// Use the inner value for cells closer than the radius, and the outer
+    /// value for every other cell.
+    Step,
+}
+
+/// A layer whose values change with the distance from a center point, from
+/// `inner` at the center to `outer` at the `radius` and beyond.
+///
+/// The center defaults to the middle of the field, and the radius defaults to
+/// the distance from the center to the farthest corner.
+///
+/// # Examples
+/// ```
+/// use rand::thread_rng;
+/// use proliferatr::{
+///     grid::field::{Falloff, FieldGenerator, Metric, RadialGradient},
+///     point::Point,
+/// };
+///
+/// let gradient = RadialGradient::builder()
+///     .center(Point::new(2, 2))
+///     .radius(2.0)
+///     .metric(Metric::Manhattan)
+///     .falloff(Falloff::Step)
+///     .inner(5.0)
+///     .outer(1.0)
+///     .build()
+///     .unwrap();
+///
+/// let grid = FieldGenerator::new(5, 5)
+///     .normalize(false)
+///     .layer(gradient)
+///     .generate(&mut thread_rng())
+///     .unwrap();
+///
+/// assert_eq!(
+///     grid.to_string(),
+///     "11111\n11511\n15551\n11511\n11111"
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct RadialGradient {
+    #[builder(default, setter(strip_option))]
+    center: Option<Point>,
+
+    #[builder(default, setter(strip_option))]
+    radius: Option<f64>,
+
+    #[builder(default = "1.0")]
+    inner: f64,
+
+    #[builder(default = "0.0")]
+    outer: f64,
+
+    #[builder(default)]
+    metric: Metric,
+
+    #[builder(default)]
+    falloff: Falloff,
+
+    #[builder(default)]
+    blend: Blend,
+}
+
+impl RadialGradientBuilder {
+    fn validate(&self) -> Result<(), String> {
+        if let Some(Some(radius)) = self.radius {
+            if radius <= 0.0 {
+                return Err("radius must be greater than 0.".into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl RadialGradient {
+    pub fn builder() -> RadialGradientBuilder {
+        RadialGradientBuilder::default()
+    }
+}
+
+impl FieldLayer for RadialGradient {
+    fn apply<R: Rng + ?Sized>(&self, field: &mut Grid<f64>, _rng: &mut R) {
+        let (cx, cy) = match self.center {
+            Some(p) => (p.x as f64, p.y as f64),
+            None => (
+                (field.width() as f64 - 1.0) / 2.0,
+                (field.height() as f64 - 1.0) / 2.0,
+            ),
+        };
+
+        let radius = self.radius.unwrap_or_else(|| {
+            let max_x = field.width().saturating_sub(1) as f64;
+            let max_y = field.height().saturating_sub(1) as f64;
+            [(0.0, 0.0), (max_x, 0.0), (0.0, max_y), (max_x, max_y)]
+                .into_iter()
+                .map(|(x, y)| self.metric.distance(cx, cy, x, y))
+                .fold(f64::EPSILON, f64::max)
+        });
+
+        for row in 0..field.height() {
+            for col in 0..field.width() {
+                let dist = self.metric.distance(cx, cy, col as f64, row as f64);
+                let t = (dist / radius).min(1.0);
+                let value = match self.falloff {
+                    Falloff::Linear => lerp(self.inner, self.outer, t),
+                    Falloff::Smooth => lerp(self.inner, self.outer, smoothstep(t)),
+                    Falloff::Step if dist < radius => self.inner,
+                    Falloff::Step => self.outer,
+                };
+
+                field[row][col] = self.blend.combine(field[row][col], value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::thread_rng;
+
+    use super::*;
+
+    #[test]
+    fn gradient() {
+        let mut field = Grid::new(5, 1, 0.0);
+        RadialGradient::builder()
+            .inner(4.0)
+            .build()
+            .unwrap()
+            .apply(&mut field, &mut thread_rng());
+        assert_eq!(field[0], vec![0.0, 2.0, 4.0, 2.0, 0.0]);
+
+        let mut field = Grid::new(3, 3, 1.0);
+        RadialGradient::builder()
+            .metric(Metric::Chebyshev)
+            .inner(0.0)
+            .outer(2.0)
+            .blend(Blend::Multiply)
+            .build()
+            .unwrap()
+            .apply(&mut field, &mut thread_rng());
+        assert_eq!(field[0], vec![2.0, 2.0, 2.0]);
+        assert_eq!(field[1], vec![2.0, 0.0, 2.0]);
+
+        assert!(RadialGradient::builder().radius(0.0).build().is_err());
+    }
+}
@@ -0,0 +1,87 @@
+use derive_builder::Builder;
+use rand::Rng;
+
+use crate::grid::Grid;
+
+use super::{Blend, FieldLayer};
+
+/// A layer of independent, uniformly distributed random values between `min`
+/// and `max` (inclusive).
+///
+/// If `integers` is set, only the whole numbers in that range are produced,
+/// each with equal probability.
+///
+/// # Examples
+/// ```
+/// use rand::thread_rng;
+/// use proliferatr::grid::field::{FieldGenerator, WhiteNoise};
+///
+/// let grid = FieldGenerator::new(10, 10)
+///     .normalize(false)
+///     .layer(
+///         WhiteNoise::builder()
+///             .min(3.0)
+///             .max(5.0)
+///             .integers(true)
+///             .build()
+///             .unwrap(),
+///     )
+///     .generate(&mut thread_rng())
+///     .unwrap();
+///
+/// assert!((0..10).all(|row| grid[row].iter().all(|v| (3..=5).contains(v))));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct WhiteNoise {
+    #[builder(default = "0.0")]
+    min: f64,
+
+    #[builder(default = "1.0")]
+    max: f64,
+
+    #[builder(default)]
+    integers: bool,
+
+    #[builder(default)]
+    blend: Blend,
+}
+
+impl WhiteNoiseBuilder {
+    fn validate(&self) -> Result<(), String> {
+        let min = self.min.unwrap_or(0.0);
+        let max = self.max.unwrap_or(1.0);
+
+        if min > max {
+            return Err("min must not be greater than max.".into());
+        }
+
+        if self.integers == Some(true) && min.ceil() > max.floor() {
+            return Err("There must be an integer between min and max.".into());
+        }
+
+        Ok(())
+    }
+}
+
+impl WhiteNoise {
+    pub fn builder() -> WhiteNoiseBuilder {
+        WhiteNoiseBuilder::default()
+    }
+}
+
+impl FieldLayer for WhiteNoise {
+    fn apply<R: Rng + ?Sized>(&self, field: &mut Grid<f64>, rng: &mut R) {
+        for row in 0..field.height() {
+            for col in 0..field.width() {
+                let value = if self.integers {
+                    rng.gen_range((self.min.ceil() as i64)..=(self.max.floor() as i64)) as f64
+                } else {
+                    rng.gen_range(self.min..=self.max)
+                };
+
+                field[row][col] = self.blend.combine(field[row][col], value);
+            }
+        }
+    }
+}
@@ -8,6 +8,9 @@ use thiserror::Error;
 
 use crate::point::Point;
 
+pub mod automata;
+pub mod field;
+
 #[derive(Debug, Clone, Error)]
 pub enum GridError {
     #[error("Rows have inconsistent width.")]
@@ -40,9 +43,9 @@ where
 impl<T> Grid<T> {
     pub fn get(&self, point: &Point) -> Option<&T> {
         if 0 <= point.x
-            && point.x <= self.width as i64
+            && point.x < self.width as i64
             && 0 <= point.y
-            && point.y <= self.height as i64
+            && point.y < self.height as i64
         {
             Some(&self.cells[point.y as usize][point.x as usize])
         } else {
@@ -52,9 +55,9 @@ impl<T> Grid<T> {
 
     pub fn get_mut(&mut self, point: &Point) -> Option<&mut T> {
         if 0 <= point.x
-            && point.x <= self.width as i64
+            && point.x < self.width as i64
             && 0 <= point.y
-            && point.y <= self.height as i64
+            && point.y < self.height as i64
         {
             Some(&mut self.cells[point.y as usize][point.x as usize])
         } else {
@@ -67,9 +70,9 @@ impl<T> Grid<T> {
     /// otherwise.
     pub fn set(&mut self, point: &Point, value: T) -> bool {
         if 0 <= point.x
-            && point.x <= self.width as i64
+            && point.x < self.width as i64
             && 0 <= point.y
-            && point.y <= self.height as i64
+            && point.y < self.height as i64
         {
             self.cells[point.y as usize][point.x as usize] = value;
             true
@@ -85,6 +88,30 @@ impl<T> Grid<T> {
     pub fn height(&self) -> usize {
         self.height
     }
+
+    /// Make a new [Grid] of the same dimensions by applying `f` to every cell.
+    ///
+    /// # Examples
+    /// ```
+    /// use proliferatr::grid::Grid;
+    ///
+    /// let grid = Grid::new(3, 2, true);
+    /// assert_eq!(grid.map(|b| if *b { '#' } else { '.' }).to_string(), "###\n###");
+    /// ```
+    pub fn map<U, F>(&self, mut f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid {
+            cells: self
+                .cells
+                .iter()
+                .map(|row| row.iter().map(&mut f).collect())
+                .collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
 }
 
 impl<T> TryFrom<Vec<Vec<T>>> for Grid<T> {
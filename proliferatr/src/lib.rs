@@ -2,12 +2,15 @@
 use rand::Rng;
 
 pub mod bound;
+pub mod circuit;
 pub mod direction;
 pub mod generic;
+pub mod graph;
 pub mod grid;
 pub mod maze;
 pub mod path;
 pub mod point;
+pub mod workflow;
 
 /// Indicates that the implementing type can act as an input generator.
 ///
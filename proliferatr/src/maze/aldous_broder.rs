@@ -0,0 +1,52 @@
+use rand::{seq::IteratorRandom, Rng};
+
+use super::{MazeAlgorithm, MazeGrid, Topology};
+
+/// Carves a maze with a random walk, opening a passage whenever the walk
+/// enters a cell for the first time.
+///
+/// This produces a uniformly random spanning tree, but is slow to finish, as
+/// the walk spends a long time wandering through visited cells.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AldousBroder;
+
+impl<T: Topology> MazeAlgorithm<T> for AldousBroder {
+    fn generate<R: Rng + ?Sized>(&self, grid: &mut MazeGrid<T>, rng: &mut R) {
+        grid.clear();
+        if grid.cell_count() == 0 {
+            return;
+        }
+
+        let mut visited = vec![false; grid.size()];
+        let mut unvisited = grid.cell_count() - 1;
+        let mut cell = grid.random_cell(rng);
+        visited[grid.index(&cell)] = true;
+
+        while unvisited > 0 {
+            let Some((dir, next, _)) = grid.neighbors(&cell).choose(rng) else {
+                break;
+            };
+
+            let idx = grid.index(&next);
+            if !visited[idx] {
+                visited[idx] = true;
+                grid.carve(&cell, dir);
+                unvisited -= 1;
+            }
+
+            cell = next;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::maze::tests::check_perfect;
+
+    use super::*;
+
+    #[test]
+    fn perfect() {
+        check_perfect(AldousBroder);
+    }
+}
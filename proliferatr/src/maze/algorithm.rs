@@ -0,0 +1,32 @@
+use rand::Rng;
+
+use super::{MazeGrid, Square, Topology};
+
+/// An algorithm for carving a maze into a [MazeGrid].
+///
+/// Any existing passages in the grid are removed before the maze is carved.
+/// Algorithms that only depend on which cells are adjacent work with any
+/// [Topology], while those that rely on the rows and columns of square cells
+/// (such as [BinaryTree]) are only implemented for [Square].
+/// Unless otherwise noted, implementations produce perfect mazes, where there
+/// is exactly one path between any two cells (see [MazeGrid::is_perfect]).
+///
+/// # Examples
+/// ```
+/// use rand::thread_rng;
+/// use proliferatr::maze::{MazeAlgorithm, MazeGrid, VertHex, Wilsons};
+///
+/// let mut grid = MazeGrid::new(20, 10);
+/// Wilsons.generate(&mut grid, &mut thread_rng());
+///
+/// assert!(grid.is_perfect());
+///
+/// let hex = MazeGrid::generate_with(VertHex, 20, 10, &Wilsons, &mut thread_rng());
+/// assert!(hex.is_perfect());
+/// ```
+///
+/// [BinaryTree]: super::BinaryTree
+pub trait MazeAlgorithm<T: Topology = Square> {
+    /// Carve a maze into the `grid`, using `rng` for any random decisions.
+    fn generate<R: Rng + ?Sized>(&self, grid: &mut MazeGrid<T>, rng: &mut R);
+}
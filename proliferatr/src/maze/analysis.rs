@@ -0,0 +1,384 @@
+use std::collections::VecDeque;
+
+use super::{Location, MazeGrid, Topology};
+
+/// The shortest path through a maze between two cells.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Solution {
+    /// The cells along the path, including both ends.
+    pub path: Vec<Location>,
+
+    /// The number of steps along the path.
+    pub length: usize,
+
+    /// The number of times the path changes direction.
+    pub turns: usize,
+}
+
+impl Solution {
+    fn new<T: Topology>(grid: &MazeGrid<T>, path: Vec<Location>) -> Self {
+        let turns = path
+            .windows(3)
+            .filter(|w| grid.direction_to(&w[0], &w[1]) != grid.direction_to(&w[1], &w[2]))
+            .count();
+
+        Self {
+            length: path.len().saturating_sub(1),
+            path,
+            turns,
+        }
+    }
+}
+
+/// The distance from a starting cell to every cell of a maze.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DistanceMap {
+    width: usize,
+    height: usize,
+    distances: Vec<Option<usize>>,
+}
+
+impl DistanceMap {
+    /// The distance to `loc`, if it is reachable.
+    pub fn get(&self, loc: &Location) -> Option<usize> {
+        if loc.row < self.height && loc.col < self.width {
+            self.distances[loc.row * self.width + loc.col]
+        } else {
+            None
+        }
+    }
+
+    /// The farthest reachable cell and its distance. Ties are broken in favor
+    /// of the first cell in row-major order.
+    pub fn farthest(&self) -> Option<(Location, usize)> {
+        self.distances
+            .iter()
+            .enumerate()
+            .filter_map(|(i, d)| d.map(|d| (i, d)))
+            .fold(None, |best: Option<(usize, usize)>, (i, d)| match best {
+                Some((_, bd)) if bd >= d => best,
+                _ => Some((i, d)),
+            })
+            .map(|(i, d)| {
+                (
+                    Location {
+                        row: i / self.width,
+                        col: i % self.width,
+                    },
+                    d,
+                )
+            })
+    }
+
+    /// The number of reachable cells, including the start.
+    pub fn reachable(&self) -> usize {
+        self.distances.iter().filter(|d| d.is_some()).count()
+    }
+}
+
+/// Summary metrics describing the structure of a maze.
+///
+/// Only open cells (cells with at least one passage) are considered.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MazeStats {
+    /// The number of open cells.
+    pub open_cells: usize,
+
+    /// The number of cells with each degree, indexed by degree.
+    pub degrees: Vec<usize>,
+
+    /// The number of cells with exactly one passage.
+    pub dead_ends: usize,
+
+    /// The number of cells with three or more passages.
+    pub junctions: usize,
+
+    /// The length of the longest shortest path between any two cells, and
+    /// the cells at either end of it.
+    pub diameter: Option<(Location, Location, usize)>,
+
+    /// The average number of ways onward from a junction, not counting the
+    /// way in (the mean of `degree - 1` over every junction).
+    pub branching_factor: f64,
+
+    /// The average length of the corridors leading into dead ends, measured
+    /// from the dead end to the nearest junction. Mazes with a high "river"
+    /// have long, winding dead ends; those with a low one have many short
+    /// dead ends.
+    pub river: f64,
+}
+
+impl<T: Topology> MazeGrid<T> {
+    /// The shortest path from `from` to `to`, if there is one.
+    ///
+    /// # Examples
+    /// ```
+    /// use proliferatr::{direction::Cardinal, maze::MazeGrid};
+    ///
+    /// let mut grid = MazeGrid::new(3, 2);
+    /// grid.carve(&(0, 0).into(), Cardinal::East);
+    /// grid.carve(&(0, 1).into(), Cardinal::East);
+    /// grid.carve(&(0, 2).into(), Cardinal::South);
+    /// grid.carve(&(1, 2).into(), Cardinal::West);
+    ///
+    /// let solution = grid.solve(&(0, 0).into(), &(1, 1).into()).unwrap();
+    /// assert_eq!(solution.length, 4);
+    /// assert_eq!(solution.turns, 2);
+    /// assert_eq!(solution.path.last(), Some(&(1, 1).into()));
+    ///
+    /// assert!(grid.solve(&(0, 0).into(), &(1, 0).into()).is_none());
+    /// ```
+    pub fn solve(&self, from: &Location, to: &Location) -> Option<Solution> {
+        if !self.contains(from) || !self.contains(to) {
+            return None;
+        }
+
+        let mut prev: Vec<Option<Location>> = vec![None; self.size()];
+        let mut seen = vec![false; self.size()];
+        seen[self.index(from)] = true;
+        let mut queue = VecDeque::from([*from]);
+
+        while let Some(loc) = queue.pop_front() {
+            if loc == *to {
+                let mut path = vec![loc];
+                let mut cur = loc;
+                while let Some(p) = prev[self.index(&cur)] {
+                    path.push(p);
+                    cur = p;
+                }
+                path.reverse();
+                return Some(Solution::new(self, path));
+            }
+
+            for (dir, other, _) in self.neighbors(&loc) {
+                let idx = self.index(&other);
+                if self.has_passage(&loc, dir) && !seen[idx] {
+                    seen[idx] = true;
+                    prev[idx] = Some(loc);
+                    queue.push_back(other);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// The distance from `from` to every reachable cell.
+    pub fn distances(&self, from: &Location) -> DistanceMap {
+        let mut distances = vec![None; self.size()];
+
+        if self.contains(from) {
+            distances[self.index(from)] = Some(0);
+            let mut queue = VecDeque::from([(*from, 0)]);
+
+            while let Some((loc, dist)) = queue.pop_front() {
+                for (dir, other, _) in self.neighbors(&loc) {
+                    let idx = self.index(&other);
+                    if self.has_passage(&loc, dir) && distances[idx].is_none() {
+                        distances[idx] = Some(dist + 1);
+                        queue.push_back((other, dist + 1));
+                    }
+                }
+            }
+        }
+
+        DistanceMap {
+            width: self.width,
+            height: self.height,
+            distances,
+        }
+    }
+
+    /// The longest shortest path between any two open cells, as the cells at
+    /// either end and the distance between them.
+    ///
+    /// For perfect mazes, this takes two breadth-first searches. Otherwise,
+    /// this searches from every open cell, which is quadratic in the size of
+    /// the maze.
+    pub fn diameter(&self) -> Option<(Location, Location, usize)> {
+        let start = self.locations().find(|l| self.degree(l) > 0)?;
+
+        if self.is_perfect() {
+            let (a, _) = self.distances(&start).farthest()?;
+            let (b, dist) = self.distances(&a).farthest()?;
+            return Some((a, b, dist));
+        }
+
+        self.locations()
+            .filter(|l| self.degree(l) > 0)
+            .filter_map(|a| self.distances(&a).farthest().map(|(b, dist)| (a, b, dist)))
+            .fold(None, |best, cur| match best {
+                Some((_, _, bd)) if bd >= cur.2 => best,
+                _ => Some(cur),
+            })
+    }
+
+    /// Every cell with exactly one passage.
+    pub fn dead_ends(&self) -> Vec<Location> {
+        self.locations().filter(|l| self.degree(l) == 1).collect()
+    }
+
+    /// Every cell with three or more passages, along with its degree.
+    pub fn junctions(&self) -> Vec<(Location, usize)> {
+        self.locations()
+            .map(|l| (l, self.degree(&l)))
+            .filter(|(_, d)| *d >= 3)
+            .collect()
+    }
+
+    /// Compute summary metrics for this maze.
+    ///
+    /// # Examples
+    /// ```
+    /// use rand::thread_rng;
+    /// use proliferatr::maze::{MazeGrid, Prim, RecursiveBacktracker};
+    ///
+    /// let mut rng = thread_rng();
+    /// let backtracker = MazeGrid::generate(30, 30, &RecursiveBacktracker, &mut rng).stats();
+    /// let prim = MazeGrid::generate(30, 30, &Prim, &mut rng).stats();
+    ///
+    /// assert_eq!(backtracker.open_cells, 900);
+    /// assert!(backtracker.dead_ends < prim.dead_ends);
+    /// assert!(backtracker.river > prim.river);
+    /// ```
+    pub fn stats(&self) -> MazeStats {
+        let mut degrees = vec![0; T::DIRECTIONS.len() + 1];
+        for loc in self.locations() {
+            degrees[self.degree(&loc)] += 1;
+        }
+
+        let open_cells = degrees[1..].iter().sum();
+        let junctions = self.junctions();
+
+        let branching_factor = if junctions.is_empty() {
+            0.0
+        } else {
+            junctions.iter().map(|(_, d)| d - 1).sum::<usize>() as f64 / junctions.len() as f64
+        };
+
+        let dead_ends = self.dead_ends();
+        let river = if dead_ends.is_empty() {
+            0.0
+        } else {
+            dead_ends
+                .iter()
+                .map(|l| self.dead_end_length(l))
+                .sum::<usize>() as f64
+                / dead_ends.len() as f64
+        };
+
+        MazeStats {
+            open_cells,
+            degrees,
+            dead_ends: dead_ends.len(),
+            junctions: junctions.len(),
+            diameter: self.diameter(),
+            branching_factor,
+            river,
+        }
+    }
+
+    /// The number of steps from the dead end at `loc` until reaching a
+    /// junction (or the other end of a corridor with no junctions).
+    fn dead_end_length(&self, loc: &Location) -> usize {
+        let mut prev = *loc;
+        let mut cur = *loc;
+        let mut length = 0;
+
+        loop {
+            let next = self
+                .neighbors(&cur)
+                .find(|(d, l, _)| self.has_passage(&cur, *d) && *l != prev);
+
+            match next {
+                Some((_, l, _)) if length == 0 || self.degree(&cur) == 2 => {
+                    prev = cur;
+                    cur = l;
+                    length += 1;
+                }
+                _ => return length,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::direction::Cardinal;
+
+    use super::*;
+
+    /// A comb: a corridor along the top row with a tooth hanging down from
+    /// every cell.
+    fn comb(width: usize, height: usize) -> MazeGrid {
+        let mut grid = MazeGrid::new(width, height);
+        for col in 0..width {
+            if col + 1 < width {
+                grid.carve(&(0, col).into(), Cardinal::East);
+            }
+
+            for row in 0..(height - 1) {
+                grid.carve(&(row, col).into(), Cardinal::South);
+            }
+        }
+        grid
+    }
+
+    #[test]
+    fn solving() {
+        let grid = comb(5, 4);
+        let solution = grid.solve(&(3, 0).into(), &(3, 4).into()).unwrap();
+
+        assert_eq!(solution.length, 3 + 4 + 3);
+        assert_eq!(solution.turns, 2);
+        assert_eq!(solution.path.len(), 11);
+
+        let distances = grid.distances(&(3, 0).into());
+        assert_eq!(distances.get(&(3, 4).into()), Some(10));
+        assert_eq!(distances.get(&(0, 0).into()), Some(3));
+        assert_eq!(distances.get(&(9, 9).into()), None);
+        assert_eq!(distances.reachable(), 20);
+        assert_eq!(distances.farthest(), Some(((3, 4).into(), 10)));
+
+        let same = grid.solve(&(1, 1).into(), &(1, 1).into()).unwrap();
+        assert_eq!((same.length, same.turns), (0, 0));
+    }
+
+    #[test]
+    fn stats() {
+        let grid = comb(5, 4);
+        let stats = grid.stats();
+
+        assert_eq!(stats.open_cells, 20);
+        assert_eq!(stats.degrees, vec![0, 5, 12, 3, 0]);
+        assert_eq!(stats.dead_ends, 5);
+        assert_eq!(stats.junctions, 3);
+        assert_eq!(stats.diameter.map(|d| d.2), Some(10));
+        assert_eq!(stats.branching_factor, 2.0);
+
+        // three teeth of length 3 from junctions, and the two end teeth run
+        // into the corridor, which reaches the first junction one step later
+        assert_eq!(stats.river, (3.0 * 3.0 + 4.0 * 2.0) / 5.0);
+
+        assert_eq!(grid.junctions()[0], ((0, 1).into(), 3));
+
+        // a ring of eight cells around a blocked center
+        let mut ring = MazeGrid::new(3, 3);
+        for loc in [(0, 0), (0, 1)] {
+            ring.carve(&loc.into(), Cardinal::East);
+        }
+        for loc in [(0, 2), (1, 2)] {
+            ring.carve(&loc.into(), Cardinal::South);
+        }
+        for loc in [(2, 2), (2, 1)] {
+            ring.carve(&loc.into(), Cardinal::West);
+        }
+        for loc in [(2, 0), (1, 0)] {
+            ring.carve(&loc.into(), Cardinal::North);
+        }
+        assert!(!ring.is_perfect());
+        assert_eq!(ring.diameter().map(|d| d.2), Some(4));
+        assert_eq!(ring.stats().dead_ends, 0);
+        assert_eq!(ring.stats().river, 0.0);
+    }
+}
@@ -0,0 +1,41 @@
+use rand::{seq::IteratorRandom, Rng};
+
+use crate::direction::Cardinal;
+
+use super::{MazeAlgorithm, MazeGrid};
+
+/// Carves a maze by opening a passage either north or west from every cell.
+///
+/// This is very fast, but produces mazes with a strong diagonal bias and an
+/// unbroken corridor along the northern and western edges.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BinaryTree;
+
+impl MazeAlgorithm for BinaryTree {
+    fn generate<R: Rng + ?Sized>(&self, grid: &mut MazeGrid, rng: &mut R) {
+        grid.clear();
+
+        for loc in grid.locations().collect::<Vec<_>>() {
+            let dir = [Cardinal::North, Cardinal::West]
+                .into_iter()
+                .filter(|d| grid.neighbor(&loc, *d).is_some())
+                .choose(rng);
+
+            if let Some(dir) = dir {
+                grid.carve(&loc, dir);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::maze::tests::check_perfect;
+
+    use super::*;
+
+    #[test]
+    fn perfect() {
+        check_perfect(BinaryTree);
+    }
+}
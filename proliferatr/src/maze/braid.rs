@@ -0,0 +1,128 @@
+use derive_builder::Builder;
+use rand::{
+    seq::{IteratorRandom, SliceRandom},
+    Rng,
+};
+
+use super::{MazeGrid, MazeModifier, Topology};
+
+/// Removes dead ends by opening a passage from the dead end into an adjacent
+/// open cell it is not already connected to, preferring cells that are
+/// themselves dead ends.
+///
+/// `fraction` controls the proportion of the dead ends present before
+/// braiding that are removed, where `1.0` removes every dead end that can be
+/// removed.
+#[derive(Debug, Clone, Copy, PartialEq, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct Braid {
+    #[builder(default = "0.5")]
+    fraction: f64,
+}
+
+impl Default for Braid {
+    fn default() -> Self {
+        Self { fraction: 0.5 }
+    }
+}
+
+impl BraidBuilder {
+    fn validate(&self) -> Result<(), String> {
+        if let Some(fraction) = self.fraction {
+            if !(0.0..=1.0).contains(&fraction) {
+                return Err("fraction must be between 0 and 1.".into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Braid {
+    pub fn builder() -> BraidBuilder {
+        BraidBuilder::default()
+    }
+}
+
+impl<T: Topology> MazeModifier<T> for Braid {
+    fn modify<R: Rng + ?Sized>(&self, grid: &mut MazeGrid<T>, rng: &mut R) -> bool {
+        let mut dead_ends: Vec<_> = grid.locations().filter(|l| grid.degree(l) == 1).collect();
+        dead_ends.shuffle(rng);
+
+        let target = (dead_ends.len() as f64 * self.fraction).round() as usize;
+        let mut removed = 0;
+
+        for loc in dead_ends {
+            if removed >= target {
+                break;
+            }
+
+            // an earlier removal may have already joined this cell
+            if grid.degree(&loc) != 1 {
+                continue;
+            }
+
+            let candidates: Vec<_> = grid
+                .neighbors(&loc)
+                .filter(|(d, _, v)| *v != 0 && !grid.has_passage(&loc, *d))
+                .collect();
+
+            let choice = candidates
+                .iter()
+                .filter(|(_, l, _)| grid.degree(l) == 1)
+                .choose(rng)
+                .or_else(|| candidates.iter().choose(rng));
+
+            if let Some((dir, _, _)) = choice {
+                grid.carve(&loc, *dir);
+                removed += 1;
+            }
+        }
+
+        removed > 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::maze::RecursiveBacktracker;
+
+    use super::*;
+
+    fn dead_ends(grid: &MazeGrid) -> usize {
+        grid.locations().filter(|l| grid.degree(l) == 1).count()
+    }
+
+    #[test]
+    fn braiding() {
+        let mut rng = StdRng::seed_from_u64(38);
+        let original = MazeGrid::generate(25, 25, &RecursiveBacktracker, &mut rng);
+
+        let mut grid = original.clone();
+        assert!(Braid::builder()
+            .fraction(1.0)
+            .build()
+            .unwrap()
+            .modify(&mut grid, &mut rng));
+        assert_eq!(dead_ends(&grid), 0);
+        assert!(grid.is_connected());
+
+        let mut grid = original.clone();
+        Braid::default().modify(&mut grid, &mut rng);
+        let remaining = dead_ends(&grid);
+        assert!(remaining > 0 && remaining < dead_ends(&original));
+        assert!(grid.is_connected());
+
+        let mut grid = original.clone();
+        assert!(!Braid::builder()
+            .fraction(0.0)
+            .build()
+            .unwrap()
+            .modify(&mut grid, &mut rng));
+        assert_eq!(grid, original);
+
+        assert!(Braid::builder().fraction(1.1).build().is_err());
+    }
+}
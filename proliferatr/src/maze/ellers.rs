@@ -0,0 +1,139 @@
+use derive_builder::Builder;
+use rand::{seq::SliceRandom, Rng};
+
+use crate::direction::Cardinal;
+
+use super::{Location, MazeAlgorithm, MazeGrid};
+
+/// Carves a maze one row at a time with Eller's algorithm, only tracking which
+/// cells of the current row are connected to each other.
+///
+/// Within a row, adjacent cells in different sets are randomly joined. Every
+/// set then opens at least one passage down into the next row, and the final
+/// row joins any sets that remain.
+#[derive(Debug, Clone, Copy, PartialEq, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct Ellers {
+    /// The probability of joining two adjacent cells in different sets.
+    #[builder(default = "0.5")]
+    join_chance: f64,
+
+    /// The probability of opening a passage down from a cell, beyond the one
+    /// passage every set requires.
+    #[builder(default = "0.3")]
+    down_chance: f64,
+}
+
+impl Default for Ellers {
+    fn default() -> Self {
+        Self {
+            join_chance: 0.5,
+            down_chance: 0.3,
+        }
+    }
+}
+
+impl EllersBuilder {
+    fn validate(&self) -> Result<(), String> {
+        for (name, chance) in [
+            ("join_chance", self.join_chance),
+            ("down_chance", self.down_chance),
+        ] {
+            if let Some(chance) = chance {
+                if !(0.0..=1.0).contains(&chance) {
+                    return Err(format!("{name} must be between 0 and 1."));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Ellers {
+    pub fn builder() -> EllersBuilder {
+        EllersBuilder::default()
+    }
+}
+
+impl MazeAlgorithm for Ellers {
+    fn generate<R: Rng + ?Sized>(&self, grid: &mut MazeGrid, rng: &mut R) {
+        grid.clear();
+        if grid.size() == 0 {
+            return;
+        }
+
+        let width = grid.width;
+        let mut next_set = 0;
+        let mut sets: Vec<Option<usize>> = vec![None; width];
+
+        for row in 0..grid.height {
+            let last_row = row + 1 == grid.height;
+
+            for set in sets.iter_mut() {
+                if set.is_none() {
+                    *set = Some(next_set);
+                    next_set += 1;
+                }
+            }
+
+            // horizontal joins, which must join everything on the last row
+            for col in 0..(width - 1) {
+                let (a, b) = (sets[col].unwrap(), sets[col + 1].unwrap());
+                if a != b && (last_row || rng.gen_bool(self.join_chance)) {
+                    grid.carve(&Location { row, col }, Cardinal::East);
+                    for set in sets.iter_mut() {
+                        if *set == Some(b) {
+                            *set = Some(a);
+                        }
+                    }
+                }
+            }
+
+            if last_row {
+                break;
+            }
+
+            // vertical passages, at least one per set
+            let mut below = vec![None; width];
+            let mut cols: Vec<usize> = (0..width).collect();
+            cols.shuffle(rng);
+            cols.sort_by_key(|c| sets[*c]);
+
+            // the columns are shuffled within each set, so the first column of
+            // each set is random
+            let mut prev = None;
+            for col in cols {
+                let first = sets[col] != prev;
+                prev = sets[col];
+
+                if first || rng.gen_bool(self.down_chance) {
+                    grid.carve(&Location { row, col }, Cardinal::South);
+                    below[col] = sets[col];
+                }
+            }
+
+            sets = below;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::maze::tests::check_perfect;
+
+    use super::*;
+
+    #[test]
+    fn perfect() {
+        check_perfect(Ellers::default());
+        check_perfect(
+            Ellers::builder()
+                .join_chance(0.9)
+                .down_chance(0.0)
+                .build()
+                .unwrap(),
+        );
+        assert!(Ellers::builder().down_chance(-1.0).build().is_err());
+    }
+}
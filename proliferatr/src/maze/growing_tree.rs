@@ -0,0 +1,153 @@
+use derive_builder::Builder;
+use rand::{seq::IteratorRandom, Rng};
+
+use super::{MazeAlgorithm, MazeGrid, Topology};
+
+/// How a [GrowingTree] selects the next active cell to grow from.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum GrowingTreeStrategy {
+    /// The most recently added cell, which behaves like the
+    /// [RecursiveBacktracker].
+    ///
+    /// [RecursiveBacktracker]: super::RecursiveBacktracker
+    #[default]
+    Newest,
+
+    /// The least recently added cell, which produces long straight corridors
+    /// radiating from the start.
+    Oldest,
+
+    /// The middle of the active cells.
+    Middle,
+
+    /// A random active cell, which behaves like [Prim].
+    ///
+    /// [Prim]: super::Prim
+    Random,
+
+    /// The newest cell with the given probability, otherwise a random cell.
+    Mixed { newest_chance: f64 },
+}
+
+impl GrowingTreeStrategy {
+    fn select<R: Rng + ?Sized>(&self, len: usize, rng: &mut R) -> usize {
+        match self {
+            Self::Newest => len - 1,
+            Self::Oldest => 0,
+            Self::Middle => len / 2,
+            Self::Random => rng.gen_range(0..len),
+            Self::Mixed { newest_chance } => {
+                if rng.gen_bool(*newest_chance) {
+                    len - 1
+                } else {
+                    rng.gen_range(0..len)
+                }
+            }
+        }
+    }
+}
+
+/// Carves a maze by growing a tree from a list of active cells. At each step,
+/// a cell is selected from the list according to the [GrowingTreeStrategy],
+/// and a passage is opened to one of its unvisited neighbors, which becomes
+/// active. Cells without unvisited neighbors are removed from the list.
+///
+/// # Examples
+/// ```
+/// use rand::thread_rng;
+/// use proliferatr::maze::{GrowingTree, GrowingTreeStrategy, MazeGrid};
+///
+/// let algorithm = GrowingTree::builder()
+///     .strategy(GrowingTreeStrategy::Mixed { newest_chance: 0.75 })
+///     .build()
+///     .unwrap();
+///
+/// let grid = MazeGrid::generate(15, 15, &algorithm, &mut thread_rng());
+/// assert!(grid.is_perfect());
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct GrowingTree {
+    #[builder(default)]
+    strategy: GrowingTreeStrategy,
+}
+
+impl GrowingTreeBuilder {
+    fn validate(&self) -> Result<(), String> {
+        if let Some(GrowingTreeStrategy::Mixed { newest_chance }) = self.strategy {
+            if !(0.0..=1.0).contains(&newest_chance) {
+                return Err("newest_chance must be between 0 and 1.".into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl GrowingTree {
+    pub fn builder() -> GrowingTreeBuilder {
+        GrowingTreeBuilder::default()
+    }
+}
+
+impl<T: Topology> MazeAlgorithm<T> for GrowingTree {
+    fn generate<R: Rng + ?Sized>(&self, grid: &mut MazeGrid<T>, rng: &mut R) {
+        grid.clear();
+        if grid.cell_count() == 0 {
+            return;
+        }
+
+        let mut visited = vec![false; grid.size()];
+        let start = grid.random_cell(rng);
+        visited[grid.index(&start)] = true;
+
+        let mut active = vec![start];
+
+        while !active.is_empty() {
+            let idx = self.strategy.select(active.len(), rng);
+            let cell = active[idx];
+
+            let next = grid
+                .neighbors(&cell)
+                .filter(|(_, l, _)| !visited[grid.index(l)])
+                .choose(rng);
+
+            match next {
+                Some((dir, loc, _)) => {
+                    visited[grid.index(&loc)] = true;
+                    grid.carve(&cell, dir);
+                    active.push(loc);
+                }
+                None => {
+                    // preserve the order, which the strategies depend on
+                    active.remove(idx);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::maze::tests::check_perfect;
+
+    use super::*;
+
+    #[test]
+    fn perfect() {
+        for strategy in [
+            GrowingTreeStrategy::Newest,
+            GrowingTreeStrategy::Oldest,
+            GrowingTreeStrategy::Middle,
+            GrowingTreeStrategy::Random,
+            GrowingTreeStrategy::Mixed { newest_chance: 0.5 },
+        ] {
+            check_perfect(GrowingTree::builder().strategy(strategy).build().unwrap());
+        }
+
+        assert!(GrowingTree::builder()
+            .strategy(GrowingTreeStrategy::Mixed { newest_chance: 1.5 })
+            .build()
+            .is_err());
+    }
+}
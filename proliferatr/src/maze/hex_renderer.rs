@@ -0,0 +1,261 @@
+use derive_builder::Builder;
+
+use crate::direction::{HorizHexDir, VertHexDir};
+
+use super::{HorizHex, Location, Masked, MazeGrid, MazeRenderError, Topology, VertHex};
+
+/// Describes how the cells of a hexagonal [Topology] are laid out as
+/// characters by a [HexRenderer].
+pub trait HexLayout: Topology {
+    /// The number of rows and columns of characters needed to draw a grid of
+    /// the given dimensions.
+    fn canvas_size(width: usize, height: usize) -> (usize, usize);
+
+    /// The row and column of the upper left character of the cell at `loc`.
+    fn origin(loc: &Location) -> (usize, usize);
+
+    /// The characters of the wall on the `dir` side of a cell, relative to
+    /// its origin.
+    fn wall(dir: Self::Dir) -> &'static [(usize, usize, char)];
+
+    /// The position of the middle of a cell, relative to its origin.
+    fn center() -> (usize, usize);
+}
+
+impl HexLayout for HorizHex {
+    fn canvas_size(width: usize, height: usize) -> (usize, usize) {
+        let shifted = usize::from(width > 1);
+        (height * 2 + 1 + shifted, width * 4 + 1)
+    }
+
+    fn origin(loc: &Location) -> (usize, usize) {
+        (loc.row * 2 + loc.col % 2, loc.col * 4)
+    }
+
+    fn wall(dir: HorizHexDir) -> &'static [(usize, usize, char)] {
+        match dir {
+            HorizHexDir::North => &[(0, 1, '_'), (0, 2, '_'), (0, 3, '_')],
+            HorizHexDir::South => &[(2, 1, '_'), (2, 2, '_'), (2, 3, '_')],
+            HorizHexDir::NorthWest => &[(1, 0, '/')],
+            HorizHexDir::NorthEast => &[(1, 4, '\\')],
+            HorizHexDir::SouthWest => &[(2, 0, '\\')],
+            HorizHexDir::SouthEast => &[(2, 4, '/')],
+        }
+    }
+
+    fn center() -> (usize, usize) {
+        (1, 2)
+    }
+}
+
+impl HexLayout for VertHex {
+    fn canvas_size(width: usize, height: usize) -> (usize, usize) {
+        let shifted = if height > 1 { 2 } else { 0 };
+        (height * 2 + 1, width * 4 + 1 + shifted)
+    }
+
+    fn origin(loc: &Location) -> (usize, usize) {
+        (loc.row * 2, loc.col * 4 + (loc.row % 2) * 2)
+    }
+
+    fn wall(dir: VertHexDir) -> &'static [(usize, usize, char)] {
+        match dir {
+            VertHexDir::NorthWest => &[(0, 1, '/')],
+            VertHexDir::NorthEast => &[(0, 3, '\\')],
+            VertHexDir::West => &[(1, 0, '|')],
+            VertHexDir::East => &[(1, 4, '|')],
+            VertHexDir::SouthWest => &[(2, 1, '\\')],
+            VertHexDir::SouthEast => &[(2, 3, '/')],
+        }
+    }
+
+    fn center() -> (usize, usize) {
+        (1, 2)
+    }
+}
+
+impl<T: HexLayout> HexLayout for Masked<T> {
+    fn canvas_size(width: usize, height: usize) -> (usize, usize) {
+        T::canvas_size(width, height)
+    }
+
+    fn origin(loc: &Location) -> (usize, usize) {
+        T::origin(loc)
+    }
+
+    fn wall(dir: T::Dir) -> &'static [(usize, usize, char)] {
+        T::wall(dir)
+    }
+
+    fn center() -> (usize, usize) {
+        T::center()
+    }
+}
+
+/// Renders a [MazeGrid] of hexagonal cells as ASCII art.
+///
+/// Walls are drawn with `/`, `\`, `|` and `_`, and the inside of every cell is
+/// filled with the `floor` glyph. Cells can be labeled with marks, which are
+/// drawn in the middle of the cell. Trailing whitespace is removed from every
+/// row.
+///
+/// # Examples
+/// ```
+/// use proliferatr::{
+///     direction::VertHexDir,
+///     maze::{HexRenderer, MazeGrid, VertHex},
+/// };
+///
+/// let mut grid = MazeGrid::with_topology(VertHex, 2, 2);
+/// grid.carve(&(0, 0).into(), VertHexDir::East);
+/// grid.carve(&(0, 1).into(), VertHexDir::SouthWest);
+/// grid.carve(&(1, 0).into(), VertHexDir::East);
+///
+/// let renderer = HexRenderer::builder()
+///     .mark(((0, 0).into(), 'S'))
+///     .mark(((1, 1).into(), 'E'))
+///     .build()
+///     .unwrap();
+///
+/// let expected = [
+///     r" / \ / \",
+///     r"| S     |",
+///     r" \ /   / \",
+///     r"  |     E |",
+///     r"   \ / \ /",
+/// ]
+/// .join("\n");
+///
+/// assert_eq!(renderer.render_string(&grid).unwrap(), expected);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Builder)]
+pub struct HexRenderer {
+    #[builder(default = "' '")]
+    floor: char,
+
+    #[builder(default, setter(each(name = "mark")))]
+    marks: Vec<(Location, char)>,
+}
+
+impl Default for HexRenderer {
+    fn default() -> Self {
+        Self {
+            floor: ' ',
+            marks: Vec::new(),
+        }
+    }
+}
+
+impl HexRenderer {
+    pub fn builder() -> HexRendererBuilder {
+        HexRendererBuilder::default()
+    }
+
+    /// Render the `grid` as rows of characters.
+    pub fn render<T: HexLayout>(
+        &self,
+        grid: &MazeGrid<T>,
+    ) -> Result<Vec<Vec<char>>, MazeRenderError> {
+        let (height, width) = T::canvas_size(grid.width, grid.height);
+        let mut output = vec![vec![' '; width]; height];
+        let (center_row, center_col) = T::center();
+
+        for loc in grid.locations() {
+            let (row, col) = T::origin(&loc);
+            output[row + center_row][col + center_col - 1..=col + center_col + 1].fill(self.floor);
+
+            for dir in T::DIRECTIONS {
+                if grid.has_passage(&loc, *dir) {
+                    continue;
+                }
+
+                for (r, c, glyph) in T::wall(*dir) {
+                    output[row + r][col + c] = *glyph;
+                }
+            }
+        }
+
+        for (loc, glyph) in self.marks.iter() {
+            if !grid.contains(loc) {
+                return Err(MazeRenderError::MarkOutOfBounds(*loc));
+            }
+
+            let (row, col) = T::origin(loc);
+            output[row + center_row][col + center_col] = *glyph;
+        }
+
+        for row in output.iter_mut() {
+            while row.last() == Some(&' ') {
+                row.pop();
+            }
+        }
+
+        Ok(output)
+    }
+
+    /// Render the `grid` as a string, with rows separated by newlines.
+    pub fn render_string<T: HexLayout>(
+        &self,
+        grid: &MazeGrid<T>,
+    ) -> Result<String, MazeRenderError> {
+        Ok(self
+            .render(grid)?
+            .iter()
+            .map(|r| r.iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::maze::RecursiveBacktracker;
+
+    use super::*;
+
+    #[test]
+    fn horiz_hex() {
+        let mut grid = MazeGrid::with_topology(HorizHex, 3, 2);
+        grid.carve(&(0, 0).into(), HorizHexDir::SouthEast);
+        grid.carve(&(0, 1).into(), HorizHexDir::NorthEast);
+        grid.carve(&(0, 2).into(), HorizHexDir::South);
+        grid.carve(&(1, 2).into(), HorizHexDir::SouthWest);
+        grid.carve(&(1, 1).into(), HorizHexDir::NorthWest);
+
+        let renderer = HexRenderer::builder().floor('.').build().unwrap();
+        let expected = [
+            r" ___     ___",
+            r"/...\___/...\",
+            r"\___ ...    /",
+            r"/...\___/...\",
+            r"\___ ... ___/",
+            r"    \___/",
+        ]
+        .join("\n");
+
+        assert_eq!(renderer.render_string(&grid).unwrap(), expected);
+    }
+
+    #[test]
+    fn masked() {
+        let pattern = [".#.", "...", ".#."].join("\n");
+        let mask = Masked::from_pattern(VertHex, &pattern).unwrap();
+        let grid =
+            MazeGrid::generate_masked(mask, &RecursiveBacktracker, &mut StdRng::seed_from_u64(2));
+
+        let rendered = HexRenderer::default().render(&grid).unwrap();
+
+        // the disabled cells are not drawn
+        assert_eq!(rendered[1].get(6), Some(&' '));
+        assert_eq!(rendered[5].get(6), Some(&' '));
+
+        let err = HexRenderer::builder()
+            .mark(((0, 1).into(), 'X'))
+            .build()
+            .unwrap()
+            .render(&grid);
+        assert_eq!(err, Err(MazeRenderError::MarkOutOfBounds((0, 1).into())));
+    }
+}
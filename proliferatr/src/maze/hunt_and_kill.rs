@@ -0,0 +1,88 @@
+use rand::{seq::IteratorRandom, Rng};
+
+use super::{Location, MazeAlgorithm, MazeGrid, Topology};
+
+/// Carves a maze with random walks through unvisited cells. When the walk
+/// reaches a dead end, "hunt" for the first unvisited cell bordering the maze,
+/// connect it to the maze, and start a new walk from there.
+///
+/// Like the [RecursiveBacktracker], this produces long, winding corridors, but
+/// without needing a stack.
+///
+/// [RecursiveBacktracker]: super::RecursiveBacktracker
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HuntAndKill;
+
+impl<T: Topology> MazeAlgorithm<T> for HuntAndKill {
+    fn generate<R: Rng + ?Sized>(&self, grid: &mut MazeGrid<T>, rng: &mut R) {
+        grid.clear();
+        if grid.cell_count() == 0 {
+            return;
+        }
+
+        let mut visited = vec![false; grid.size()];
+        let mut cell = grid.random_cell(rng);
+        visited[grid.index(&cell)] = true;
+
+        // rows before this are entirely visited, so the hunt can skip them
+        let mut hunt_row = 0;
+
+        loop {
+            let next = grid
+                .neighbors(&cell)
+                .filter(|(_, l, _)| !visited[grid.index(l)])
+                .choose(rng);
+
+            if let Some((dir, loc, _)) = next {
+                visited[grid.index(&loc)] = true;
+                grid.carve(&cell, dir);
+                cell = loc;
+                continue;
+            }
+
+            let mut found = None;
+            let mut first_incomplete = grid.height;
+            'hunt: for row in hunt_row..grid.height {
+                for col in 0..grid.width {
+                    let loc: Location = (row, col).into();
+                    if !grid.contains(&loc) || visited[grid.index(&loc)] {
+                        continue;
+                    }
+                    first_incomplete = first_incomplete.min(row);
+
+                    let joined = grid
+                        .neighbors(&loc)
+                        .filter(|(_, l, _)| visited[grid.index(l)])
+                        .choose(rng);
+
+                    if let Some((dir, _, _)) = joined {
+                        found = Some((loc, dir));
+                        break 'hunt;
+                    }
+                }
+            }
+            hunt_row = first_incomplete;
+
+            match found {
+                Some((loc, dir)) => {
+                    visited[grid.index(&loc)] = true;
+                    grid.carve(&loc, dir);
+                    cell = loc;
+                }
+                None => break,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::maze::tests::check_perfect;
+
+    use super::*;
+
+    #[test]
+    fn perfect() {
+        check_perfect(HuntAndKill);
+    }
+}
@@ -0,0 +1,156 @@
+use derive_builder::Builder;
+use rand::Rng;
+
+use crate::direction::Cardinal;
+
+use super::{Location, MazeGrid, MazeModifier};
+
+/// Creates islands by blocking rectangular groups of cells and opening a loop
+/// of passages around each group.
+///
+/// Blocked cells have all of their passages removed (and are rendered as
+/// walls). Every cell that used to connect through a blocked cell is part of
+/// the surrounding loop, so the open cells remain connected.
+///
+/// Islands are never placed on the edge of the grid, and never include or
+/// surround blocked cells.
+///
+/// # Examples
+/// ```
+/// use rand::thread_rng;
+/// use proliferatr::maze::{HuntAndKill, Islands, MazeGrid, MazeModifier};
+///
+/// let mut rng = thread_rng();
+/// let mut grid = MazeGrid::generate(20, 20, &HuntAndKill, &mut rng);
+///
+/// let islands = Islands::builder().count(5).max_size(2).build().unwrap();
+///
+/// assert!(islands.modify(&mut grid, &mut rng));
+/// assert!(grid.is_connected());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct Islands {
+    /// The number of islands to create.
+    count: usize,
+
+    /// The maximum width and height of an island, in cells.
+    #[builder(default = "1")]
+    max_size: usize,
+
+    /// The number of times to attempt to place each island.
+    #[builder(default = "100")]
+    max_attempts: usize,
+}
+
+impl IslandsBuilder {
+    fn validate(&self) -> Result<(), String> {
+        if self.max_size == Some(0) {
+            return Err("max_size must be at least 1.".into());
+        }
+
+        Ok(())
+    }
+}
+
+impl Islands {
+    pub fn builder() -> IslandsBuilder {
+        IslandsBuilder::default()
+    }
+}
+
+impl MazeModifier for Islands {
+    fn modify<R: Rng + ?Sized>(&self, grid: &mut MazeGrid, rng: &mut R) -> bool {
+        let mut created = 0;
+
+        for _ in 0..self.count {
+            for _ in 0..self.max_attempts {
+                let width = rng.gen_range(1..=self.max_size);
+                let height = rng.gen_range(1..=self.max_size);
+
+                // leave room for the surrounding loop
+                if width + 2 > grid.width || height + 2 > grid.height {
+                    continue;
+                }
+
+                let top = rng.gen_range(1..=(grid.height - height - 1));
+                let left = rng.gen_range(1..=(grid.width - width - 1));
+
+                // the island and the ring of cells around it
+                let area: Vec<Location> = ((top - 1)..=(top + height))
+                    .flat_map(|row| ((left - 1)..=(left + width)).map(move |col| (row, col).into()))
+                    .collect();
+
+                if area.iter().any(|l| grid.get(l) == Some(0)) {
+                    continue;
+                }
+
+                let inside = |l: &Location| {
+                    (top..(top + height)).contains(&l.row)
+                        && (left..(left + width)).contains(&l.col)
+                };
+
+                for loc in area.iter().filter(|l| inside(l)) {
+                    for dir in [
+                        Cardinal::North,
+                        Cardinal::East,
+                        Cardinal::South,
+                        Cardinal::West,
+                    ] {
+                        grid.uncarve(loc, dir);
+                    }
+                }
+
+                // join each ring cell to the next ring cell clockwise
+                for loc in area.iter().filter(|l| !inside(l)) {
+                    let dir = if loc.row == top - 1 && loc.col < left + width {
+                        Cardinal::East
+                    } else if loc.col == left + width && loc.row < top + height {
+                        Cardinal::South
+                    } else if loc.row == top + height && loc.col > left - 1 {
+                        Cardinal::West
+                    } else {
+                        Cardinal::North
+                    };
+
+                    grid.carve(loc, dir);
+                }
+
+                created += 1;
+                break;
+            }
+        }
+
+        created > 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::maze::RecursiveBacktracker;
+
+    use super::*;
+
+    #[test]
+    fn islands() {
+        let mut rng = StdRng::seed_from_u64(38);
+        let mut grid = MazeGrid::generate(20, 20, &RecursiveBacktracker, &mut rng);
+
+        let islands = Islands::builder().count(6).max_size(3).build().unwrap();
+        assert!(islands.modify(&mut grid, &mut rng));
+        assert!(grid.is_connected());
+
+        let blocked = grid.locations().filter(|l| grid.get(l) == Some(0)).count();
+        assert!(blocked >= 6);
+
+        // every blocked cell is surrounded by open cells or other blocked
+        // cells, never the edge of the grid
+        for loc in grid.locations().filter(|l| grid.get(l) == Some(0)) {
+            assert_eq!(grid.neighbors(&loc).count(), 4);
+        }
+
+        assert!(Islands::builder().count(1).max_size(0).build().is_err());
+    }
+}
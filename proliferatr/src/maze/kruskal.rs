@@ -0,0 +1,49 @@
+use rand::{seq::SliceRandom, Rng};
+
+use super::{disjoint_set::DisjointSet, MazeAlgorithm, MazeGrid, Topology};
+
+/// Carves a maze with a randomized version of Kruskal's algorithm: consider
+/// every wall in a random order, and remove it if the cells on either side are
+/// not yet connected.
+///
+/// This produces mazes with many short dead ends.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Kruskal;
+
+impl<T: Topology> MazeAlgorithm<T> for Kruskal {
+    fn generate<R: Rng + ?Sized>(&self, grid: &mut MazeGrid<T>, rng: &mut R) {
+        grid.clear();
+
+        // each wall is seen from both sides, so only keep it from the side
+        // that comes first
+        let mut walls = Vec::with_capacity(grid.size() * T::DIRECTIONS.len() / 2);
+        for loc in grid.locations() {
+            for (dir, other, _) in grid.neighbors(&loc) {
+                if grid.index(&other) > grid.index(&loc) {
+                    walls.push((loc, dir, other));
+                }
+            }
+        }
+        walls.shuffle(rng);
+
+        let mut sets = DisjointSet::new(grid.size());
+
+        for (loc, dir, other) in walls {
+            if sets.union(grid.index(&loc), grid.index(&other)) {
+                grid.carve(&loc, dir);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::maze::tests::check_perfect;
+
+    use super::*;
+
+    #[test]
+    fn perfect() {
+        check_perfect(Kruskal);
+    }
+}
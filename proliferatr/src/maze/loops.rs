@@ -0,0 +1,136 @@
+use derive_builder::Builder;
+use rand::{seq::IteratorRandom, Rng};
+
+use crate::direction::Cardinal;
+
+use super::{Location, MazeGrid, MazeModifier};
+
+/// Adds loops to a maze by opening random walls between adjacent open cells.
+///
+/// Unless `allow_open_areas` is set, walls are only opened if doing so would
+/// not create a 2x2 block of mutually connected cells, so the maze keeps its
+/// corridors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Builder)]
+pub struct Loops {
+    /// The number of walls to open.
+    count: usize,
+
+    #[builder(default)]
+    allow_open_areas: bool,
+
+    /// The number of times to attempt to find a suitable wall to open, per
+    /// loop.
+    #[builder(default = "100")]
+    max_attempts: usize,
+}
+
+impl Loops {
+    pub fn builder() -> LoopsBuilder {
+        LoopsBuilder::default()
+    }
+}
+
+/// Returns `true` if opening the wall on the `dir` side of `loc` would
+/// complete a 2x2 block of mutually connected cells.
+fn completes_open_area(grid: &MazeGrid, loc: &Location, dir: Cardinal) -> bool {
+    let Some(other) = grid.neighbor(loc, dir) else {
+        return false;
+    };
+
+    // the 2x2 blocks containing the wall extend to either side of it, and
+    // consist of `loc`, `other` and their neighbors on that side
+    [dir.left(), dir.right()].into_iter().any(|side| {
+        let Some(a) = grid.neighbor(loc, side) else {
+            return false;
+        };
+
+        grid.has_passage(loc, side) && grid.has_passage(&other, side) && grid.has_passage(&a, dir)
+    })
+}
+
+impl MazeModifier for Loops {
+    fn modify<R: Rng + ?Sized>(&self, grid: &mut MazeGrid, rng: &mut R) -> bool {
+        if grid.size() == 0 {
+            return false;
+        }
+
+        let mut opened = 0;
+
+        for _ in 0..self.count {
+            for _ in 0..self.max_attempts {
+                let loc = grid.random_cell(rng);
+                if grid.get(&loc) == Some(0) {
+                    continue;
+                }
+
+                let wall = grid
+                    .neighbors(&loc)
+                    .filter(|(d, _, v)| *v != 0 && !grid.has_passage(&loc, *d))
+                    .filter(|(d, _, _)| {
+                        self.allow_open_areas || !completes_open_area(grid, &loc, *d)
+                    })
+                    .choose(rng);
+
+                if let Some((dir, _, _)) = wall {
+                    grid.carve(&loc, dir);
+                    opened += 1;
+                    break;
+                }
+            }
+        }
+
+        opened > 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::maze::Kruskal;
+
+    use super::*;
+
+    fn passages(grid: &MazeGrid) -> usize {
+        grid.locations().map(|l| grid.degree(&l)).sum::<usize>() / 2
+    }
+
+    fn has_open_area(grid: &MazeGrid) -> bool {
+        grid.locations().any(|l| {
+            let (Some(e), Some(s)) = (
+                grid.neighbor(&l, Cardinal::East),
+                grid.neighbor(&l, Cardinal::South),
+            ) else {
+                return false;
+            };
+
+            grid.has_passage(&l, Cardinal::East)
+                && grid.has_passage(&l, Cardinal::South)
+                && grid.has_passage(&e, Cardinal::South)
+                && grid.has_passage(&s, Cardinal::East)
+        })
+    }
+
+    #[test]
+    fn loops() {
+        let mut rng = StdRng::seed_from_u64(38);
+        let original = MazeGrid::generate(20, 20, &Kruskal, &mut rng);
+
+        let mut grid = original.clone();
+        let loops = Loops::builder().count(30).build().unwrap();
+        assert!(loops.modify(&mut grid, &mut rng));
+        assert_eq!(passages(&grid), passages(&original) + 30);
+        assert!(grid.is_connected());
+        assert!(!has_open_area(&grid));
+
+        let mut grid = original.clone();
+        let loops = Loops::builder()
+            .count(400)
+            .allow_open_areas(true)
+            .build()
+            .unwrap();
+        loops.modify(&mut grid, &mut rng);
+        assert!(grid.is_connected());
+        assert!(has_open_area(&grid));
+    }
+}
@@ -1,10 +1,58 @@
-use std::fmt::Display;
+use std::{collections::VecDeque, fmt::Display};
 
 use itertools::Itertools;
-use rand::{seq::IteratorRandom, Rng};
+use rand::Rng;
 
 use crate::direction::Cardinal;
 
+mod aldous_broder;
+mod algorithm;
+mod analysis;
+mod binary_tree;
+mod braid;
+mod disjoint_set;
+mod ellers;
+mod growing_tree;
+mod hex_renderer;
+mod hunt_and_kill;
+mod islands;
+mod kruskal;
+mod loops;
+mod modifier;
+mod prim;
+mod recursive_backtracker;
+mod renderer;
+mod rooms;
+mod sidewinder;
+mod topology;
+mod wilsons;
+
+pub use aldous_broder::AldousBroder;
+pub use algorithm::MazeAlgorithm;
+pub use analysis::{DistanceMap, MazeStats, Solution};
+pub use binary_tree::BinaryTree;
+pub use braid::{Braid, BraidBuilder, BraidBuilderError};
+pub use ellers::Ellers;
+pub use growing_tree::{
+    GrowingTree, GrowingTreeBuilder, GrowingTreeBuilderError, GrowingTreeStrategy,
+};
+pub use hex_renderer::{HexLayout, HexRenderer, HexRendererBuilder, HexRendererBuilderError};
+pub use hunt_and_kill::HuntAndKill;
+pub use islands::{Islands, IslandsBuilder, IslandsBuilderError};
+pub use kruskal::Kruskal;
+pub use loops::{Loops, LoopsBuilder, LoopsBuilderError};
+pub use modifier::MazeModifier;
+pub use prim::Prim;
+pub use recursive_backtracker::RecursiveBacktracker;
+pub use renderer::{
+    MazeMark, MazeRenderError, MazeRenderer, MazeRendererBuilder, MazeRendererBuilderError,
+    Opening, WallStyle,
+};
+pub use rooms::{Rooms, RoomsBuilder, RoomsBuilderError};
+pub use sidewinder::Sidewinder;
+pub use topology::{HorizHex, MaskError, Masked, Square, Topology, VertHex};
+pub use wilsons::Wilsons;
+
 const LOC_CARD_NEIGHBOR_OFFSETS: [(Cardinal, i64, i64); 4] = [
     (Cardinal::North, -1, 0),
     (Cardinal::East, 0, 1),
@@ -68,28 +116,119 @@ impl Location {
     }
 }
 
+/// A maze, stored as a grid of cells whose bits record the directions in which
+/// each cell has a passage to its neighbor.
+///
+/// The shape of the cells, and which cells are adjacent, is determined by the
+/// [Topology], which defaults to [Square]. Cells without any passages are
+/// considered blocked.
 #[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct MazeGrid {
+pub struct MazeGrid<T = Square> {
     pub cells: Vec<Vec<u8>>,
     pub width: usize,
     pub height: usize,
+    topology: T,
 }
 
-impl MazeGrid {
+impl MazeGrid<Square> {
     pub fn new(width: usize, height: usize) -> Self {
+        Self::with_topology(Square, width, height)
+    }
+
+    /// Make a new grid of the given dimensions, carving a maze into it using
+    /// the specified algorithm.
+    pub fn generate<A: MazeAlgorithm, R: Rng + ?Sized>(
+        width: usize,
+        height: usize,
+        algorithm: &A,
+        rng: &mut R,
+    ) -> Self {
+        Self::generate_with(Square, width, height, algorithm, rng)
+    }
+}
+
+impl<T: Topology> MazeGrid<Masked<T>> {
+    /// Make a new grid with the same dimensions as the `mask`.
+    ///
+    /// Prefer this to [MazeGrid::with_topology] for masks, as a grid smaller
+    /// than the mask crops it, which can split the enabled cells into regions
+    /// that no maze can join.
+    pub fn masked(mask: Masked<T>) -> Self {
+        let (width, height) = (mask.width(), mask.height());
+        Self::with_topology(mask, width, height)
+    }
+
+    /// Make a new grid with the same dimensions as the `mask`, carving a maze
+    /// into it using the specified algorithm.
+    pub fn generate_masked<A: MazeAlgorithm<Masked<T>>, R: Rng + ?Sized>(
+        mask: Masked<T>,
+        algorithm: &A,
+        rng: &mut R,
+    ) -> Self {
+        let mut grid = Self::masked(mask);
+        algorithm.generate(&mut grid, rng);
+        grid
+    }
+}
+
+impl<T: Topology> MazeGrid<T> {
+    /// Make a new grid of the given dimensions with the specified topology.
+    ///
+    /// # Examples
+    /// ```
+    /// use proliferatr::{direction::VertHexDir, maze::{MazeGrid, VertHex}};
+    ///
+    /// let mut grid = MazeGrid::with_topology(VertHex, 3, 3);
+    ///
+    /// // odd rows are shifted right
+    /// assert_eq!(grid.carve(&(1, 1).into(), VertHexDir::NorthEast), Some((0, 2).into()));
+    /// assert_eq!(grid.carve(&(0, 1).into(), VertHexDir::SouthEast), Some((1, 1).into()));
+    /// assert_eq!(grid.degree(&(1, 1).into()), 2);
+    /// ```
+    pub fn with_topology(topology: T, width: usize, height: usize) -> Self {
         Self {
             cells: vec![vec![0; width]; height],
             width,
             height,
+            topology,
         }
     }
 
+    /// Make a new grid of the given dimensions with the specified topology,
+    /// carving a maze into it using the specified algorithm.
+    pub fn generate_with<A: MazeAlgorithm<T>, R: Rng + ?Sized>(
+        topology: T,
+        width: usize,
+        height: usize,
+        algorithm: &A,
+        rng: &mut R,
+    ) -> Self {
+        let mut grid = Self::with_topology(topology, width, height);
+        algorithm.generate(&mut grid, rng);
+        grid
+    }
+
+    /// The topology of this grid.
+    pub fn topology(&self) -> &T {
+        &self.topology
+    }
+
+    /// The number of locations in this grid, including any disabled by the
+    /// topology. This is the length needed for per-cell state indexed by
+    /// [MazeGrid::index].
     pub fn size(&self) -> usize {
         self.width * self.height
     }
 
+    /// The number of cells in this grid that are enabled by the topology.
+    pub fn cell_count(&self) -> usize {
+        self.locations().count()
+    }
+
+    /// Returns `true` if `loc` is within this grid and enabled by the
+    /// topology.
     pub fn contains(&self, loc: &Location) -> bool {
-        loc.row < self.height && loc.col < self.width
+        loc.row < self.height && loc.col < self.width && self.topology.is_enabled(loc)
     }
 
     pub fn get(&self, loc: &Location) -> Option<u8> {
@@ -109,54 +248,209 @@ impl MazeGrid {
         }
     }
 
-    pub fn random_cell<R: Rng + Clone + ?Sized>(&self, rng: &mut R) -> Location {
-        Location {
-            row: rng.gen_range(0..self.height),
-            col: rng.gen_range(0..self.width),
+    /// A random enabled cell. Panics if there are none.
+    pub fn random_cell<R: Rng + ?Sized>(&self, rng: &mut R) -> Location {
+        assert!(self.cell_count() > 0, "the grid has no enabled cells");
+
+        loop {
+            let loc = Location {
+                row: rng.gen_range(0..self.height),
+                col: rng.gen_range(0..self.width),
+            };
+
+            if self.contains(&loc) {
+                return loc;
+            }
         }
     }
 
-    pub fn neighbors(&self, loc: &Location) -> impl Iterator<Item = (Cardinal, Location, u8)> + '_ {
-        loc.cardinal_neighbors()
-            .filter(|(_, l)| self.contains(l))
-            .map(|(d, l)| (d, l, self.cells[l.row][l.col]))
+    /// Remove every passage from this grid.
+    pub fn clear(&mut self) {
+        for row in self.cells.iter_mut() {
+            row.fill(0);
+        }
     }
 
-    pub fn char_representation(&self) -> Vec<Vec<char>> {
-        // the top and left edge isn't included
-        let rendered_height = self.height * 2 + 1;
-        let rendered_width = self.width * 2 + 1;
-        let mut output = vec![vec!['#'; self.width * 2 + 1]; self.height * 2 + 1];
-
-        // TODO: make configurable
-        // corner goals
-        output[0][1] = '.';
-        output[rendered_height - 1][rendered_width - 2] = '.';
-
-        for r in 0..self.height {
-            for c in 0..self.width {
-                let v = self.cells[r][c];
-                if v == 0 {
+    /// Iterate over every enabled location in this grid, in row-major order.
+    pub fn locations(&self) -> impl Iterator<Item = Location> + '_ {
+        (0..self.height)
+            .flat_map(move |row| (0..self.width).map(move |col| Location { row, col }))
+            .filter(|l| self.topology.is_enabled(l))
+    }
+
+    /// The row-major index of `loc`, which is useful for tracking per-cell
+    /// state in a flat `Vec`.
+    pub fn index(&self, loc: &Location) -> usize {
+        loc.row * self.width + loc.col
+    }
+
+    /// The neighbor of `loc` in the direction `dir`, if it is in the grid.
+    pub fn neighbor(&self, loc: &Location, dir: T::Dir) -> Option<Location> {
+        self.topology
+            .neighbor(loc, dir)
+            .filter(|l| self.contains(l))
+    }
+
+    /// The direction from `loc` to the adjacent location `other`, if they are
+    /// adjacent.
+    pub fn direction_to(&self, loc: &Location, other: &Location) -> Option<T::Dir> {
+        T::DIRECTIONS
+            .iter()
+            .copied()
+            .find(|d| self.topology.neighbor(loc, *d) == Some(*other))
+    }
+
+    /// Returns `true` if there is a passage from `loc` in the direction `dir`.
+    pub fn has_passage(&self, loc: &Location, dir: T::Dir) -> bool {
+        self.get(loc).map(|v| v & T::bit(dir) != 0).unwrap_or(false)
+    }
+
+    /// Open a passage between `loc` and its neighbor in the direction `dir`.
+    ///
+    /// Returns the neighbor, or `None` (without altering the grid) if there is
+    /// no such neighbor.
+    pub fn carve(&mut self, loc: &Location, dir: T::Dir) -> Option<Location> {
+        if !self.contains(loc) {
+            return None;
+        }
+
+        let other = self.neighbor(loc, dir)?;
+        self.cells[loc.row][loc.col] |= T::bit(dir);
+        self.cells[other.row][other.col] |= T::bit(T::opposite(dir));
+        Some(other)
+    }
+
+    /// Close the passage between `loc` and its neighbor in the direction `dir`.
+    ///
+    /// Returns the neighbor, or `None` (without altering the grid) if there is
+    /// no such neighbor.
+    pub fn uncarve(&mut self, loc: &Location, dir: T::Dir) -> Option<Location> {
+        if !self.contains(loc) {
+            return None;
+        }
+
+        let other = self.neighbor(loc, dir)?;
+        self.cells[loc.row][loc.col] &= !T::bit(dir);
+        self.cells[other.row][other.col] &= !T::bit(T::opposite(dir));
+        Some(other)
+    }
+
+    /// The number of passages leading out of `loc`.
+    pub fn degree(&self, loc: &Location) -> usize {
+        self.get(loc).map(|v| v.count_ones() as usize).unwrap_or(0)
+    }
+
+    /// Returns `true` if every open cell (a cell with at least one passage) can
+    /// be reached from every other open cell.
+    pub fn is_connected(&self) -> bool {
+        let Some(start) = self.locations().find(|l| self.degree(l) > 0) else {
+            return true;
+        };
+
+        self.reachable_from(&start) == self.locations().filter(|l| self.degree(l) > 0).count()
+    }
+
+    /// Returns `true` if this grid is a perfect maze: every passage is open from
+    /// both sides and stays within the grid, and there is exactly one path
+    /// between every pair of cells.
+    ///
+    /// # Examples
+    /// ```
+    /// use rand::thread_rng;
+    /// use proliferatr::{direction::Cardinal, maze::{MazeGrid, Sidewinder}};
+    ///
+    /// let mut grid = MazeGrid::generate(10, 8, &Sidewinder::default(), &mut thread_rng());
+    /// assert!(grid.is_perfect());
+    ///
+    /// // opening every passage introduces loops
+    /// for loc in grid.locations().collect::<Vec<_>>() {
+    ///     grid.carve(&loc, Cardinal::East);
+    ///     grid.carve(&loc, Cardinal::South);
+    /// }
+    /// assert!(!grid.is_perfect());
+    /// ```
+    pub fn is_perfect(&self) -> bool {
+        let Some(start) = self.locations().next() else {
+            return true;
+        };
+
+        let mut passages = 0;
+
+        for loc in self.locations() {
+            for dir in T::DIRECTIONS.iter().copied() {
+                if !self.has_passage(&loc, dir) {
                     continue;
                 }
 
-                output[r * 2 + 1][c * 2 + 1] = '.';
-
-                if v & Cardinal::East as u8 != 0 {
-                    output[r * 2 + 1][c * 2 + 2] = '.';
+                match self.neighbor(&loc, dir) {
+                    Some(other) if self.has_passage(&other, T::opposite(dir)) => passages += 1,
+                    _ => return false,
                 }
+            }
+        }
 
-                if v & Cardinal::South as u8 != 0 {
-                    output[r * 2 + 2][c * 2 + 1] = '.';
+        let cells = self.cell_count();
+
+        // every passage was counted from both sides
+        passages / 2 == cells - 1 && self.reachable_from(&start) == cells
+    }
+
+    /// The number of cells reachable from `start` through passages, including
+    /// `start` itself.
+    fn reachable_from(&self, start: &Location) -> usize {
+        let mut seen = vec![false; self.size()];
+        seen[self.index(start)] = true;
+        let mut queue = VecDeque::from([*start]);
+        let mut reached = 1;
+
+        while let Some(loc) = queue.pop_front() {
+            for (dir, other, _) in self.neighbors(&loc) {
+                let idx = self.index(&other);
+                if self.has_passage(&loc, dir) && !seen[idx] {
+                    seen[idx] = true;
+                    reached += 1;
+                    queue.push_back(other);
                 }
             }
         }
 
-        output
+        reached
+    }
+
+    pub fn neighbors(&self, loc: &Location) -> impl Iterator<Item = (T::Dir, Location, u8)> + '_ {
+        let loc = *loc;
+        T::DIRECTIONS.iter().filter_map(move |dir| {
+            self.neighbor(&loc, *dir)
+                .map(|l| (*dir, l, self.cells[l.row][l.col]))
+        })
     }
 }
 
-impl Display for MazeGrid {
+impl MazeGrid<Square> {
+    /// Render this grid with `#` walls and `.` floors, with an entrance at the
+    /// top of the upper left cell and an exit at the bottom of the lower right
+    /// cell.
+    ///
+    /// See [MazeRenderer] for more control over rendering.
+    pub fn char_representation(&self) -> Vec<Vec<char>> {
+        if self.size() == 0 {
+            return vec![vec!['#']];
+        }
+
+        MazeRenderer::builder()
+            .entrance(Opening::new((0, 0), Cardinal::North))
+            .exit(Opening::new(
+                (self.height - 1, self.width - 1),
+                Cardinal::South,
+            ))
+            .build()
+            .expect("all fields have defaults")
+            .render(self)
+            .expect("the corners are on the edge")
+    }
+}
+
+impl Display for MazeGrid<Square> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         self.char_representation()
             .iter()
@@ -166,23 +460,35 @@ impl Display for MazeGrid {
     }
 }
 
-// slow, but just to test out the grid rendering
-pub fn aldos_broder<R: Rng + Clone + ?Sized>(rng: &mut R, grid: &mut MazeGrid) {
-    let mut unvisited = grid.size() - 1;
-    let mut cell = grid.random_cell(rng);
-    let mut cell_value = grid.get(&cell).unwrap();
-
-    while unvisited > 0 {
-        if let Some((dir, loc, mut v)) = grid.neighbors(&cell).choose(rng) {
-            if v == 0 {
-                v |= dir.opposite() as u8;
-                grid.set(&loc, v);
-                grid.set(&cell, cell_value | dir as u8);
-                unvisited -= 1;
-            }
+/// Carve a maze into the `grid` using the [AldousBroder] algorithm.
+pub fn aldos_broder<T: Topology, R: Rng + ?Sized>(rng: &mut R, grid: &mut MazeGrid<T>) {
+    AldousBroder.generate(grid, rng);
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
 
-            cell = loc;
-            cell_value = v;
+    use super::*;
+
+    /// Assert that the `algorithm` produces perfect mazes for a variety of grid
+    /// sizes and seeds, and that the mazes depend on the seed.
+    pub(crate) fn check_perfect<A: MazeAlgorithm>(algorithm: A) {
+        for (width, height) in [(1, 1), (1, 7), (7, 1), (2, 2), (10, 10), (31, 17)] {
+            for seed in 0..3 {
+                let mut rng = StdRng::seed_from_u64(seed);
+                let grid = MazeGrid::generate(width, height, &algorithm, &mut rng);
+                assert!(grid.is_perfect(), "{width}x{height} seed {seed}\n{grid}");
+
+                // regenerating into a used grid starts from scratch
+                let mut again = grid.clone();
+                algorithm.generate(&mut again, &mut StdRng::seed_from_u64(seed));
+                assert_eq!(grid, again);
+            }
         }
+
+        let a = MazeGrid::generate(20, 20, &algorithm, &mut StdRng::seed_from_u64(1));
+        let b = MazeGrid::generate(20, 20, &algorithm, &mut StdRng::seed_from_u64(2));
+        assert_ne!(a, b);
     }
 }
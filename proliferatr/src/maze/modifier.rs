@@ -0,0 +1,38 @@
+use rand::Rng;
+
+use super::{MazeGrid, Square, Topology};
+
+/// A post-processing step that alters an existing maze in a [MazeGrid].
+///
+/// Cells without any passages are considered blocked. Modifiers never open
+/// passages into blocked cells (unless that is their purpose), and they
+/// preserve connectivity: if every open cell could reach every other open
+/// cell before the modification, the same is true after it (see
+/// [MazeGrid::is_connected]).
+///
+/// As with [MazeAlgorithm], modifiers that only depend on which cells are
+/// adjacent (such as [Braid]) work with any [Topology], while the rest are
+/// only implemented for [Square].
+///
+/// [MazeAlgorithm]: super::MazeAlgorithm
+/// [Braid]: super::Braid
+///
+/// # Examples
+/// ```
+/// use rand::thread_rng;
+/// use proliferatr::maze::{Braid, MazeGrid, MazeModifier, RecursiveBacktracker};
+///
+/// let mut rng = thread_rng();
+/// let mut grid = MazeGrid::generate(20, 20, &RecursiveBacktracker, &mut rng);
+///
+/// Braid::builder().fraction(1.0).build().unwrap().modify(&mut grid, &mut rng);
+///
+/// assert!(grid.is_connected());
+/// assert!(!grid.is_perfect());
+/// ```
+pub trait MazeModifier<T: Topology = Square> {
+    /// Modify the maze in `grid`, using `rng` for any random decisions.
+    ///
+    /// Returns `true` if the grid was modified.
+    fn modify<R: Rng + ?Sized>(&self, grid: &mut MazeGrid<T>, rng: &mut R) -> bool;
+}
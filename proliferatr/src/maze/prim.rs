@@ -0,0 +1,72 @@
+use rand::{seq::IteratorRandom, Rng};
+
+use super::{MazeAlgorithm, MazeGrid, Topology};
+
+/// Carves a maze with a randomized version of Prim's algorithm: repeatedly
+/// pick a random cell bordering the maze and connect it to a random
+/// neighboring cell that is already part of the maze.
+///
+/// This produces mazes that radiate out from the starting cell, with many
+/// short dead ends.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Prim;
+
+impl<T: Topology> MazeAlgorithm<T> for Prim {
+    fn generate<R: Rng + ?Sized>(&self, grid: &mut MazeGrid<T>, rng: &mut R) {
+        grid.clear();
+        if grid.cell_count() == 0 {
+            return;
+        }
+
+        let mut in_maze = vec![false; grid.size()];
+        let mut in_frontier = vec![false; grid.size()];
+        let mut frontier = Vec::new();
+
+        let start = grid.random_cell(rng);
+        in_maze[grid.index(&start)] = true;
+
+        for (_, loc, _) in grid.neighbors(&start) {
+            in_frontier[grid.index(&loc)] = true;
+            frontier.push(loc);
+        }
+
+        while !frontier.is_empty() {
+            let cell = frontier.swap_remove(rng.gen_range(0..frontier.len()));
+            let idx = grid.index(&cell);
+            in_maze[idx] = true;
+
+            let (dir, _, _) = grid
+                .neighbors(&cell)
+                .filter(|(_, l, _)| in_maze[grid.index(l)])
+                .choose(rng)
+                .expect("frontier cells border the maze");
+            grid.carve(&cell, dir);
+
+            let new: Vec<_> = grid
+                .neighbors(&cell)
+                .map(|(_, l, _)| l)
+                .filter(|l| {
+                    let i = grid.index(l);
+                    !in_maze[i] && !in_frontier[i]
+                })
+                .collect();
+
+            for loc in new {
+                in_frontier[grid.index(&loc)] = true;
+                frontier.push(loc);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::maze::tests::check_perfect;
+
+    use super::*;
+
+    #[test]
+    fn perfect() {
+        check_perfect(Prim);
+    }
+}
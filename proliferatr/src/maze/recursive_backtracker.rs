@@ -0,0 +1,57 @@
+use rand::{seq::IteratorRandom, Rng};
+
+use super::{MazeAlgorithm, MazeGrid, Topology};
+
+/// Carves a maze with a randomized depth-first search, backtracking whenever
+/// the search reaches a cell with no unvisited neighbors.
+///
+/// This produces mazes with long, winding corridors and few dead ends.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RecursiveBacktracker;
+
+impl<T: Topology> MazeAlgorithm<T> for RecursiveBacktracker {
+    fn generate<R: Rng + ?Sized>(&self, grid: &mut MazeGrid<T>, rng: &mut R) {
+        grid.clear();
+        if grid.cell_count() == 0 {
+            return;
+        }
+
+        let mut visited = vec![false; grid.size()];
+        let start = grid.random_cell(rng);
+        visited[grid.index(&start)] = true;
+
+        // an explicit stack, as the recursion can be as deep as the grid is
+        // large
+        let mut stack = vec![start];
+
+        while let Some(cell) = stack.last().copied() {
+            let next = grid
+                .neighbors(&cell)
+                .filter(|(_, l, _)| !visited[grid.index(l)])
+                .choose(rng);
+
+            match next {
+                Some((dir, loc, _)) => {
+                    visited[grid.index(&loc)] = true;
+                    grid.carve(&cell, dir);
+                    stack.push(loc);
+                }
+                None => {
+                    stack.pop();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::maze::tests::check_perfect;
+
+    use super::*;
+
+    #[test]
+    fn perfect() {
+        check_perfect(RecursiveBacktracker);
+    }
+}
@@ -0,0 +1,407 @@
+use derive_builder::Builder;
+use thiserror::Error;
+
+use crate::{direction::Cardinal, grid::CharGrid};
+
+use super::{Location, MazeGrid, Topology};
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum MazeRenderError {
+    #[error("Cannot open {dir} from {loc:?}, as it is not on that edge of the maze.")]
+    NotOnEdge { loc: Location, dir: Cardinal },
+
+    #[error("Mark at {0:?} is outside of the maze.")]
+    MarkOutOfBounds(Location),
+}
+
+/// How the walls of a maze are drawn.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum WallStyle {
+    /// Every wall is drawn with the wall glyph.
+    #[default]
+    Block,
+
+    /// Walls are drawn with Unicode box-drawing characters that connect to
+    /// their neighbors, ignoring the wall glyph.
+    ThinWalls,
+}
+
+/// An opening in the outer wall of a maze, leading out of `loc` in the
+/// direction `dir`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Opening {
+    pub loc: Location,
+    pub dir: Cardinal,
+}
+
+impl Opening {
+    pub fn new(loc: impl Into<Location>, dir: Cardinal) -> Self {
+        Self {
+            loc: loc.into(),
+            dir,
+        }
+    }
+}
+
+/// A glyph drawn over the rendered maze, either in the cell at `loc` or, if
+/// `dir` is specified, in the wall or passage between `loc` and its neighbor
+/// in that direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MazeMark {
+    pub loc: Location,
+    pub dir: Option<Cardinal>,
+    pub glyph: char,
+}
+
+impl MazeMark {
+    /// Mark the cell at `loc`.
+    pub fn cell(loc: impl Into<Location>, glyph: char) -> Self {
+        Self {
+            loc: loc.into(),
+            dir: None,
+            glyph,
+        }
+    }
+
+    /// Mark the wall or passage on the `dir` side of the cell at `loc`.
+    pub fn side(loc: impl Into<Location>, dir: Cardinal, glyph: char) -> Self {
+        Self {
+            loc: loc.into(),
+            dir: Some(dir),
+            glyph,
+        }
+    }
+}
+
+/// Renders a [MazeGrid] as characters.
+///
+/// A maze of `width` by `height` cells is rendered as `2 * width + 1` by
+/// `2 * height + 1` characters, where the cell at `(row, col)` is drawn at
+/// `(2 * row + 1, 2 * col + 1)` and the characters between cells are either
+/// walls or floor, depending on whether there is a passage. Cells without any
+/// passages are drawn as walls.
+///
+/// # Examples
+/// ```
+/// use proliferatr::{
+///     direction::Cardinal,
+///     maze::{MazeGrid, MazeMark, MazeRenderer, Opening, WallStyle},
+/// };
+///
+/// let mut grid = MazeGrid::new(3, 2);
+/// grid.carve(&(0, 0).into(), Cardinal::East);
+/// grid.carve(&(0, 1).into(), Cardinal::East);
+/// grid.carve(&(0, 2).into(), Cardinal::South);
+/// grid.carve(&(1, 2).into(), Cardinal::West);
+/// grid.carve(&(1, 1).into(), Cardinal::West);
+///
+/// let renderer = MazeRenderer::builder()
+///     .entrance(Opening::new((0, 0), Cardinal::West))
+///     .exit(Opening::new((1, 0), Cardinal::South))
+///     .start('S')
+///     .end('E')
+///     .mark(MazeMark::side((1, 2), Cardinal::West, '<'))
+///     .build()
+///     .unwrap();
+///
+/// let expected = [
+///     "#######",
+///     "S.....#",
+///     "#####.#",
+///     "#...<.#",
+///     "#E#####",
+/// ];
+///
+/// assert_eq!(renderer.render_string(&grid).unwrap(), expected.join("\n"));
+///
+/// let renderer = MazeRenderer::builder()
+///     .style(WallStyle::ThinWalls)
+///     .floor(' ')
+///     .entrance(Opening::new((0, 0), Cardinal::West))
+///     .exit(Opening::new((1, 0), Cardinal::South))
+///     .build()
+///     .unwrap();
+///
+/// let expected = [
+///     "╶─────┐",
+///     "      │",
+///     "┌───╴ │",
+///     "│     │",
+///     "╵ ╶───┘",
+/// ];
+///
+/// assert_eq!(
+///     renderer.render_grid(&grid).unwrap().to_string(),
+///     expected.join("\n")
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Builder)]
+pub struct MazeRenderer {
+    #[builder(default)]
+    style: WallStyle,
+
+    #[builder(default = "'#'")]
+    wall: char,
+
+    #[builder(default = "'.'")]
+    floor: char,
+
+    /// The glyph drawn in the entrance, if there is one. Defaults to the floor
+    /// glyph.
+    #[builder(default, setter(strip_option))]
+    start: Option<char>,
+
+    /// The glyph drawn in the exit, if there is one. Defaults to the floor
+    /// glyph.
+    #[builder(default, setter(strip_option))]
+    end: Option<char>,
+
+    #[builder(default, setter(strip_option))]
+    entrance: Option<Opening>,
+
+    #[builder(default, setter(strip_option))]
+    exit: Option<Opening>,
+
+    /// Additional glyphs drawn over the rendered maze, in order.
+    #[builder(default, setter(each(name = "mark")))]
+    marks: Vec<MazeMark>,
+}
+
+impl Default for MazeRenderer {
+    fn default() -> Self {
+        Self {
+            style: WallStyle::default(),
+            wall: '#',
+            floor: '.',
+            start: None,
+            end: None,
+            entrance: None,
+            exit: None,
+            marks: Vec::new(),
+        }
+    }
+}
+
+impl MazeRenderer {
+    pub fn builder() -> MazeRendererBuilder {
+        MazeRendererBuilder::default()
+    }
+
+    /// Render the `grid` as rows of characters.
+    pub fn render<T: Topology<Dir = Cardinal>>(
+        &self,
+        grid: &MazeGrid<T>,
+    ) -> Result<Vec<Vec<char>>, MazeRenderError> {
+        let height = grid.height * 2 + 1;
+        let width = grid.width * 2 + 1;
+
+        // start with a map of which positions are open, then pick glyphs
+        let mut open = vec![vec![false; width]; height];
+
+        for loc in grid.locations() {
+            let (r, c) = center(&loc);
+
+            if grid.get(&loc) != Some(0) {
+                open[r][c] = true;
+            }
+
+            if grid.has_passage(&loc, Cardinal::East) {
+                open[r][c + 1] = true;
+            }
+
+            if grid.has_passage(&loc, Cardinal::South) {
+                open[r + 1][c] = true;
+
+                // the post between four mutually connected cells is open
+                if grid.has_passage(&loc, Cardinal::East)
+                    && grid.neighbor(&loc, Cardinal::East).is_some_and(|east| {
+                        grid.has_passage(&east, Cardinal::South)
+                            && grid
+                                .neighbor(&loc, Cardinal::South)
+                                .is_some_and(|south| grid.has_passage(&south, Cardinal::East))
+                    })
+                {
+                    open[r + 1][c + 1] = true;
+                }
+            }
+        }
+
+        let mut openings = Vec::new();
+        for (opening, glyph) in [(&self.entrance, self.start), (&self.exit, self.end)] {
+            if let Some(opening) = opening {
+                if !on_edge(grid, opening) {
+                    return Err(MazeRenderError::NotOnEdge {
+                        loc: opening.loc,
+                        dir: opening.dir,
+                    });
+                }
+
+                let (r, c) = self.side(grid, &opening.loc, opening.dir)?;
+
+                open[r][c] = true;
+                openings.push((r, c, glyph.unwrap_or(self.floor)));
+            }
+        }
+
+        let mut output: Vec<Vec<char>> = (0..height)
+            .map(|r| {
+                (0..width)
+                    .map(|c| {
+                        if open[r][c] {
+                            self.floor
+                        } else {
+                            self.wall_glyph(&open, r, c)
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        for (r, c, glyph) in openings {
+            output[r][c] = glyph;
+        }
+
+        for mark in self.marks.iter() {
+            let (r, c) = match mark.dir {
+                Some(dir) => self.side(grid, &mark.loc, dir)?,
+                None if grid.contains(&mark.loc) => center(&mark.loc),
+                None => return Err(MazeRenderError::MarkOutOfBounds(mark.loc)),
+            };
+            output[r][c] = mark.glyph;
+        }
+
+        Ok(output)
+    }
+
+    /// Render the `grid` as a [CharGrid].
+    pub fn render_grid<T: Topology<Dir = Cardinal>>(
+        &self,
+        grid: &MazeGrid<T>,
+    ) -> Result<CharGrid, MazeRenderError> {
+        Ok(CharGrid::try_from(self.render(grid)?).expect("the outer wall is always rendered"))
+    }
+
+    /// Render the `grid` as a string, with rows separated by newlines.
+    pub fn render_string<T: Topology<Dir = Cardinal>>(
+        &self,
+        grid: &MazeGrid<T>,
+    ) -> Result<String, MazeRenderError> {
+        Ok(self
+            .render(grid)?
+            .iter()
+            .map(|r| r.iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n"))
+    }
+
+    /// The rendered position of the `dir` side of the cell at `loc`.
+    fn side<T: Topology<Dir = Cardinal>>(
+        &self,
+        grid: &MazeGrid<T>,
+        loc: &Location,
+        dir: Cardinal,
+    ) -> Result<(usize, usize), MazeRenderError> {
+        if !grid.contains(loc) {
+            return Err(MazeRenderError::MarkOutOfBounds(*loc));
+        }
+
+        let (r, c) = center(loc);
+        Ok(match dir {
+            Cardinal::North => (r - 1, c),
+            Cardinal::South => (r + 1, c),
+            Cardinal::East => (r, c + 1),
+            Cardinal::West => (r, c - 1),
+        })
+    }
+
+    fn wall_glyph(&self, open: &[Vec<bool>], r: usize, c: usize) -> char {
+        if self.style == WallStyle::Block {
+            return self.wall;
+        }
+
+        let is_wall = |r: Option<usize>, c: Option<usize>| match (r, c) {
+            (Some(r), Some(c)) => open.get(r).and_then(|row| row.get(c)) == Some(&false),
+            _ => false,
+        };
+
+        // posts (even rows and columns) connect in every direction, and walls
+        // between cells only connect to the posts at either end
+        let north = c % 2 == 0 && is_wall(r.checked_sub(1), Some(c));
+        let south = c % 2 == 0 && is_wall(Some(r + 1), Some(c));
+        let west = r % 2 == 0 && is_wall(Some(r), c.checked_sub(1));
+        let east = r % 2 == 0 && is_wall(Some(r), Some(c + 1));
+
+        match (north, east, south, west) {
+            (false, false, false, false) => '·',
+            (true, false, false, false) => '╵',
+            (false, true, false, false) => '╶',
+            (false, false, true, false) => '╷',
+            (false, false, false, true) => '╴',
+            (true, false, true, false) => '│',
+            (false, true, false, true) => '─',
+            (false, true, true, false) => '┌',
+            (false, false, true, true) => '┐',
+            (true, true, false, false) => '└',
+            (true, false, false, true) => '┘',
+            (true, true, true, false) => '├',
+            (true, false, true, true) => '┤',
+            (false, true, true, true) => '┬',
+            (true, true, false, true) => '┴',
+            (true, true, true, true) => '┼',
+        }
+    }
+}
+
+fn center(loc: &Location) -> (usize, usize) {
+    (loc.row * 2 + 1, loc.col * 2 + 1)
+}
+
+fn on_edge<T: Topology<Dir = Cardinal>>(grid: &MazeGrid<T>, opening: &Opening) -> bool {
+    grid.contains(&opening.loc) && grid.neighbor(&opening.loc, opening.dir).is_none()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn errors() {
+        let grid = MazeGrid::new(3, 3);
+
+        let renderer = MazeRenderer::builder()
+            .entrance(Opening::new((1, 1), Cardinal::North))
+            .build()
+            .unwrap();
+        assert_eq!(
+            renderer.render(&grid),
+            Err(MazeRenderError::NotOnEdge {
+                loc: (1, 1).into(),
+                dir: Cardinal::North
+            })
+        );
+
+        let renderer = MazeRenderer::builder()
+            .mark(MazeMark::cell((3, 0), 'x'))
+            .build()
+            .unwrap();
+        assert_eq!(
+            renderer.render(&grid),
+            Err(MazeRenderError::MarkOutOfBounds((3, 0).into()))
+        );
+    }
+
+    #[test]
+    fn open_areas() {
+        // a 2x2 room has no post in the middle
+        let mut grid = MazeGrid::new(2, 2);
+        grid.carve(&(0, 0).into(), Cardinal::East);
+        grid.carve(&(0, 0).into(), Cardinal::South);
+        grid.carve(&(1, 1).into(), Cardinal::North);
+        grid.carve(&(1, 1).into(), Cardinal::West);
+
+        assert_eq!(
+            MazeRenderer::default().render_string(&grid).unwrap(),
+            "#####\n#...#\n#...#\n#...#\n#####"
+        );
+    }
+}
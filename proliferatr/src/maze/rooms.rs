@@ -0,0 +1,188 @@
+use derive_builder::Builder;
+use rand::Rng;
+
+use crate::direction::Cardinal;
+
+use super::{Location, MazeGrid, MazeModifier};
+
+/// Carves rectangular rooms into a maze by opening every wall between the
+/// cells of each room.
+///
+/// Rooms are placed at random, and never include blocked cells. Unless
+/// `allow_overlap` is set, rooms do not overlap or touch each other.
+///
+/// # Examples
+/// ```
+/// use rand::thread_rng;
+/// use proliferatr::maze::{MazeGrid, MazeModifier, Rooms, Wilsons};
+///
+/// let mut rng = thread_rng();
+/// let mut grid = MazeGrid::generate(30, 30, &Wilsons, &mut rng);
+///
+/// let rooms = Rooms::builder()
+///     .count(3)
+///     .min_size(3)
+///     .max_size(5)
+///     .build()
+///     .unwrap();
+///
+/// assert!(rooms.modify(&mut grid, &mut rng));
+/// assert!(grid.is_connected());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct Rooms {
+    /// The number of rooms to carve.
+    count: usize,
+
+    /// The minimum width and height of a room, in cells.
+    #[builder(default = "2")]
+    min_size: usize,
+
+    /// The maximum width and height of a room, in cells.
+    #[builder(default = "4")]
+    max_size: usize,
+
+    #[builder(default)]
+    allow_overlap: bool,
+
+    /// The number of times to attempt to place each room.
+    #[builder(default = "100")]
+    max_attempts: usize,
+}
+
+impl RoomsBuilder {
+    fn validate(&self) -> Result<(), String> {
+        let min = self.min_size.unwrap_or(2);
+        let max = self.max_size.unwrap_or(4);
+
+        if min == 0 || min > max {
+            return Err("min_size must be at least 1 and no more than max_size.".into());
+        }
+
+        Ok(())
+    }
+}
+
+impl Rooms {
+    pub fn builder() -> RoomsBuilder {
+        RoomsBuilder::default()
+    }
+}
+
+/// A rectangle of cells, with an inclusive upper left and exclusive lower
+/// right.
+#[derive(Debug, Clone, Copy)]
+struct Room {
+    top: usize,
+    left: usize,
+    bottom: usize,
+    right: usize,
+}
+
+impl Room {
+    /// Returns `true` if the rooms overlap or share an edge.
+    fn touches(&self, other: &Room) -> bool {
+        self.top <= other.bottom
+            && other.top <= self.bottom
+            && self.left <= other.right
+            && other.left <= self.right
+    }
+
+    fn locations(&self) -> impl Iterator<Item = Location> + '_ {
+        (self.top..self.bottom)
+            .flat_map(move |row| (self.left..self.right).map(move |col| Location { row, col }))
+    }
+}
+
+impl MazeModifier for Rooms {
+    fn modify<R: Rng + ?Sized>(&self, grid: &mut MazeGrid, rng: &mut R) -> bool {
+        let mut placed: Vec<Room> = Vec::new();
+
+        for _ in 0..self.count {
+            for _ in 0..self.max_attempts {
+                let width = rng.gen_range(self.min_size..=self.max_size);
+                let height = rng.gen_range(self.min_size..=self.max_size);
+
+                if width > grid.width || height > grid.height {
+                    continue;
+                }
+
+                let top = rng.gen_range(0..=(grid.height - height));
+                let left = rng.gen_range(0..=(grid.width - width));
+                let room = Room {
+                    top,
+                    left,
+                    bottom: top + height,
+                    right: left + width,
+                };
+
+                if !self.allow_overlap && placed.iter().any(|r| r.touches(&room)) {
+                    continue;
+                }
+
+                if room.locations().any(|l| grid.get(&l) == Some(0)) {
+                    continue;
+                }
+
+                for loc in room.locations() {
+                    if loc.col + 1 < room.right {
+                        grid.carve(&loc, Cardinal::East);
+                    }
+
+                    if loc.row + 1 < room.bottom {
+                        grid.carve(&loc, Cardinal::South);
+                    }
+                }
+
+                placed.push(room);
+                break;
+            }
+        }
+
+        !placed.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::maze::{MazeRenderer, Prim};
+
+    use super::*;
+
+    #[test]
+    fn rooms() {
+        let mut rng = StdRng::seed_from_u64(38);
+        let mut grid = MazeGrid::generate(30, 30, &Prim, &mut rng);
+
+        let rooms = Rooms::builder()
+            .count(4)
+            .min_size(4)
+            .max_size(4)
+            .build()
+            .unwrap();
+        assert!(rooms.modify(&mut grid, &mut rng));
+        assert!(grid.is_connected());
+
+        // each room renders as a 7x7 open area
+        let rendered = MazeRenderer::default().render(&grid).unwrap();
+        let open_areas = (0..(rendered.len() - 6))
+            .flat_map(|r| (0..(rendered[0].len() - 6)).map(move |c| (r, c)))
+            .filter(|(r, c)| (0..7).all(|dr| (0..7).all(|dc| rendered[r + dr][c + dc] == '.')))
+            .count();
+        assert_eq!(open_areas, 4);
+
+        assert!(Rooms::builder()
+            .count(1)
+            .min_size(5)
+            .max_size(4)
+            .build()
+            .is_err());
+
+        // rooms that cannot fit
+        let mut grid = MazeGrid::generate(3, 3, &Prim, &mut rng);
+        assert!(!rooms.modify(&mut grid, &mut rng));
+    }
+}
@@ -0,0 +1,87 @@
+use derive_builder::Builder;
+use rand::Rng;
+
+use crate::direction::Cardinal;
+
+use super::{Location, MazeAlgorithm, MazeGrid};
+
+/// Carves a maze one row at a time, building eastward runs of cells and
+/// closing each run by opening a passage north from a random cell in it.
+///
+/// This produces mazes with an unbroken corridor along the northern edge.
+#[derive(Debug, Clone, Copy, PartialEq, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct Sidewinder {
+    /// The probability of closing the current run at each cell.
+    #[builder(default = "0.5")]
+    close_chance: f64,
+}
+
+impl Default for Sidewinder {
+    fn default() -> Self {
+        Self { close_chance: 0.5 }
+    }
+}
+
+impl SidewinderBuilder {
+    fn validate(&self) -> Result<(), String> {
+        if let Some(chance) = self.close_chance {
+            if !(0.0..=1.0).contains(&chance) {
+                return Err("close_chance must be between 0 and 1.".into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Sidewinder {
+    pub fn builder() -> SidewinderBuilder {
+        SidewinderBuilder::default()
+    }
+}
+
+impl MazeAlgorithm for Sidewinder {
+    fn generate<R: Rng + ?Sized>(&self, grid: &mut MazeGrid, rng: &mut R) {
+        grid.clear();
+
+        for row in 0..grid.height {
+            let mut run_start = 0;
+
+            for col in 0..grid.width {
+                let loc = Location { row, col };
+                let at_east = col + 1 == grid.width;
+
+                // the first row can only be a single corridor
+                let close = at_east || (row > 0 && rng.gen_bool(self.close_chance));
+
+                if close {
+                    if row > 0 {
+                        let chosen = Location {
+                            row,
+                            col: rng.gen_range(run_start..=col),
+                        };
+                        grid.carve(&chosen, Cardinal::North);
+                    }
+                    run_start = col + 1;
+                } else {
+                    grid.carve(&loc, Cardinal::East);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::maze::tests::check_perfect;
+
+    use super::*;
+
+    #[test]
+    fn perfect() {
+        check_perfect(Sidewinder::default());
+        check_perfect(Sidewinder::builder().close_chance(0.1).build().unwrap());
+        assert!(Sidewinder::builder().close_chance(2.0).build().is_err());
+    }
+}
@@ -0,0 +1,447 @@
+use std::{collections::VecDeque, fmt::Debug, hash::Hash};
+
+use thiserror::Error;
+
+use crate::direction::{Cardinal, HorizHexDir, VertHexDir};
+
+use super::Location;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum MaskError {
+    #[error("The mask does not enable any cells.")]
+    Empty,
+
+    #[error("Rows of the mask have inconsistent width.")]
+    InconsistentWidth,
+
+    #[error("The enabled cells of the mask are not connected.")]
+    Disconnected,
+}
+
+/// Describes the shape of the cells of a [MazeGrid] and how they connect to
+/// one another.
+///
+/// Every topology addresses cells by [Location] on a rectangular grid of rows
+/// and columns, but decides which locations are adjacent, and in which
+/// directions. Passages are stored as bitmasks, so every direction must map to
+/// a distinct bit of a `u8`.
+///
+/// [MazeGrid]: super::MazeGrid
+pub trait Topology: Debug + Clone {
+    /// The directions in which a cell can have passages.
+    type Dir: Debug + Copy + Eq + Hash + 'static;
+
+    /// Every direction, in a fixed order.
+    const DIRECTIONS: &'static [Self::Dir];
+
+    /// The bit used to record a passage in the direction `dir`.
+    fn bit(dir: Self::Dir) -> u8;
+
+    /// The direction opposite `dir`.
+    fn opposite(dir: Self::Dir) -> Self::Dir;
+
+    /// The location adjacent to `loc` in the direction `dir`, if it has a
+    /// non-negative row and column. This does not check the dimensions of any
+    /// particular grid.
+    fn neighbor(&self, loc: &Location, dir: Self::Dir) -> Option<Location>;
+
+    /// Returns `true` if `loc` is part of the maze. Defaults to every location.
+    fn is_enabled(&self, _loc: &Location) -> bool {
+        true
+    }
+}
+
+fn offset(loc: &Location, dr: i64, dc: i64) -> Option<Location> {
+    Some(Location {
+        row: loc.row.checked_add_signed(dr as isize)?,
+        col: loc.col.checked_add_signed(dc as isize)?,
+    })
+}
+
+/// Square cells, with passages in the [Cardinal] directions.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Square;
+
+impl Topology for Square {
+    type Dir = Cardinal;
+
+    const DIRECTIONS: &'static [Cardinal] = &[
+        Cardinal::North,
+        Cardinal::East,
+        Cardinal::South,
+        Cardinal::West,
+    ];
+
+    fn bit(dir: Cardinal) -> u8 {
+        dir as u8
+    }
+
+    fn opposite(dir: Cardinal) -> Cardinal {
+        dir.opposite()
+    }
+
+    fn neighbor(&self, loc: &Location, dir: Cardinal) -> Option<Location> {
+        match dir {
+            Cardinal::North => offset(loc, -1, 0),
+            Cardinal::East => offset(loc, 0, 1),
+            Cardinal::South => offset(loc, 1, 0),
+            Cardinal::West => offset(loc, 0, -1),
+        }
+    }
+}
+
+/// Hexagonal cells with flat edges to the north and south, with passages in
+/// the [HorizHexDir] directions.
+///
+/// Cells in the same column are stacked vertically, and every odd column is
+/// shifted down by half a cell.
+///
+/// ```text
+///  ___     ___
+/// /0,0\___/0,2\
+/// \___/0,1\___/
+/// /1,0\___/1,2\
+/// \___/1,1\___/
+///     \___/
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HorizHex;
+
+impl Topology for HorizHex {
+    type Dir = HorizHexDir;
+
+    const DIRECTIONS: &'static [HorizHexDir] = &[
+        HorizHexDir::North,
+        HorizHexDir::NorthEast,
+        HorizHexDir::SouthEast,
+        HorizHexDir::South,
+        HorizHexDir::SouthWest,
+        HorizHexDir::NorthWest,
+    ];
+
+    fn bit(dir: HorizHexDir) -> u8 {
+        dir as u8
+    }
+
+    fn opposite(dir: HorizHexDir) -> HorizHexDir {
+        dir.opposite()
+    }
+
+    fn neighbor(&self, loc: &Location, dir: HorizHexDir) -> Option<Location> {
+        // odd columns sit lower, so their diagonal neighbors are a row lower
+        let shift = (loc.col % 2) as i64;
+        match dir {
+            HorizHexDir::North => offset(loc, -1, 0),
+            HorizHexDir::South => offset(loc, 1, 0),
+            HorizHexDir::NorthEast => offset(loc, shift - 1, 1),
+            HorizHexDir::NorthWest => offset(loc, shift - 1, -1),
+            HorizHexDir::SouthEast => offset(loc, shift, 1),
+            HorizHexDir::SouthWest => offset(loc, shift, -1),
+        }
+    }
+}
+
+/// Hexagonal cells with flat edges to the east and west, with passages in the
+/// [VertHexDir] directions.
+///
+/// Cells in the same row are side by side, and every odd row is shifted right
+/// by half a cell.
+///
+/// ```text
+///  / \ / \ / \
+/// |0,0|0,1|0,2|
+///  \ / \ / \ / \
+///   |1,0|1,1|1,2|
+///    \ / \ / \ /
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct VertHex;
+
+impl Topology for VertHex {
+    type Dir = VertHexDir;
+
+    const DIRECTIONS: &'static [VertHexDir] = &[
+        VertHexDir::NorthEast,
+        VertHexDir::East,
+        VertHexDir::SouthEast,
+        VertHexDir::SouthWest,
+        VertHexDir::West,
+        VertHexDir::NorthWest,
+    ];
+
+    fn bit(dir: VertHexDir) -> u8 {
+        dir as u8
+    }
+
+    fn opposite(dir: VertHexDir) -> VertHexDir {
+        dir.opposite()
+    }
+
+    fn neighbor(&self, loc: &Location, dir: VertHexDir) -> Option<Location> {
+        // odd rows sit further right, so their diagonal neighbors are a column
+        // further right
+        let shift = (loc.row % 2) as i64;
+        match dir {
+            VertHexDir::East => offset(loc, 0, 1),
+            VertHexDir::West => offset(loc, 0, -1),
+            VertHexDir::NorthEast => offset(loc, -1, shift),
+            VertHexDir::NorthWest => offset(loc, -1, shift - 1),
+            VertHexDir::SouthEast => offset(loc, 1, shift),
+            VertHexDir::SouthWest => offset(loc, 1, shift - 1),
+        }
+    }
+}
+
+/// Restricts another topology to an irregular shape, by disabling every cell
+/// not enabled by a mask.
+///
+/// The enabled cells must be connected to one another, so that a maze can
+/// reach all of them. Locations beyond the dimensions of the mask are
+/// disabled, while a grid smaller than the mask would crop it, so use
+/// [MazeGrid::masked] to make a grid that fits the mask.
+///
+/// # Examples
+/// ```
+/// use rand::thread_rng;
+/// use proliferatr::maze::{Masked, MazeGrid, RecursiveBacktracker, Square};
+///
+/// let pattern = [
+///     "#....#",
+///     "......",
+///     "......",
+///     "#....#",
+/// ]
+/// .join("\n");
+///
+/// let mask = Masked::from_pattern(Square, &pattern).unwrap();
+/// let grid = MazeGrid::generate_masked(mask, &RecursiveBacktracker, &mut thread_rng());
+///
+/// assert!(grid.is_perfect());
+/// assert_eq!(grid.cell_count(), 20);
+/// assert_eq!(grid.get(&(0, 0).into()), None);
+/// ```
+///
+/// [MazeGrid::masked]: super::MazeGrid::masked
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Masked<T> {
+    inner: T,
+    enabled: Vec<Vec<bool>>,
+}
+
+impl<T: Topology> Masked<T> {
+    /// Restrict the `inner` topology to the cells that are `true` in the
+    /// `enabled` mask, which is indexed by row, then column.
+    pub fn new(inner: T, enabled: Vec<Vec<bool>>) -> Result<Self, MaskError> {
+        let width = enabled.first().map(|r| r.len()).unwrap_or(0);
+        if enabled.iter().any(|r| r.len() != width) {
+            return Err(MaskError::InconsistentWidth);
+        }
+
+        let mask = Self { inner, enabled };
+
+        let mut cells = (0..mask.height())
+            .flat_map(|row| (0..width).map(move |col| Location { row, col }))
+            .filter(|l| mask.is_enabled(l));
+        let Some(start) = cells.next() else {
+            return Err(MaskError::Empty);
+        };
+        let total = cells.count() + 1;
+
+        let mut seen = vec![vec![false; width]; mask.height()];
+        seen[start.row][start.col] = true;
+        let mut queue = VecDeque::from([start]);
+        let mut reached = 1;
+
+        while let Some(loc) = queue.pop_front() {
+            for dir in T::DIRECTIONS {
+                if let Some(other) = mask.neighbor(&loc, *dir) {
+                    if mask.is_enabled(&other) && !seen[other.row][other.col] {
+                        seen[other.row][other.col] = true;
+                        reached += 1;
+                        queue.push_back(other);
+                    }
+                }
+            }
+        }
+
+        if reached != total {
+            return Err(MaskError::Disconnected);
+        }
+
+        Ok(mask)
+    }
+
+    /// Restrict the `inner` topology using a pattern with one line per row,
+    /// where `#` disables a cell and any other character enables it.
+    pub fn from_pattern(inner: T, pattern: &str) -> Result<Self, MaskError> {
+        Self::new(
+            inner,
+            pattern
+                .lines()
+                .map(|line| line.chars().map(|ch| ch != '#').collect())
+                .collect(),
+        )
+    }
+
+    /// The number of columns in the mask.
+    pub fn width(&self) -> usize {
+        self.enabled.first().map(|r| r.len()).unwrap_or(0)
+    }
+
+    /// The number of rows in the mask.
+    pub fn height(&self) -> usize {
+        self.enabled.len()
+    }
+
+    /// The topology being restricted.
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+}
+
+impl<T: Topology> Topology for Masked<T> {
+    type Dir = T::Dir;
+
+    const DIRECTIONS: &'static [T::Dir] = T::DIRECTIONS;
+
+    fn bit(dir: T::Dir) -> u8 {
+        T::bit(dir)
+    }
+
+    fn opposite(dir: T::Dir) -> T::Dir {
+        T::opposite(dir)
+    }
+
+    fn neighbor(&self, loc: &Location, dir: T::Dir) -> Option<Location> {
+        self.inner.neighbor(loc, dir)
+    }
+
+    fn is_enabled(&self, loc: &Location) -> bool {
+        self.enabled
+            .get(loc.row)
+            .and_then(|r| r.get(loc.col))
+            .copied()
+            .unwrap_or(false)
+            && self.inner.is_enabled(loc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::maze::{
+        AldousBroder, GrowingTree, HuntAndKill, Kruskal, MazeAlgorithm, MazeGrid, Prim,
+        RecursiveBacktracker, Wilsons,
+    };
+
+    use super::*;
+
+    fn check_adjacency<T: Topology>(topology: T) {
+        for row in 1..5 {
+            for col in 1..5 {
+                let loc = Location { row, col };
+                for dir in T::DIRECTIONS {
+                    let other = topology.neighbor(&loc, *dir).unwrap();
+                    assert_ne!(other, loc);
+                    assert_eq!(topology.neighbor(&other, T::opposite(*dir)), Some(loc));
+                }
+            }
+        }
+
+        let bits: u8 = T::DIRECTIONS.iter().map(|d| T::bit(*d)).sum();
+        assert_eq!(bits.count_ones() as usize, T::DIRECTIONS.len());
+    }
+
+    fn check_algorithms<T: Topology>(topology: T, width: usize, height: usize) {
+        fn check<T: Topology, A: MazeAlgorithm<T> + Debug>(
+            algorithm: A,
+            topology: &T,
+            width: usize,
+            height: usize,
+        ) {
+            for seed in 0..3 {
+                let mut rng = StdRng::seed_from_u64(seed);
+                let grid =
+                    MazeGrid::generate_with(topology.clone(), width, height, &algorithm, &mut rng);
+                assert!(grid.is_perfect(), "{algorithm:?} seed {seed}");
+            }
+        }
+
+        check(AldousBroder, &topology, width, height);
+        check(RecursiveBacktracker, &topology, width, height);
+        check(Wilsons, &topology, width, height);
+        check(Kruskal, &topology, width, height);
+        check(Prim, &topology, width, height);
+        check(HuntAndKill, &topology, width, height);
+        check(GrowingTree::default(), &topology, width, height);
+    }
+
+    #[test]
+    fn adjacency() {
+        check_adjacency(Square);
+        check_adjacency(HorizHex);
+        check_adjacency(VertHex);
+    }
+
+    #[test]
+    fn hex_mazes() {
+        check_algorithms(HorizHex, 9, 7);
+        check_algorithms(VertHex, 9, 7);
+        check_algorithms(VertHex, 1, 5);
+    }
+
+    #[test]
+    fn masked_mazes() {
+        let pattern = ["..#..", ".....", "##.##", "....."].join("\n");
+
+        check_algorithms(Masked::from_pattern(Square, &pattern).unwrap(), 5, 4);
+        check_algorithms(Masked::from_pattern(VertHex, &pattern).unwrap(), 5, 4);
+
+        // larger than the mask
+        let mask = Masked::from_pattern(HorizHex, &pattern).unwrap();
+        let grid = MazeGrid::generate_with(mask, 8, 8, &Wilsons, &mut StdRng::seed_from_u64(1));
+        assert!(grid.is_perfect());
+        assert_eq!(grid.cell_count(), 15);
+        assert!(!grid.contains(&(7, 7).into()));
+    }
+
+    #[test]
+    fn grid_fits_mask() {
+        // cropping this to a single row would split it in two
+        let mask = Masked::from_pattern(Square, "..#..\n.....").unwrap();
+
+        let grid = MazeGrid::masked(mask.clone());
+        assert_eq!((grid.width, grid.height), (5, 2));
+
+        for seed in 0..10 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            for grid in [
+                MazeGrid::generate_masked(mask.clone(), &AldousBroder, &mut rng),
+                MazeGrid::generate_masked(mask.clone(), &Wilsons, &mut rng),
+            ] {
+                assert!(grid.is_perfect());
+                assert_eq!(grid.cell_count(), 9);
+            }
+        }
+    }
+
+    #[test]
+    fn mask_errors() {
+        assert_eq!(
+            Masked::from_pattern(Square, "##\n##"),
+            Err(MaskError::Empty)
+        );
+        assert_eq!(
+            Masked::from_pattern(Square, "..\n..."),
+            Err(MaskError::InconsistentWidth)
+        );
+        assert_eq!(
+            Masked::from_pattern(Square, "#.\n.#"),
+            Err(MaskError::Disconnected)
+        );
+
+        // diagonal cells are adjacent in a hex grid
+        assert!(Masked::from_pattern(VertHex, "#.\n.#").is_ok());
+    }
+}
@@ -0,0 +1,70 @@
+use rand::{
+    seq::{IteratorRandom, SliceRandom},
+    Rng,
+};
+
+use super::{MazeAlgorithm, MazeGrid, Topology};
+
+/// Carves a maze with loop-erased random walks: from each cell not yet in the
+/// maze, walk randomly until reaching the maze, then add the walk (minus any
+/// loops) to the maze.
+///
+/// Like [AldousBroder], this produces a uniformly random spanning tree, but
+/// is considerably faster.
+///
+/// [AldousBroder]: super::AldousBroder
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Wilsons;
+
+impl<T: Topology> MazeAlgorithm<T> for Wilsons {
+    fn generate<R: Rng + ?Sized>(&self, grid: &mut MazeGrid<T>, rng: &mut R) {
+        grid.clear();
+        if grid.cell_count() == 0 {
+            return;
+        }
+
+        let mut in_maze = vec![false; grid.size()];
+        let first = grid.random_cell(rng);
+        in_maze[grid.index(&first)] = true;
+
+        // the direction the walk last left each cell in, which erases loops
+        // by overwriting the exit whenever a cell is revisited
+        let mut exits: Vec<Option<T::Dir>> = vec![None; grid.size()];
+
+        let mut order: Vec<_> = grid.locations().collect();
+        order.shuffle(rng);
+
+        for start in order {
+            if in_maze[grid.index(&start)] {
+                continue;
+            }
+
+            let mut cell = start;
+            while !in_maze[grid.index(&cell)] {
+                let (dir, next, _) = grid.neighbors(&cell).choose(rng).unwrap();
+                exits[grid.index(&cell)] = Some(dir);
+                cell = next;
+            }
+
+            let mut cell = start;
+            while !in_maze[grid.index(&cell)] {
+                let idx = grid.index(&cell);
+                in_maze[idx] = true;
+                let dir = exits[idx].unwrap();
+                cell = grid.carve(&cell, dir).unwrap();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::maze::tests::check_perfect;
+
+    use super::*;
+
+    #[test]
+    fn perfect() {
+        check_perfect(Wilsons);
+    }
+}
@@ -1,10 +1,11 @@
-use std::collections::VecDeque;
+use std::{collections::VecDeque, ops::Deref};
 
+use itertools::{Itertools, MinMaxResult};
 use thiserror::Error;
 
 use crate::point::Point;
 
-use super::{PathError, PointPath};
+use super::{PathError, PathMutator, PointPath, ValidationError};
 
 #[derive(Debug, Clone, Error)]
 pub enum ClosedPathError {
@@ -12,13 +13,28 @@ pub enum ClosedPathError {
     InvalidDimension { width: usize, height: usize },
 }
 
+/// The winding order of a [ClosedPath], with positive y treated as North.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Winding {
+    Clockwise,
+    CounterClockwise,
+}
+
+/// The location of a [Point] relative to a [ClosedPath].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PointLocation {
+    Inside,
+    Boundary,
+    Outside,
+}
+
 /// A closed (loop) latice path in a 2D coordinate plane.
 ///
 /// For convenience, the first and last points in this path are identical.
 ///
 /// Note: there is no internal mechanism for preventing you from manually
 /// breaking the loop or manually altering the path such that is is no longer
-/// a latice path.
+/// a latice path. See [ValidatedClosedPath] for a wrapper that does.
 #[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct ClosedPath {
     points: VecDeque<Point>,
@@ -83,6 +99,196 @@ impl ClosedPath {
 
         Ok(Self { points: raw })
     }
+
+    /// Twice the signed area of the polygon described by this path, via the
+    /// shoelace formula. Negative for clockwise winding order.
+    fn signed_area_x2(&self) -> i64 {
+        self.points
+            .iter()
+            .tuple_windows()
+            .map(|(a, b)| a.x * b.y - b.x * a.y)
+            .sum()
+    }
+
+    /// The area enclosed by this path.
+    ///
+    /// Because this is a latice path, the area is always a whole number.
+    ///
+    /// # Examples
+    /// ```
+    /// use proliferatr::path::ClosedPath;
+    ///
+    /// // a 10 x 15 grid of points encloses a 9 x 14 region
+    /// let p = ClosedPath::rect_path(10, 15).unwrap();
+    /// assert_eq!(p.area(), 9 * 14);
+    /// ```
+    pub fn area(&self) -> i64 {
+        self.signed_area_x2().abs() / 2
+    }
+
+    /// The total length of the segments of this path.
+    ///
+    /// For a latice path, this is also the number of latice points on the
+    /// boundary.
+    ///
+    /// # Examples
+    /// ```
+    /// use proliferatr::path::ClosedPath;
+    ///
+    /// let p = ClosedPath::rect_path(10, 15).unwrap();
+    /// assert_eq!(p.perimeter(), 9 * 2 + 14 * 2);
+    /// ```
+    pub fn perimeter(&self) -> i64 {
+        self.points
+            .iter()
+            .tuple_windows()
+            .map(|(a, b)| a.manhattan_distance(b))
+            .sum()
+    }
+
+    /// The number of latice points strictly inside this path, via Pick's
+    /// theorem.
+    ///
+    /// # Examples
+    /// ```
+    /// use proliferatr::path::ClosedPath;
+    ///
+    /// let p = ClosedPath::rect_path(10, 15).unwrap();
+    /// assert_eq!(p.interior_points(), 8 * 13);
+    /// ```
+    pub fn interior_points(&self) -> i64 {
+        if self.points.len() < 2 {
+            return 0;
+        }
+
+        // A = I + B/2 - 1
+        self.area() - self.perimeter() / 2 + 1
+    }
+
+    /// The winding order of this path, if it encloses a non-zero area.
+    ///
+    /// # Examples
+    /// ```
+    /// use proliferatr::path::{
+    ///     BothAxisReflector, ClosedPath, PathMutator, Winding, XAxisReflector,
+    /// };
+    ///
+    /// let mut p = ClosedPath::rect_path(10, 15).unwrap();
+    /// assert_eq!(p.winding(), Some(Winding::Clockwise));
+    ///
+    /// // a single reflection reverses the winding order
+    /// XAxisReflector::default().mutate(&mut p);
+    /// assert_eq!(p.winding(), Some(Winding::CounterClockwise));
+    ///
+    /// // but two will not
+    /// BothAxisReflector::default().mutate(&mut p);
+    /// assert_eq!(p.winding(), Some(Winding::CounterClockwise));
+    /// ```
+    pub fn winding(&self) -> Option<Winding> {
+        match self.signed_area_x2() {
+            0 => None,
+            v if v < 0 => Some(Winding::Clockwise),
+            _ => Some(Winding::CounterClockwise),
+        }
+    }
+
+    /// Determine if the specified `point` is inside, outside, or on the
+    /// boundary of this path.
+    ///
+    /// Unlike [Self::enclosed_points], this does not require the path to be a
+    /// latice path.
+    ///
+    /// # Examples
+    /// ```
+    /// use proliferatr::path::{ClosedPath, PointLocation};
+    ///
+    /// let p = ClosedPath::rect_path(3, 3).unwrap();
+    /// assert_eq!(p.locate(&(1, 1).into()), PointLocation::Inside);
+    /// assert_eq!(p.locate(&(2, 1).into()), PointLocation::Boundary);
+    /// assert_eq!(p.locate(&(3, 1).into()), PointLocation::Outside);
+    /// ```
+    pub fn locate(&self, point: &Point) -> PointLocation {
+        let mut inside = false;
+
+        for (a, b) in self.points.iter().tuple_windows() {
+            let (ax, ay) = ((a.x - point.x) as i128, (a.y - point.y) as i128);
+            let (bx, by) = ((b.x - point.x) as i128, (b.y - point.y) as i128);
+            let cross = ax * by - bx * ay;
+
+            // collinear and between the endpoints
+            if cross == 0 && ax * bx <= 0 && ay * by <= 0 {
+                return PointLocation::Boundary;
+            }
+
+            // half-open crossing rule on a ray cast in the +x direction
+            if (ay > 0) != (by > 0) {
+                // the edge crosses to the right of the point if the sign of the
+                // cross product matches the direction the edge travels in y
+                if (cross > 0) == (by > ay) {
+                    inside = !inside;
+                }
+            }
+        }
+
+        if inside {
+            PointLocation::Inside
+        } else {
+            PointLocation::Outside
+        }
+    }
+
+    /// Get an iterator over the latice points strictly inside this path.
+    ///
+    /// This assumes the path is a latice path, and will yield exactly
+    /// [Self::interior_points] points if that is the case.
+    ///
+    /// # Examples
+    /// ```
+    /// use proliferatr::path::{ClosedPath, PointPath};
+    ///
+    /// let p = ClosedPath::rect_path(3, 4).unwrap();
+    /// let enclosed: Vec<_> = p.enclosed_points().collect();
+    /// assert_eq!(enclosed, vec![(1, 1).into(), (1, 2).into()]);
+    /// ```
+    pub fn enclosed_points(&self) -> impl Iterator<Item = Point> + '_ {
+        let (min_y, max_y) = match self.points.iter().map(|p| p.y).minmax() {
+            MinMaxResult::NoElements => (0, -1),
+            MinMaxResult::OneElement(y) => (y, y),
+            MinMaxResult::MinMax(min, max) => (min, max),
+        };
+
+        (min_y..=max_y).flat_map(move |y| self.enclosed_in_row(y).map(move |x| Point::new(x, y)))
+    }
+
+    /// The x coordinates of the enclosed points in the row at `y`.
+    fn enclosed_in_row(&self, y: i64) -> impl Iterator<Item = i64> {
+        let mut crossings = Vec::new();
+        let mut boundary = Vec::new();
+
+        for (a, b) in self.points.iter().tuple_windows() {
+            if a.x == b.x {
+                let (lo, hi) = (a.y.min(b.y), a.y.max(b.y));
+                // half-open so shared vertices are only counted once
+                if lo <= y && y < hi {
+                    crossings.push(a.x);
+                }
+
+                if lo <= y && y <= hi {
+                    boundary.push((a.x, a.x));
+                }
+            } else if a.y == y && b.y == y {
+                boundary.push((a.x.min(b.x), a.x.max(b.x)));
+            }
+        }
+
+        crossings.sort_unstable();
+
+        crossings
+            .into_iter()
+            .tuples()
+            .flat_map(|(start, end)| (start + 1)..end)
+            .filter(move |x| !boundary.iter().any(|(lo, hi)| lo <= x && x <= hi))
+    }
 }
 
 impl PointPath for ClosedPath {
@@ -121,4 +327,144 @@ impl PointPath for ClosedPath {
             *p += dxdy;
         }
     }
+
+    fn replace_points<I: Iterator<Item = Point>>(&mut self, points: I) {
+        self.points = points.collect();
+    }
+}
+
+impl FromIterator<Point> for ClosedPath {
+    /// Make a [ClosedPath] from the points as given.
+    ///
+    /// This does not close the loop or otherwise validate the points.
+    fn from_iter<T: IntoIterator<Item = Point>>(iter: T) -> Self {
+        Self {
+            points: VecDeque::from_iter(iter),
+        }
+    }
+}
+
+/// A [ClosedPath] that is guaranteed to be closed, latice, and simple.
+///
+/// This does not implement [PointPath], as that would allow arbitrary
+/// modification, but derefs to the underlying [ClosedPath] for read access.
+/// Modifications can be made via [mutate](ValidatedClosedPath::mutate), which
+/// rejects any mutation that would break the invariants.
+///
+/// # Examples
+/// ```
+/// use proliferatr::path::{
+///     ClosedPath, PathScaler, PointPath, ValidatedClosedPath, ValidationError,
+/// };
+///
+/// let p = ClosedPath::rect_path(3, 3).unwrap();
+/// let mut valid = ValidatedClosedPath::try_from(p).unwrap();
+/// assert_eq!(valid.area(), 4);
+///
+/// let mut scaler = PathScaler::builder().x_factor(2).build().unwrap();
+/// assert!(valid.mutate(&mut scaler).unwrap());
+/// assert_eq!(valid.area(), 8);
+///
+/// // not closed
+/// let p = ClosedPath::from_iter([(0, 0).into(), (0, 1).into(), (1, 1).into(), (1, 0).into()]);
+/// assert!(matches!(
+///     ValidatedClosedPath::try_from(p),
+///     Err(ValidationError::NotClosed { .. })
+/// ));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ValidatedClosedPath(ClosedPath);
+
+impl ValidatedClosedPath {
+    /// Apply the `mutator` to this path.
+    ///
+    /// If the mutated path would no longer be valid, this path is left
+    /// unchanged and the reason is returned. Otherwise, returns whether or not
+    /// the path was mutated.
+    pub fn mutate<M: PathMutator>(&mut self, mutator: &mut M) -> Result<bool, ValidationError> {
+        let mut candidate = self.0.clone();
+        let mutated = mutator.mutate(&mut candidate);
+
+        if mutated {
+            Self::validate(&candidate)?;
+            self.0 = candidate;
+        }
+
+        Ok(mutated)
+    }
+
+    /// Consume this wrapper, returning the underlying [ClosedPath].
+    pub fn into_inner(self) -> ClosedPath {
+        self.0
+    }
+
+    fn validate(path: &ClosedPath) -> Result<(), ValidationError> {
+        path.check_closed()?;
+        path.check_lattice()?;
+        path.check_simple()
+    }
+}
+
+impl TryFrom<ClosedPath> for ValidatedClosedPath {
+    type Error = ValidationError;
+
+    fn try_from(value: ClosedPath) -> Result<Self, Self::Error> {
+        Self::validate(&value)?;
+        Ok(Self(value))
+    }
+}
+
+impl Deref for ValidatedClosedPath {
+    type Target = ClosedPath;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<ValidatedClosedPath> for ClosedPath {
+    fn from(value: ValidatedClosedPath) -> Self {
+        value.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::path::{PathCondenser, PathMutator, UnitSegmentAdder};
+
+    use super::*;
+
+    #[test]
+    fn enclosed_points_agree_with_picks_theorem() {
+        let mut p = ClosedPath::rect_path(20, 20).unwrap();
+        let mut adder = UnitSegmentAdder::builder()
+            .passes(10)
+            .rng(StdRng::seed_from_u64(1234))
+            .build()
+            .unwrap();
+        adder.mutate(&mut p);
+
+        let enclosed: Vec<_> = p.enclosed_points().collect();
+        assert_eq!(enclosed.len() as i64, p.interior_points());
+
+        for point in enclosed.iter() {
+            assert_eq!(p.locate(point), PointLocation::Inside);
+        }
+
+        for point in p.points() {
+            assert_eq!(p.locate(point), PointLocation::Boundary);
+        }
+
+        // condensing doesn't change the shape
+        let area = p.area();
+        let perimeter = p.perimeter();
+        let winding = p.winding();
+        PathCondenser::default().mutate(&mut p);
+        assert_eq!(p.area(), area);
+        assert_eq!(p.perimeter(), perimeter);
+        assert_eq!(p.winding(), winding);
+        assert_eq!(p.enclosed_points().count(), enclosed.len());
+    }
 }
@@ -1,22 +1,57 @@
 use std::collections::VecDeque;
 
+use rand::Rng;
 use thiserror::Error;
 
-use crate::point::Point;
+use crate::{direction::Relative, point::Point};
 
 mod closed_path;
+mod mutator_chain;
+mod occupancy;
 mod path_condenser;
+mod path_normalizer;
+mod path_rasterizer;
 mod path_reflector;
+mod path_rotator;
 mod path_scaler;
+mod path_translator;
+mod random_polygon;
+mod segment;
+mod self_avoiding_path;
 mod unit_segment_adder;
+mod unit_segment_alterer;
+mod validation;
 
-pub use closed_path::{ClosedPath, ClosedPathError};
+pub use closed_path::{ClosedPath, ClosedPathError, PointLocation, ValidatedClosedPath, Winding};
+pub use mutator_chain::{ChainReport, MutatorChain, MutatorStep, StepReport, StopCondition};
+pub use occupancy::Occupancy;
 pub use path_condenser::{PathCondenser, PathCondenserBuilder, PathCondenserBuilderError};
+pub use path_normalizer::{PathNormalizer, PathNormalizerBuilder, PathNormalizerBuilderError};
+pub use path_rasterizer::{
+    Arrows, AsciiPipes, BoxDrawing, Glyphs, PathRasterizer, PathRasterizerBuilder,
+    PathRasterizerBuilderError, RasterizerError, Solid,
+};
 pub use path_reflector::{BothAxisReflector, XAxisReflector, YAxisReflector};
+pub use path_rotator::{
+    PathRotator, PathRotatorBuilder, PathRotatorBuilderError, Rotation, RotationCenter,
+};
 pub use path_scaler::{PathScaler, PathScalerBuilder, PathScalerBuilderError};
+pub use path_translator::{PathTranslator, PathTranslatorBuilder, PathTranslatorBuilderError};
+pub use random_polygon::{
+    PolygonShape, RandomPolygon, RandomPolygonBuilder, RandomPolygonBuilderError,
+    RandomPolygonError,
+};
+pub use segment::Segment;
+pub use self_avoiding_path::{
+    SelfAvoidingPath, SelfAvoidingPathBuilder, SelfAvoidingPathBuilderError, SelfAvoidingPathError,
+};
 pub use unit_segment_adder::{
     UnitSegmentAdder, UnitSegmentAdderBuilder, UnitSegmentAdderBuilderError,
 };
+pub use unit_segment_alterer::{
+    UnitSegmentAlterer, UnitSegmentAltererBuilder, UnitSegmentAltererBuilderError,
+};
+pub use validation::ValidationError;
 
 /// Indicates that this type describes a 2D path formed by the traversal of a
 /// collection of [Point].
@@ -48,10 +83,176 @@ pub trait PointPath {
     /// [Point] in the path.
     fn translate(&mut self, dxdy: Point);
 
+    /// Replace all of the points of this path with `points`.
+    ///
+    /// The default implementation removes and inserts points one at a time,
+    /// so implementors should override this if they can do better.
+    fn replace_points<I: Iterator<Item = Point>>(&mut self, points: I) {
+        while !self.is_empty() {
+            self.remove(self.len() - 1);
+        }
+
+        self.insert_many(0, points);
+    }
+
     /// Returns `true` if this path is empty.
     fn is_empty(&self) -> bool {
         self.len() == 0
     }
+
+    /// Returns `true` if the first and last points of this path are identical
+    /// and the path has at least three distinct points.
+    fn is_closed(&self) -> bool {
+        self.check_closed().is_ok()
+    }
+
+    /// Like [Self::is_closed], but reports why the path is not closed.
+    fn check_closed(&self) -> Result<(), ValidationError> {
+        validation::check_closed(self)
+    }
+
+    /// Returns `true` if every segment of this path is horizontal or vertical
+    /// and of non-zero length.
+    fn is_lattice(&self) -> bool {
+        self.check_lattice().is_ok()
+    }
+
+    /// Like [Self::is_lattice], but reports the index of the first offending
+    /// segment. The segment at index `i` is from point `i` to point `i + 1`.
+    fn check_lattice(&self) -> Result<(), ValidationError> {
+        validation::check_lattice(self)
+    }
+
+    /// Returns `true` if every segment of this path is horizontal or vertical
+    /// and exactly one unit long.
+    fn is_unit(&self) -> bool {
+        self.check_unit().is_ok()
+    }
+
+    /// Like [Self::is_unit], but reports the index of the first offending
+    /// segment. The segment at index `i` is from point `i` to point `i + 1`.
+    fn check_unit(&self) -> Result<(), ValidationError> {
+        validation::check_unit(self)
+    }
+
+    /// Returns `true` if no two segments of this path intersect, other than
+    /// consecutive segments meeting at their shared point (and the first and
+    /// last segments of a closed path meeting at the start).
+    ///
+    /// This works for segments of any length or orientation.
+    ///
+    /// # Examples
+    /// ```
+    /// use proliferatr::path::{Path, PointPath, ValidationError};
+    ///
+    /// let mut p = Path::from_iter([
+    ///     (0, 0).into(),
+    ///     (4, 0).into(),
+    ///     (4, 4).into(),
+    ///     (2, 4).into(),
+    /// ]);
+    /// assert!(p.is_simple());
+    ///
+    /// p.append((2, -1).into());
+    /// assert!(!p.is_simple());
+    /// assert_eq!(
+    ///     p.check_simple(),
+    ///     Err(ValidationError::SelfIntersection { first: 0, second: 3 })
+    /// );
+    /// ```
+    fn is_simple(&self) -> bool {
+        self.check_simple().is_ok()
+    }
+
+    /// Like [Self::is_simple], but reports the indices of a pair of intersecting
+    /// segments. The segment at index `i` is from point `i` to point `i + 1`.
+    fn check_simple(&self) -> Result<(), ValidationError> {
+        validation::check_simple(self)
+    }
+
+    /// Iterate over the segments of this path. The segment at index `i` is
+    /// from point `i` to point `i + 1`.
+    ///
+    /// # Examples
+    /// ```
+    /// use proliferatr::{direction::Cardinal, path::{Path, PointPath}};
+    ///
+    /// let p = Path::from_iter([(0, 0).into(), (0, 4).into(), (3, 4).into()]);
+    /// let segs: Vec<_> = p.segments().collect();
+    ///
+    /// assert_eq!(segs.len(), 2);
+    /// assert_eq!(segs[1].direction, Some(Cardinal::East));
+    /// assert_eq!(segs[1].length, 3);
+    /// ```
+    fn segments(&self) -> impl Iterator<Item = Segment> + '_ {
+        (1..self.len()).map(|i| Segment::new(*self.get(i - 1).unwrap(), *self.get(i).unwrap()))
+    }
+
+    /// Iterate over the direction of each segment of this path, where north is
+    /// [Relative::Up]. Segments that are not horizontal or vertical have no
+    /// direction.
+    fn directions(&self) -> impl Iterator<Item = Option<Relative>> + '_ {
+        self.segments().map(|s| s.relative())
+    }
+
+    /// Iterate over the turns between consecutive segments of this path. For a
+    /// closed path, this includes the turn from the last segment back onto the
+    /// first.
+    ///
+    /// Continuing straight is [Relative::Up] and reversing is
+    /// [Relative::Down]. Turns involving a segment that is not horizontal or
+    /// vertical are `None`.
+    ///
+    /// # Examples
+    /// ```
+    /// use proliferatr::{direction::Relative, path::{Path, PointPath}};
+    ///
+    /// let p = Path::from_iter([
+    ///     (0, 0).into(),
+    ///     (0, 4).into(),
+    ///     (3, 4).into(),
+    ///     (3, 6).into(),
+    /// ]);
+    /// let turns: Vec<_> = p.turns().flatten().collect();
+    ///
+    /// assert_eq!(turns, vec![Relative::Right, Relative::Left]);
+    /// ```
+    fn turns(&self) -> impl Iterator<Item = Option<Relative>> + '_ {
+        let wrap = if self.is_closed() {
+            self.segments().last().zip(self.segments().next())
+        } else {
+            None
+        };
+
+        self.segments()
+            .zip(self.segments().skip(1))
+            .chain(wrap)
+            .map(|(a, b)| a.turn_to(&b))
+    }
+
+    /// Insert the intermediate points of every segment, so that this becomes
+    /// a unit path. This is the inverse of the [PathCondenser].
+    ///
+    /// Returns `Ok(true)` if any points were inserted, and leaves the path
+    /// unmodified if it is not a lattice path.
+    fn expand_to_unit(&mut self) -> Result<bool, ValidationError> {
+        self.check_lattice()?;
+
+        let mut expanded = false;
+
+        // work backwards so that insertions don't shift the segments we have
+        // yet to visit
+        for i in (1..self.len()).rev() {
+            let seg = Segment::new(*self.get(i - 1).unwrap(), *self.get(i).unwrap());
+            if seg.length > 1 {
+                let points = seg.unit_points().unwrap().take(seg.length as usize - 1);
+                self.insert_many(i, points);
+                expanded = true;
+            }
+        }
+
+        Ok(expanded)
+    }
 }
 
 /// Path mutators mutate a given [PointPath], by optionally adding, removing,
@@ -66,10 +267,35 @@ pub trait PathMutator {
     fn mutate<P: PointPath>(&mut self, path: &mut P) -> bool;
 }
 
+/// Like [PathMutator], but for mutators that make random decisions using an
+/// RNG borrowed for each call.
+///
+/// Borrowing the RNG, rather than owning a clone of it, means that using the
+/// mutator advances the caller's stream, which keeps seeded generation
+/// reproducible.
+pub trait RandomPathMutator {
+    /// Attempt to mutate the given path using `rng`.
+    ///
+    /// Returns `true` if the path was mutated.
+    fn mutate_with<P: PointPath, R: Rng + ?Sized>(&mut self, path: &mut P, rng: &mut R) -> bool;
+}
+
 #[derive(Debug, Clone, Error)]
 pub enum PathError {
     #[error(transparent)]
     ClosedPath(#[from] ClosedPathError),
+
+    #[error(transparent)]
+    Rasterizer(#[from] RasterizerError),
+
+    #[error(transparent)]
+    Validation(#[from] ValidationError),
+
+    #[error(transparent)]
+    SelfAvoidingPath(#[from] SelfAvoidingPathError),
+
+    #[error(transparent)]
+    RandomPolygon(#[from] RandomPolygonError),
 }
 
 /// A sequence of [Point] describing a 2D path.
@@ -160,4 +386,8 @@ impl PointPath for Path {
             *p += dxdy;
         }
     }
+
+    fn replace_points<I: Iterator<Item = Point>>(&mut self, points: I) {
+        self.points = points.collect();
+    }
 }
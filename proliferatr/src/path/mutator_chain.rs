@@ -0,0 +1,302 @@
+use std::fmt;
+
+use super::{PathMutator, PointPath};
+
+/// An object-safe version of [PathMutator] for a specific [PointPath] type, so
+/// that mutators of different types can be stored together.
+trait DynPathMutator<P: PointPath> {
+    fn mutate_path(&mut self, path: &mut P) -> bool;
+}
+
+impl<P: PointPath, M: PathMutator> DynPathMutator<P> for M {
+    fn mutate_path(&mut self, path: &mut P) -> bool {
+        self.mutate(path)
+    }
+}
+
+/// Conditions under which a [MutatorStep] stops repeating.
+///
+/// Conditions are checked before every repetition of the step.
+pub enum StopCondition<P> {
+    /// Stop once the path has at least this many points.
+    LenAtLeast(usize),
+
+    /// Stop once the path has at most this many points.
+    LenAtMost(usize),
+
+    /// Stop once a repetition of the step fails to mutate the path.
+    Unchanged,
+
+    /// Stop once the function returns `true`.
+    Custom(Box<dyn Fn(&P) -> bool>),
+}
+
+impl<P> fmt::Debug for StopCondition<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LenAtLeast(n) => f.debug_tuple("LenAtLeast").field(n).finish(),
+            Self::LenAtMost(n) => f.debug_tuple("LenAtMost").field(n).finish(),
+            Self::Unchanged => f.write_str("Unchanged"),
+            Self::Custom(_) => f.write_str("Custom"),
+        }
+    }
+}
+
+impl<P: PointPath> StopCondition<P> {
+    fn is_met(&self, path: &P, last_mutated: Option<bool>) -> bool {
+        match self {
+            Self::LenAtLeast(n) => path.len() >= *n,
+            Self::LenAtMost(n) => path.len() <= *n,
+            Self::Unchanged => last_mutated == Some(false),
+            Self::Custom(f) => f(path),
+        }
+    }
+}
+
+/// A single step in a [MutatorChain]: a mutator, the maximum number of times
+/// to apply it, and the conditions under which to stop early.
+pub struct MutatorStep<P: PointPath> {
+    label: String,
+    mutator: Box<dyn DynPathMutator<P>>,
+    repeat: usize,
+    stop: Vec<StopCondition<P>>,
+}
+
+impl<P: PointPath> fmt::Debug for MutatorStep<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MutatorStep")
+            .field("label", &self.label)
+            .field("repeat", &self.repeat)
+            .field("stop", &self.stop)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<P: PointPath> MutatorStep<P> {
+    /// Make a new step that applies the `mutator` once.
+    pub fn new<M: PathMutator + 'static>(mutator: M) -> Self {
+        Self {
+            label: std::any::type_name::<M>().to_string(),
+            mutator: Box::new(mutator),
+            repeat: 1,
+            stop: Vec::new(),
+        }
+    }
+
+    /// Set the label used for this step in the [ChainReport]. Defaults to the
+    /// type name of the mutator.
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    /// Set the maximum number of times to apply the mutator.
+    pub fn repeat(mut self, repeat: usize) -> Self {
+        self.repeat = repeat;
+        self
+    }
+
+    /// Add a condition that stops this step early. The step stops when any of
+    /// its conditions are met.
+    pub fn until(mut self, condition: StopCondition<P>) -> Self {
+        self.stop.push(condition);
+        self
+    }
+
+    fn run(&mut self, path: &mut P) -> StepReport {
+        let len_before = path.len();
+        let mut runs = 0;
+        let mut mutations = 0;
+        let mut stopped_early = false;
+        let mut last_mutated = None;
+
+        while runs < self.repeat {
+            if self.stop.iter().any(|c| c.is_met(path, last_mutated)) {
+                stopped_early = true;
+                break;
+            }
+
+            let mutated = self.mutator.mutate_path(path);
+            runs += 1;
+            if mutated {
+                mutations += 1;
+            }
+            last_mutated = Some(mutated);
+        }
+
+        StepReport {
+            label: self.label.clone(),
+            runs,
+            mutations,
+            stopped_early,
+            len_before,
+            len_after: path.len(),
+        }
+    }
+}
+
+/// Statistics for a single step of a [MutatorChain] run.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StepReport {
+    /// The label of the step.
+    pub label: String,
+
+    /// The number of times the mutator was applied.
+    pub runs: usize,
+
+    /// The number of times the mutator reported that it mutated the path.
+    pub mutations: usize,
+
+    /// If the step stopped because one of its conditions was met.
+    pub stopped_early: bool,
+
+    /// The number of points in the path before this step.
+    pub len_before: usize,
+
+    /// The number of points in the path after this step.
+    pub len_after: usize,
+}
+
+/// Statistics for a [MutatorChain] run.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct ChainReport {
+    pub steps: Vec<StepReport>,
+}
+
+impl ChainReport {
+    /// Returns `true` if any step mutated the path.
+    pub fn mutated(&self) -> bool {
+        self.steps.iter().any(|s| s.mutations > 0)
+    }
+}
+
+/// Applies a sequence of [PathMutator] to a path, in order.
+///
+/// Because [PathMutator] is generic over the path type, a chain is specific to
+/// a single [PointPath] type.
+///
+/// # Examples
+/// ```
+/// use rand::thread_rng;
+/// use proliferatr::path::{
+///     ClosedPath, MutatorChain, MutatorStep, PathCondenser, PathScaler, PointPath,
+///     StopCondition, UnitSegmentAdder,
+/// };
+///
+/// let adder = UnitSegmentAdder::builder()
+///     .rng(thread_rng())
+///     .build()
+///     .unwrap();
+///
+/// let scaler = PathScaler::builder()
+///     .x_factor(2)
+///     .y_factor(3)
+///     .build()
+///     .unwrap();
+///
+/// let mut chain = MutatorChain::default()
+///     .step(
+///         MutatorStep::new(adder)
+///             .label("grow")
+///             .repeat(100)
+///             .until(StopCondition::LenAtLeast(100)),
+///     )
+///     .step(MutatorStep::new(PathCondenser::default()))
+///     .step(MutatorStep::new(scaler));
+///
+/// let mut p = ClosedPath::rect_path(10, 10).unwrap();
+/// let report = chain.run(&mut p);
+///
+/// assert_eq!(report.steps.len(), 3);
+/// assert_eq!(report.steps[0].label, "grow");
+/// assert!(report.steps[0].len_after >= 100);
+/// assert_eq!(report.steps[2].mutations, 1);
+/// ```
+pub struct MutatorChain<P: PointPath> {
+    steps: Vec<MutatorStep<P>>,
+}
+
+impl<P: PointPath> Default for MutatorChain<P> {
+    fn default() -> Self {
+        Self { steps: Vec::new() }
+    }
+}
+
+impl<P: PointPath> fmt::Debug for MutatorChain<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MutatorChain")
+            .field("steps", &self.steps)
+            .finish()
+    }
+}
+
+impl<P: PointPath> MutatorChain<P> {
+    /// Append a step to this chain.
+    pub fn step(mut self, step: MutatorStep<P>) -> Self {
+        self.steps.push(step);
+        self
+    }
+
+    /// Append a step to this chain.
+    pub fn push(&mut self, step: MutatorStep<P>) {
+        self.steps.push(step);
+    }
+
+    /// Run every step of this chain against the `path`, in order.
+    pub fn run(&mut self, path: &mut P) -> ChainReport {
+        ChainReport {
+            steps: self.steps.iter_mut().map(|s| s.run(path)).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::path::{ClosedPath, PathCondenser, PathScaler, XAxisReflector};
+
+    use super::*;
+
+    #[test]
+    fn stop_conditions() {
+        let mut chain = MutatorChain::default()
+            .step(
+                MutatorStep::new(PathCondenser::builder().limit(1).build().unwrap())
+                    .repeat(1000)
+                    .until(StopCondition::LenAtMost(20)),
+            )
+            .step(
+                MutatorStep::new(PathCondenser::default())
+                    .repeat(1000)
+                    .until(StopCondition::Unchanged),
+            )
+            .step(
+                MutatorStep::new(XAxisReflector::default())
+                    .repeat(1000)
+                    .until(StopCondition::Custom(Box::new(|p: &ClosedPath| {
+                        p.points().any(|pt| pt.y < 0)
+                    }))),
+            )
+            .step(MutatorStep::new(PathScaler::default()).repeat(3));
+
+        let mut p = ClosedPath::rect_path(10, 10).unwrap();
+        let report = chain.run(&mut p);
+
+        let s = &report.steps[0];
+        assert_eq!((s.runs, s.mutations, s.stopped_early), (17, 17, true));
+        assert_eq!((s.len_before, s.len_after), (37, 20));
+
+        // one run to finish condensing, one run to detect no change
+        let s = &report.steps[1];
+        assert_eq!((s.runs, s.mutations, s.stopped_early), (2, 1, true));
+        assert_eq!(s.len_after, 5);
+
+        let s = &report.steps[2];
+        assert_eq!((s.runs, s.mutations, s.stopped_early), (1, 1, true));
+
+        // the default scaler never mutates
+        let s = &report.steps[3];
+        assert_eq!((s.runs, s.mutations, s.stopped_early), (3, 0, false));
+
+        assert!(report.mutated());
+    }
+}
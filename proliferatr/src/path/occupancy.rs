@@ -0,0 +1,146 @@
+use rustc_hash::FxHashSet;
+
+use crate::{bound::Bound2D, point::Point};
+
+/// A set of occupied points, used to keep paths from crossing themselves.
+///
+/// Points within the (optional) bounds are stored in a dense bitset, which is
+/// considerably faster than hashing for paths of tens of thousands of points.
+/// Any points outside of the bounds fall back to a hash set.
+///
+/// # Examples
+/// ```
+/// use proliferatr::{bound::Bound2D, path::Occupancy, point::Point};
+///
+/// let bounds = Bound2D { min_x: -5, max_x: 5, min_y: -5, max_y: 5 };
+/// let mut occupied = Occupancy::new(Some(bounds));
+///
+/// assert!(occupied.insert(Point::new(1, 2)));
+/// assert!(!occupied.insert(Point::new(1, 2)));
+/// assert!(occupied.insert(Point::new(100, 2)));
+///
+/// assert!(occupied.contains(&Point::new(1, 2)));
+/// assert!(occupied.contains(&Point::new(100, 2)));
+/// assert_eq!(occupied.len(), 2);
+///
+/// assert!(occupied.remove(&Point::new(1, 2)));
+/// assert!(!occupied.contains(&Point::new(1, 2)));
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Occupancy {
+    bounds: Option<Bound2D>,
+    width: usize,
+    bits: Vec<u64>,
+    overflow: FxHashSet<Point>,
+    len: usize,
+}
+
+impl Occupancy {
+    /// Make a new, empty set, with dense storage for the points within
+    /// `bounds`.
+    pub fn new(bounds: Option<Bound2D>) -> Self {
+        let (width, bits) = match bounds {
+            Some(b) if b.max_x >= b.min_x && b.max_y >= b.min_y => {
+                let width = (b.max_x - b.min_x + 1) as usize;
+                let height = (b.max_y - b.min_y + 1) as usize;
+                (width, vec![0; (width * height).div_ceil(64)])
+            }
+            _ => (0, Vec::new()),
+        };
+
+        Self {
+            bounds: bounds.filter(|_| width > 0),
+            width,
+            bits,
+            overflow: FxHashSet::default(),
+            len: 0,
+        }
+    }
+
+    fn index(&self, point: &Point) -> Option<usize> {
+        let b = self.bounds.as_ref()?;
+        if !b.contains(point) {
+            return None;
+        }
+
+        let p = b.normalize(point);
+        Some(p.y as usize * self.width + p.x as usize)
+    }
+
+    /// The number of occupied points.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if there are no occupied points.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns `true` if the `point` is occupied.
+    pub fn contains(&self, point: &Point) -> bool {
+        match self.index(point) {
+            Some(idx) => self.bits[idx / 64] & (1 << (idx % 64)) != 0,
+            None => self.overflow.contains(point),
+        }
+    }
+
+    /// Mark the `point` as occupied.
+    ///
+    /// Returns `true` if the point was not already occupied.
+    pub fn insert(&mut self, point: Point) -> bool {
+        let inserted = match self.index(&point) {
+            Some(idx) => {
+                let word = &mut self.bits[idx / 64];
+                let mask = 1 << (idx % 64);
+                let was_set = *word & mask != 0;
+                *word |= mask;
+                !was_set
+            }
+            None => self.overflow.insert(point),
+        };
+
+        if inserted {
+            self.len += 1;
+        }
+
+        inserted
+    }
+
+    /// Mark the `point` as unoccupied.
+    ///
+    /// Returns `true` if the point was occupied.
+    pub fn remove(&mut self, point: &Point) -> bool {
+        let removed = match self.index(point) {
+            Some(idx) => {
+                let word = &mut self.bits[idx / 64];
+                let mask = 1 << (idx % 64);
+                let was_set = *word & mask != 0;
+                *word &= !mask;
+                was_set
+            }
+            None => self.overflow.remove(point),
+        };
+
+        if removed {
+            self.len -= 1;
+        }
+
+        removed
+    }
+
+    /// Mark every point as unoccupied.
+    pub fn clear(&mut self) {
+        self.bits.fill(0);
+        self.overflow.clear();
+        self.len = 0;
+    }
+}
+
+impl Extend<Point> for Occupancy {
+    fn extend<T: IntoIterator<Item = Point>>(&mut self, iter: T) {
+        for p in iter {
+            self.insert(p);
+        }
+    }
+}
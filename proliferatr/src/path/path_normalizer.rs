@@ -0,0 +1,77 @@
+use derive_builder::Builder;
+
+use crate::{bound::Bound2D, point::Point};
+
+use super::{PathMutator, PointPath};
+
+/// A [PathMutator] that moves a [PointPath] to a standard location.
+///
+/// Without a target, the path is moved such that the minimum `x` and `y` of
+/// its bounds are at the origin. With a target [Bound2D], the bounds of the
+/// path are centered in the target, rounding toward the negative x and y
+/// directions. Paths larger than the target will extend past its edges.
+///
+/// # Examples
+/// ```
+/// use proliferatr::{
+///     bound::Bound2D,
+///     path::{ClosedPath, PathMutator, PathNormalizer, PointPath},
+/// };
+///
+/// let mut p = ClosedPath::rect_path(3, 3).unwrap();
+/// p.translate((-7, 4).into());
+///
+/// let mut normalizer = PathNormalizer::default();
+/// normalizer.mutate(&mut p);
+/// assert_eq!(p.get(0).copied(), Some((0, 0).into()));
+///
+/// let mut normalizer = PathNormalizer::builder()
+///     .target(Bound2D { min_x: 0, max_x: 9, min_y: 0, max_y: 9 })
+///     .build()
+///     .unwrap();
+/// normalizer.mutate(&mut p);
+/// assert_eq!(p.get(0).copied(), Some((3, 3).into()));
+/// assert_eq!(p.get(4).copied(), Some((5, 5).into()));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Builder)]
+pub struct PathNormalizer {
+    #[builder(default, setter(into, strip_option))]
+    target: Option<Bound2D>,
+}
+
+impl PathNormalizer {
+    pub fn builder() -> PathNormalizerBuilder {
+        PathNormalizerBuilder::default()
+    }
+}
+
+impl PathMutator for PathNormalizer {
+    fn mutate<P: PointPath>(&mut self, path: &mut P) -> bool {
+        if path.is_empty() {
+            return false;
+        }
+
+        let bounds = Bound2D::derive_from(path.points());
+
+        let dxdy = match self.target {
+            Some(target) => {
+                let slack_x = (target.max_x - target.min_x) - (bounds.max_x - bounds.min_x);
+                let slack_y = (target.max_y - target.min_y) - (bounds.max_y - bounds.min_y);
+
+                Point::new(
+                    target.min_x + slack_x.div_euclid(2) - bounds.min_x,
+                    target.min_y + slack_y.div_euclid(2) - bounds.min_y,
+                )
+            }
+            None => Point::new(-bounds.min_x, -bounds.min_y),
+        };
+
+        if dxdy == Point::default() {
+            return false;
+        }
+
+        path.translate(dxdy);
+
+        true
+    }
+}
@@ -0,0 +1,427 @@
+use derive_builder::Builder;
+use rustc_hash::FxHashMap;
+use thiserror::Error;
+
+use crate::{
+    bound::Bound2D,
+    direction::Cardinal,
+    grid::{CharGrid, Grid},
+    point::Point,
+};
+
+use super::{PathError, PointPath};
+
+#[derive(Debug, Clone, Error)]
+pub enum RasterizerError {
+    #[error("Points {from:?} and {to:?} (index {idx}) do not form a latice segment.")]
+    NonLaticeSegment { idx: usize, from: Point, to: Point },
+
+    #[error("Path overlaps itself at {point:?} (indices {first} and {second}).")]
+    Overlap {
+        point: Point,
+        first: usize,
+        second: usize,
+    },
+
+    #[error("Point {point:?} lies outside the target grid.")]
+    OutOfBounds { point: Point },
+
+    #[error("Start index {idx} is out of range for a path of {len} points.")]
+    InvalidStart { idx: usize, len: usize },
+}
+
+type Cell = (Point, Option<Cardinal>, Option<Cardinal>);
+
+/// Indicates that the implementing type can provide the characters used to
+/// draw a path.
+///
+/// `incoming` is the direction of travel that entered the cell and `outgoing`
+/// is the direction of travel that leaves the cell. The first cell of an open
+/// path has no `incoming` direction and the last cell of an open path has no
+/// `outgoing` direction. These will never both be `None`.
+pub trait Glyphs {
+    fn glyph(&self, incoming: Option<Cardinal>, outgoing: Option<Cardinal>) -> char;
+}
+
+/// The pipe characters `-|7JFL`, where `7` connects west and south, `J`
+/// connects west and north, `F` connects east and south, and `L` connects
+/// east and north.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AsciiPipes;
+
+impl Glyphs for AsciiPipes {
+    fn glyph(&self, incoming: Option<Cardinal>, outgoing: Option<Cardinal>) -> char {
+        pick_shape(
+            incoming,
+            outgoing,
+            ['-', '|', '7', 'J', 'F', 'L'],
+            ['-', '|'],
+        )
+    }
+}
+
+/// The unicode box drawing characters `─│┐┘┌└`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BoxDrawing;
+
+impl Glyphs for BoxDrawing {
+    fn glyph(&self, incoming: Option<Cardinal>, outgoing: Option<Cardinal>) -> char {
+        pick_shape(
+            incoming,
+            outgoing,
+            ['─', '│', '┐', '┘', '┌', '└'],
+            ['─', '│'],
+        )
+    }
+}
+
+/// Every cell of the path is drawn as `#`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Solid;
+
+impl Glyphs for Solid {
+    fn glyph(&self, _incoming: Option<Cardinal>, _outgoing: Option<Cardinal>) -> char {
+        '#'
+    }
+}
+
+/// Every cell of the path is drawn as an arrow (`^>v<`) pointing in the
+/// direction of travel out of that cell. The last cell of an open path points
+/// in the direction it was entered.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Arrows;
+
+impl Glyphs for Arrows {
+    fn glyph(&self, incoming: Option<Cardinal>, outgoing: Option<Cardinal>) -> char {
+        match outgoing.or(incoming) {
+            Some(Cardinal::North) => '^',
+            Some(Cardinal::East) => '>',
+            Some(Cardinal::South) => 'v',
+            Some(Cardinal::West) => '<',
+            None => unreachable!("glyph requested without a direction"),
+        }
+    }
+}
+
+// The masks of the connected sides of a cell in the order: horizontal,
+// vertical, west-south, west-north, east-south, east-north
+const SHAPE_MASKS: [u8; 6] = [
+    Cardinal::East as u8 | Cardinal::West as u8,
+    Cardinal::North as u8 | Cardinal::South as u8,
+    Cardinal::West as u8 | Cardinal::South as u8,
+    Cardinal::West as u8 | Cardinal::North as u8,
+    Cardinal::East as u8 | Cardinal::South as u8,
+    Cardinal::East as u8 | Cardinal::North as u8,
+];
+
+/// Select the shape by the set of sides of the cell the path connects to.
+///
+/// `ends` are the horizontal and vertical shapes used for the endpoints of an
+/// open path.
+fn pick_shape(
+    incoming: Option<Cardinal>,
+    outgoing: Option<Cardinal>,
+    shapes: [char; 6],
+    ends: [char; 2],
+) -> char {
+    match (incoming, outgoing) {
+        (Some(i), Some(o)) => {
+            // we enter a cell through the side opposite our direction of travel
+            let mask = i.opposite() as u8 | o as u8;
+            SHAPE_MASKS
+                .iter()
+                .position(|m| *m == mask)
+                .map(|pos| shapes[pos])
+                .unwrap_or_else(|| unreachable!("Unexpected combo ({:?}, {:?})", i, o))
+        }
+        (Some(d), None) | (None, Some(d)) => match d {
+            Cardinal::East | Cardinal::West => ends[0],
+            Cardinal::North | Cardinal::South => ends[1],
+        },
+        (None, None) => unreachable!("glyph requested without a direction"),
+    }
+}
+
+/// Draws a [PointPath] onto a [Grid] of chars.
+///
+/// Segments do not need to be unit segments, but every segment must be
+/// horizontal or vertical. Paths whose first and last points are identical are
+/// treated as closed, and the shared point is drawn as a corner (or straight)
+/// joining the last segment to the first.
+///
+/// The positive y direction is treated as North, so rows are flipped relative
+/// to the y coordinate (the row with index `0` is the largest y value).
+///
+/// A path that visits the same cell more than once (other than the shared
+/// start/end point of a closed path) is an error.
+///
+/// # Examples
+/// ```
+/// use proliferatr::path::{AsciiPipes, ClosedPath, PathRasterizer, PointPath};
+///
+/// let mut p = ClosedPath::rect_path(3, 3).unwrap();
+///
+/// let rasterizer = PathRasterizer::builder()
+///     .glyphs(AsciiPipes)
+///     .start('S')
+///     .build()
+///     .unwrap();
+///
+/// let grid = rasterizer.rasterize(&p).unwrap();
+/// assert_eq!(grid.to_string(), "F-7\n|.|\nS-J");
+///
+/// // paths with negative coordinates are shifted to fit in the grid
+/// p.translate((-5, -5).into());
+/// let grid = rasterizer.rasterize(&p).unwrap();
+/// assert_eq!(grid.to_string(), "F-7\n|.|\nS-J");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Builder)]
+pub struct PathRasterizer<G>
+where
+    G: Glyphs + Clone,
+{
+    /// The glyph set used to draw the path.
+    glyphs: G,
+
+    /// The char used for cells that are not part of the path.
+    #[builder(default = "'.'")]
+    background: char,
+
+    /// The optional char used to mark the start of the path.
+    #[builder(default, setter(strip_option))]
+    start: Option<char>,
+
+    /// The index of the point in the path to mark with the `start` char.
+    #[builder(default)]
+    start_idx: usize,
+}
+
+impl<G> PathRasterizer<G>
+where
+    G: Glyphs + Clone,
+{
+    pub fn builder() -> PathRasterizerBuilder<G> {
+        PathRasterizerBuilder::default()
+    }
+
+    /// Make a new [CharGrid] large enough to contain the `path` and draw the
+    /// `path` onto it.
+    ///
+    /// If the path has any negative coordinates, the entire path is offset
+    /// such that the smallest `x` and `y` values map to the edges of the grid.
+    pub fn rasterize<P: PointPath>(&self, path: &P) -> Result<CharGrid, PathError> {
+        if path.is_empty() {
+            return Ok(Grid::new(0, 0, self.background));
+        }
+
+        let bounds = Bound2D::derive_from(path.points());
+        let offset = Point::new(-bounds.min_x.min(0), -bounds.min_y.min(0));
+        let width = (bounds.max_x + offset.x + 1) as usize;
+        let height = (bounds.max_y + offset.y + 1) as usize;
+
+        let mut grid = Grid::new(width, height, self.background);
+        self.draw_offset(path, &mut grid, offset)?;
+        Ok(grid)
+    }
+
+    /// Draw the `path` onto an existing `grid` without offsetting it.
+    ///
+    /// Fails if any point of the path lies outside the grid.
+    pub fn draw<P: PointPath>(&self, path: &P, grid: &mut CharGrid) -> Result<(), PathError> {
+        self.draw_offset(path, grid, Point::default())
+    }
+
+    fn draw_offset<P: PointPath>(
+        &self,
+        path: &P,
+        grid: &mut CharGrid,
+        offset: Point,
+    ) -> Result<(), PathError> {
+        let len = path.len();
+        if len == 0 {
+            return Ok(());
+        }
+
+        if self.start.is_some() && self.start_idx >= len {
+            return Err(RasterizerError::InvalidStart {
+                idx: self.start_idx,
+                len,
+            }
+            .into());
+        }
+
+        let closed = len > 1 && path.get(0) == path.get(len - 1);
+
+        // expand the path into (cell, incoming, outgoing)
+        let mut cells: Vec<Cell> = Vec::with_capacity(len);
+        let mut seen: FxHashMap<Point, usize> = FxHashMap::default();
+        let mut cur = *path.get(0).unwrap();
+        let mut incoming = None;
+
+        for idx in 1..len {
+            let next = *path.get(idx).unwrap();
+            if next == cur {
+                continue;
+            }
+
+            let dir = cur
+                .cardinal_to(&next)
+                .ok_or(RasterizerError::NonLaticeSegment {
+                    idx: idx - 1,
+                    from: cur,
+                    to: next,
+                })?;
+            let step = step_for(dir);
+
+            while cur != next {
+                Self::record(&mut cells, &mut seen, cur, incoming, Some(dir), idx - 1)?;
+                incoming = Some(dir);
+                cur += step;
+            }
+        }
+
+        if closed {
+            // the final point is the first point, so join the last segment to
+            // the first one
+            if let Some(first) = cells.first_mut() {
+                first.1 = incoming;
+            }
+        } else {
+            Self::record(&mut cells, &mut seen, cur, incoming, None, len - 1)?;
+        }
+
+        let height = grid.height() as i64;
+        for (point, incoming, outgoing) in cells.iter() {
+            let ch = if incoming.is_none() && outgoing.is_none() {
+                // a path of a single distinct point
+                self.glyphs.glyph(None, Some(Cardinal::East))
+            } else {
+                self.glyphs.glyph(*incoming, *outgoing)
+            };
+
+            Self::set(grid, height, offset_by(point, &offset), ch)?;
+        }
+
+        // because the path cannot overlap, the start point maps to exactly
+        // one cell
+        if let Some(start) = self.start {
+            let point = path.get(self.start_idx).unwrap();
+            Self::set(grid, height, offset_by(point, &offset), start)?;
+        }
+
+        Ok(())
+    }
+
+    fn record(
+        cells: &mut Vec<Cell>,
+        seen: &mut FxHashMap<Point, usize>,
+        point: Point,
+        incoming: Option<Cardinal>,
+        outgoing: Option<Cardinal>,
+        idx: usize,
+    ) -> Result<(), RasterizerError> {
+        if let Some(first) = seen.insert(point, idx) {
+            return Err(RasterizerError::Overlap {
+                point,
+                first,
+                second: idx,
+            });
+        }
+
+        cells.push((point, incoming, outgoing));
+        Ok(())
+    }
+
+    fn set(
+        grid: &mut CharGrid,
+        height: i64,
+        point: Point,
+        ch: char,
+    ) -> Result<(), RasterizerError> {
+        let flipped = Point::new(point.x, height - 1 - point.y);
+        if point.y < 0 || !grid.set(&flipped, ch) {
+            return Err(RasterizerError::OutOfBounds { point });
+        }
+
+        Ok(())
+    }
+}
+
+fn offset_by(point: &Point, offset: &Point) -> Point {
+    let mut point = *point;
+    point += offset;
+    point
+}
+
+fn step_for(dir: Cardinal) -> Point {
+    match dir {
+        Cardinal::North => Point::new(0, 1),
+        Cardinal::East => Point::new(1, 0),
+        Cardinal::South => Point::new(0, -1),
+        Cardinal::West => Point::new(-1, 0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::path::{ClosedPath, Path};
+
+    use super::*;
+
+    #[test]
+    fn open_path() {
+        let p = Path::from_iter([(0, 0).into(), (2, 0).into(), (2, 2).into(), (1, 2).into()]);
+
+        let rasterizer = PathRasterizer::builder().glyphs(Arrows).build().unwrap();
+        let grid = rasterizer.rasterize(&p).unwrap();
+        assert_eq!(grid.to_string(), ".<<\n..^\n>>^");
+
+        let rasterizer = PathRasterizer::builder()
+            .glyphs(BoxDrawing)
+            .start('S')
+            .start_idx(3)
+            .build()
+            .unwrap();
+        let grid = rasterizer.rasterize(&p).unwrap();
+        assert_eq!(grid.to_string(), ".S┐\n..│\n──┘");
+    }
+
+    #[test]
+    fn draw_onto_existing() {
+        let p = ClosedPath::rect_path(2, 2).unwrap();
+        let mut grid = Grid::new(3, 3, ' ');
+
+        let rasterizer = PathRasterizer::builder().glyphs(Solid).build().unwrap();
+        rasterizer.draw(&p, &mut grid).unwrap();
+        assert_eq!(grid.to_string(), "   \n## \n## ");
+
+        let mut small = Grid::new(1, 1, ' ');
+        assert!(matches!(
+            rasterizer.draw(&p, &mut small),
+            Err(PathError::Rasterizer(RasterizerError::OutOfBounds { .. }))
+        ));
+    }
+
+    #[test]
+    fn errors() {
+        let rasterizer = PathRasterizer::builder()
+            .glyphs(AsciiPipes)
+            .build()
+            .unwrap();
+
+        let p = Path::from_iter([(0, 0).into(), (2, 0).into(), (1, 0).into()]);
+        assert!(matches!(
+            rasterizer.rasterize(&p),
+            Err(PathError::Rasterizer(RasterizerError::Overlap { .. }))
+        ));
+
+        let p = Path::from_iter([(0, 0).into(), (2, 1).into()]);
+        assert!(matches!(
+            rasterizer.rasterize(&p),
+            Err(PathError::Rasterizer(RasterizerError::NonLaticeSegment {
+                idx: 0,
+                ..
+            }))
+        ));
+    }
+}
@@ -0,0 +1,187 @@
+use derive_builder::Builder;
+use rand::{
+    distributions::{Distribution, Standard},
+    Rng,
+};
+
+use crate::{bound::Bound2D, point::Point};
+
+use super::{PathMutator, PointPath};
+
+/// A counterclockwise rotation by a multiple of 90 degrees.
+///
+/// A random rotation can be selected with `rng.gen::<Rotation>()`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Rotation {
+    #[default]
+    Deg90,
+    Deg180,
+    Deg270,
+}
+
+impl Rotation {
+    /// Rotate the `point` about the origin.
+    pub fn apply(&self, point: &mut Point) {
+        let (x, y) = (point.x, point.y);
+        (point.x, point.y) = match self {
+            Self::Deg90 => (-y, x),
+            Self::Deg180 => (-x, -y),
+            Self::Deg270 => (y, -x),
+        };
+    }
+}
+
+impl Distribution<Rotation> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Rotation {
+        match rng.gen_range(0..3) {
+            0 => Rotation::Deg90,
+            1 => Rotation::Deg180,
+            _ => Rotation::Deg270,
+        }
+    }
+}
+
+/// The point about which a [PathRotator] rotates a path.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RotationCenter {
+    /// Rotate about `(0, 0)`.
+    #[default]
+    Origin,
+
+    /// Rotate about the center of the bounding box of the path.
+    ///
+    /// When the center does not fall on a latice point, the rotated path is
+    /// shifted toward the negative x and y directions to keep it on the
+    /// latice. Because of this, repeated 90 degree rotations of a path whose
+    /// bounds have a width and height of differing parity will drift.
+    Center,
+}
+
+/// A [PathMutator] that rotates the points in a given [PointPath] by a
+/// multiple of 90 degrees.
+///
+/// # Examples
+/// ```
+/// use proliferatr::path::{
+///     ClosedPath, PathMutator, PathRotator, PointPath, Rotation, RotationCenter,
+/// };
+///
+/// let mut p = ClosedPath::rect_path(2, 3).unwrap();
+///
+/// let mut rotator = PathRotator::builder()
+///     .rotation(Rotation::Deg90)
+///     .build()
+///     .unwrap();
+///
+/// rotator.mutate(&mut p);
+///
+/// assert_eq!(p.get(0).copied(), Some((0, 0).into()));
+/// assert_eq!(p.get(1).copied(), Some((-1, 0).into()));
+/// assert_eq!(p.get(2).copied(), Some((-2, 0).into()));
+/// assert_eq!(p.get(3).copied(), Some((-2, 1).into()));
+///
+/// // rotating about the center keeps the path in place
+/// let mut p = ClosedPath::rect_path(3, 3).unwrap();
+/// let mut rotator = PathRotator::builder()
+///     .rotation(Rotation::Deg180)
+///     .center(RotationCenter::Center)
+///     .build()
+///     .unwrap();
+///
+/// rotator.mutate(&mut p);
+/// assert_eq!(p.get(0).copied(), Some((2, 2).into()));
+/// assert_eq!(p.get(2).copied(), Some((2, 0).into()));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Builder)]
+pub struct PathRotator {
+    #[builder(default)]
+    rotation: Rotation,
+
+    #[builder(default)]
+    center: RotationCenter,
+}
+
+impl PathRotator {
+    pub fn builder() -> PathRotatorBuilder {
+        PathRotatorBuilder::default()
+    }
+}
+
+impl PathMutator for PathRotator {
+    fn mutate<P: PointPath>(&mut self, path: &mut P) -> bool {
+        if path.is_empty() {
+            return false;
+        }
+
+        let before = Bound2D::derive_from(path.points());
+
+        for p in path.points_mut() {
+            self.rotation.apply(p);
+        }
+
+        if self.center == RotationCenter::Center {
+            let after = Bound2D::derive_from(path.points());
+
+            // align the centers of the bounding boxes
+            let old_w = before.max_x - before.min_x;
+            let old_h = before.max_y - before.min_y;
+            let new_w = after.max_x - after.min_x;
+            let new_h = after.max_y - after.min_y;
+
+            let dxdy = Point::new(
+                before.min_x + (old_w - new_w).div_euclid(2) - after.min_x,
+                before.min_y + (old_h - new_h).div_euclid(2) - after.min_y,
+            );
+
+            path.translate(dxdy);
+        }
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::path::ClosedPath;
+
+    use super::*;
+
+    #[test]
+    fn full_rotation() {
+        // the width and height of the bounds must have the same parity to
+        // avoid drifting when rotating about the center
+        let original = ClosedPath::rect_path(4, 6).unwrap();
+
+        for center in [RotationCenter::Origin, RotationCenter::Center] {
+            let mut p = original.clone();
+            let mut rotator = PathRotator::builder().center(center).build().unwrap();
+
+            for _ in 0..4 {
+                rotator.mutate(&mut p);
+            }
+
+            assert_eq!(p, original);
+        }
+    }
+
+    #[test]
+    fn center_keeps_bounds_centered() {
+        let mut p = ClosedPath::rect_path(5, 9).unwrap();
+        let mut rotator = PathRotator::builder()
+            .rotation(Rotation::Deg270)
+            .center(RotationCenter::Center)
+            .build()
+            .unwrap();
+        rotator.mutate(&mut p);
+
+        assert_eq!(
+            Bound2D::derive_from(p.points()),
+            Bound2D {
+                min_x: -2,
+                max_x: 6,
+                min_y: 2,
+                max_y: 6,
+            }
+        );
+    }
+}
@@ -0,0 +1,49 @@
+use derive_builder::Builder;
+
+use crate::point::Point;
+
+use super::{PathMutator, PointPath};
+
+/// A [PathMutator] that translates the points in a given [PointPath] by a
+/// fixed offset.
+///
+/// # Examples
+/// ```
+/// use proliferatr::path::{ClosedPath, PathMutator, PathTranslator, PointPath};
+///
+/// let mut p = ClosedPath::rect_path(2, 2).unwrap();
+///
+/// let mut translator = PathTranslator::builder()
+///     .offset((3, -1))
+///     .build()
+///     .unwrap();
+///
+/// translator.mutate(&mut p);
+///
+/// assert_eq!(p.get(0).copied(), Some((3, -1).into()));
+/// assert_eq!(p.get(2).copied(), Some((4, 0).into()));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Builder)]
+pub struct PathTranslator {
+    #[builder(default, setter(into))]
+    offset: Point,
+}
+
+impl PathTranslator {
+    pub fn builder() -> PathTranslatorBuilder {
+        PathTranslatorBuilder::default()
+    }
+}
+
+impl PathMutator for PathTranslator {
+    fn mutate<P: PointPath>(&mut self, path: &mut P) -> bool {
+        // break early in this special case
+        if self.offset == Point::default() || path.is_empty() {
+            return false;
+        }
+
+        path.translate(self.offset);
+
+        true
+    }
+}
@@ -0,0 +1,587 @@
+use derive_builder::Builder;
+use rand::{seq::SliceRandom, Rng};
+use rustc_hash::{FxHashMap, FxHashSet};
+use thiserror::Error;
+
+use crate::{bound::Bound2D, point::Point, InputGenerator};
+
+use super::{ClosedPath, PointPath};
+
+// the 8 neighbors of a cell, in clockwise order starting from north
+const RING: [(i64, i64); 8] = [
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum RandomPolygonError {
+    #[error("Orthogonal polygons must have an even number of at least 4 vertices, not {0}.")]
+    InvalidVertexCount(usize),
+
+    #[error("The bounds are too small to contain a polygon.")]
+    BoundsTooSmall,
+
+    #[error("Failed to generate a polygon after {0} attempts.")]
+    Exhausted(usize),
+}
+
+/// The kinds of polygon a [RandomPolygon] can generate.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PolygonShape {
+    /// The boundary of a polyomino grown one unit cell at a time. The
+    /// polyomino never has holes or cells that only touch at a corner.
+    ///
+    /// Produces a unit path enclosing exactly `min_area`.
+    #[default]
+    Polyomino,
+
+    /// A polygon formed from four monotone "staircases", one in each quadrant
+    /// around a center point. Every point on the boundary is visible from the
+    /// center.
+    ///
+    /// Produces a unit path.
+    Star,
+
+    /// An orthogonal polygon with exactly the specified number of vertices,
+    /// made by repeatedly cutting random notches into (or adding them onto)
+    /// the corners of a rectangle.
+    ///
+    /// Produces a condensed path, where every point is a vertex.
+    Orthogonal { vertices: usize },
+}
+
+/// A type that can generate random simple [ClosedPath], with clockwise
+/// winding order.
+///
+/// Every point of the generated path is within the configured bounds, and the
+/// polygon will not contain or touch any point in the excluded regions.
+///
+/// # Examples
+/// ```
+/// use rand::thread_rng;
+/// use proliferatr::{
+///     bound::Bound2D,
+///     path::{ClosedPath, PathCondenser, PathMutator, PointPath, PolygonShape, Winding},
+///     InputGenerator,
+/// };
+///
+/// let bounds = Bound2D { min_x: 0, max_x: 50, min_y: 0, max_y: 50 };
+///
+/// let generator = ClosedPath::random()
+///     .shape(PolygonShape::Orthogonal { vertices: 20 })
+///     .bounds(bounds)
+///     .min_area(100)
+///     .exclude(Bound2D { min_x: 20, max_x: 30, min_y: 20, max_y: 30 })
+///     .build()
+///     .unwrap();
+///
+/// let p = generator.gen_input(&mut thread_rng()).unwrap();
+/// assert_eq!(p.len(), 21);
+/// assert!(p.area() >= 100);
+/// assert!(p.is_closed() && p.is_lattice() && p.is_simple());
+/// assert_eq!(p.winding(), Some(Winding::Clockwise));
+///
+/// let generator = ClosedPath::random()
+///     .shape(PolygonShape::Polyomino)
+///     .bounds(bounds)
+///     .min_area(200)
+///     .build()
+///     .unwrap();
+///
+/// let p = generator.gen_input(&mut thread_rng()).unwrap();
+/// assert_eq!(p.area(), 200);
+/// assert!(p.is_closed() && p.is_unit() && p.is_simple());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Builder)]
+pub struct RandomPolygon {
+    #[builder(default)]
+    shape: PolygonShape,
+
+    /// All points of the polygon lie within these bounds.
+    bounds: Bound2D,
+
+    /// The minimum area enclosed by the polygon.
+    #[builder(default = "1")]
+    min_area: usize,
+
+    /// Regions the polygon must not contain or touch.
+    #[builder(default, setter(each(name = "exclude")))]
+    exclusions: Vec<Bound2D>,
+
+    /// The number of attempts to make before giving up.
+    #[builder(default = "100")]
+    max_attempts: usize,
+}
+
+impl ClosedPath {
+    /// Make a builder for a generator of random [ClosedPath].
+    ///
+    /// See [RandomPolygon].
+    pub fn random() -> RandomPolygonBuilder {
+        RandomPolygonBuilder::default()
+    }
+}
+
+impl RandomPolygon {
+    pub fn builder() -> RandomPolygonBuilder {
+        RandomPolygonBuilder::default()
+    }
+
+    /// Generate a random polygon using the configuration of this generator.
+    pub fn gen_polygon<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> Result<ClosedPath, RandomPolygonError> {
+        if self.bounds.max_x - self.bounds.min_x < 1 || self.bounds.max_y - self.bounds.min_y < 1 {
+            return Err(RandomPolygonError::BoundsTooSmall);
+        }
+
+        if let PolygonShape::Orthogonal { vertices } = self.shape {
+            if vertices < 4 || vertices % 2 == 1 {
+                return Err(RandomPolygonError::InvalidVertexCount(vertices));
+            }
+        }
+
+        for _ in 0..self.max_attempts {
+            let candidate = match self.shape {
+                PolygonShape::Polyomino => self.polyomino(rng),
+                PolygonShape::Star => self.star(rng),
+                PolygonShape::Orthogonal { vertices } => self.orthogonal(rng, vertices),
+            };
+
+            if let Some(path) = candidate {
+                if path.area() >= self.min_area as i64 {
+                    return Ok(path);
+                }
+            }
+        }
+
+        Err(RandomPolygonError::Exhausted(self.max_attempts))
+    }
+
+    fn excluded(&self, point: &Point) -> bool {
+        self.exclusions.iter().any(|b| b.contains(point))
+    }
+
+    /// Returns `true` if the unit cell with lower left corner `cell` may be
+    /// part of a polyomino.
+    fn cell_allowed(&self, cell: &Point) -> bool {
+        let corners = [
+            *cell,
+            Point::new(cell.x + 1, cell.y),
+            Point::new(cell.x, cell.y + 1),
+            Point::new(cell.x + 1, cell.y + 1),
+        ];
+
+        // an exclusion zone can only overlap the cell without containing a
+        // corner if it's strictly inside the cell, which is impossible for
+        // latice bounds
+        corners
+            .iter()
+            .all(|c| self.bounds.contains(c) && !self.excluded(c))
+    }
+
+    /// Returns `true` if the polygon does not contain or touch any of the
+    /// excluded regions.
+    fn avoids_exclusions(&self, path: &ClosedPath) -> bool {
+        use super::PointLocation;
+
+        for zone in self.exclusions.iter() {
+            // any segment entering the zone
+            for (a, b) in path.points().zip(path.points().skip(1)) {
+                if a.x.min(b.x) <= zone.max_x
+                    && a.x.max(b.x) >= zone.min_x
+                    && a.y.min(b.y) <= zone.max_y
+                    && a.y.max(b.y) >= zone.min_y
+                {
+                    return false;
+                }
+            }
+
+            // the zone entirely inside the polygon
+            if path.locate(&Point::new(zone.min_x, zone.min_y)) != PointLocation::Outside {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    fn polyomino<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<ClosedPath> {
+        let target = self.min_area.max(1);
+
+        let seed = Point::new(
+            rng.gen_range(self.bounds.min_x..self.bounds.max_x),
+            rng.gen_range(self.bounds.min_y..self.bounds.max_y),
+        );
+
+        if !self.cell_allowed(&seed) {
+            return None;
+        }
+
+        let mut cells = FxHashSet::default();
+        cells.insert(seed);
+
+        let mut frontier: Vec<Point> = Vec::new();
+        let mut in_frontier = FxHashSet::default();
+        push_neighbors(&seed, &cells, &mut frontier, &mut in_frontier);
+
+        while cells.len() < target {
+            if frontier.is_empty() {
+                return None;
+            }
+
+            let idx = rng.gen_range(0..frontier.len());
+            let cell = frontier.swap_remove(idx);
+            in_frontier.remove(&cell);
+
+            // rejected cells will be reconsidered when a neighbor is added
+            if !self.cell_allowed(&cell) || !is_simple_addition(&cell, &cells) {
+                continue;
+            }
+
+            cells.insert(cell);
+            push_neighbors(&cell, &cells, &mut frontier, &mut in_frontier);
+        }
+
+        Some(trace_cells(&cells))
+    }
+
+    fn star<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<ClosedPath> {
+        let b = &self.bounds;
+        if b.max_x - b.min_x < 2 || b.max_y - b.min_y < 2 {
+            return None;
+        }
+
+        let center = Point::new(
+            rng.gen_range((b.min_x + 1)..b.max_x),
+            rng.gen_range((b.min_y + 1)..b.max_y),
+        );
+
+        let north = rng.gen_range(1..=(b.max_y - center.y));
+        let east = rng.gen_range(1..=(b.max_x - center.x));
+        let south = rng.gen_range(1..=(center.y - b.min_y));
+        let west = rng.gen_range(1..=(center.x - b.min_x));
+
+        // clockwise from the top of the y axis, each quadrant moves in two
+        // directions: away from the axis it starts on and toward the axis it
+        // ends on. The first and last moves are fixed so the staircases only
+        // touch the axes at a single point.
+        let quadrants = [
+            ((1, 0), east, (0, -1), north),
+            ((0, -1), south, (-1, 0), east),
+            ((-1, 0), west, (0, 1), south),
+            ((0, 1), north, (1, 0), west),
+        ];
+
+        let mut cur = Point::new(center.x, center.y + north);
+        let mut points = vec![cur];
+
+        for (away, away_count, toward, toward_count) in quadrants {
+            let mut moves = vec![away; (away_count - 1) as usize];
+            moves.extend(std::iter::repeat(toward).take((toward_count - 1) as usize));
+            moves.shuffle(rng);
+
+            for (dx, dy) in std::iter::once(away)
+                .chain(moves)
+                .chain(std::iter::once(toward))
+            {
+                cur = Point::new(cur.x + dx, cur.y + dy);
+                points.push(cur);
+            }
+        }
+
+        let path = ClosedPath::from_iter(points);
+
+        if self.avoids_exclusions(&path) {
+            Some(path)
+        } else {
+            None
+        }
+    }
+
+    fn orthogonal<R: Rng + ?Sized>(&self, rng: &mut R, vertices: usize) -> Option<ClosedPath> {
+        let b = &self.bounds;
+        let x0 = rng.gen_range(b.min_x..b.max_x);
+        let x1 = rng.gen_range((x0 + 1)..=b.max_x);
+        let y0 = rng.gen_range(b.min_y..b.max_y);
+        let y1 = rng.gen_range((y0 + 1)..=b.max_y);
+
+        // clockwise, without the duplicated start point
+        let mut corners = vec![
+            Point::new(x0, y0),
+            Point::new(x0, y1),
+            Point::new(x1, y1),
+            Point::new(x1, y0),
+        ];
+
+        let mut failures = 0;
+
+        while corners.len() < vertices {
+            if failures > vertices * 10 {
+                return None;
+            }
+
+            let n = corners.len();
+            let i = rng.gen_range(0..n);
+            let p = corners[(i + n - 1) % n];
+            let v = corners[i];
+            let q = corners[(i + 1) % n];
+
+            let len_p = p.manhattan_distance(&v);
+            let len_q = q.manhattan_distance(&v);
+
+            if len_p < 2 || len_q < 2 {
+                failures += 1;
+                continue;
+            }
+
+            // replace the corner with a notch: the rectangle spanned by `v` and
+            // `c` is removed from a convex corner or added onto a reflex corner
+            let da = rng.gen_range(1..len_p);
+            let db = rng.gen_range(1..len_q);
+            let u1 = unit(&v, &p);
+            let u2 = unit(&v, &q);
+
+            let a = Point::new(v.x + u1.x * da, v.y + u1.y * da);
+            let bb = Point::new(v.x + u2.x * db, v.y + u2.y * db);
+            let c = Point::new(a.x + u2.x * db, a.y + u2.y * db);
+
+            if !self.bounds.contains(&c) {
+                failures += 1;
+                continue;
+            }
+
+            let mut candidate = corners.clone();
+            candidate.splice(i..=i, [a, c, bb]);
+
+            let path = close(&candidate);
+            if path.is_simple() && self.avoids_exclusions(&path) {
+                corners = candidate;
+            } else {
+                failures += 1;
+            }
+        }
+
+        let path = close(&corners);
+
+        if self.avoids_exclusions(&path) {
+            Some(path)
+        } else {
+            None
+        }
+    }
+}
+
+impl InputGenerator for RandomPolygon {
+    type GeneratorError = RandomPolygonError;
+    type Output = ClosedPath;
+
+    fn gen_input<R: Rng + Clone>(&self, rng: &mut R) -> Result<Self::Output, Self::GeneratorError> {
+        self.gen_polygon(rng)
+    }
+}
+
+fn unit(from: &Point, to: &Point) -> Point {
+    Point::new((to.x - from.x).signum(), (to.y - from.y).signum())
+}
+
+fn close(corners: &[Point]) -> ClosedPath {
+    ClosedPath::from_iter(corners.iter().chain(corners.first()).copied())
+}
+
+fn push_neighbors(
+    cell: &Point,
+    cells: &FxHashSet<Point>,
+    frontier: &mut Vec<Point>,
+    in_frontier: &mut FxHashSet<Point>,
+) {
+    for (dx, dy) in [(0, 1), (1, 0), (0, -1), (-1, 0)] {
+        let n = Point::new(cell.x + dx, cell.y + dy);
+        if !cells.contains(&n) && in_frontier.insert(n) {
+            frontier.push(n);
+        }
+    }
+}
+
+/// Returns `true` if adding `cell` to `cells` keeps the boundary of the
+/// polyomino a single simple loop.
+///
+/// This is the case if the addition does not create a corner-only contact
+/// with a diagonal neighbor, and if the occupied neighbors form a single
+/// contiguous run around the cell (otherwise the addition would close a loop
+/// around some unoccupied region).
+fn is_simple_addition(cell: &Point, cells: &FxHashSet<Point>) -> bool {
+    let ring: Vec<bool> = RING
+        .iter()
+        .map(|(dx, dy)| cells.contains(&Point::new(cell.x + dx, cell.y + dy)))
+        .collect();
+
+    // diagonals are at the odd indices
+    for i in (1..8).step_by(2) {
+        if ring[i] && !ring[i - 1] && !ring[(i + 1) % 8] {
+            return false;
+        }
+    }
+
+    let runs = (0..8).filter(|&i| ring[i] && !ring[(i + 7) % 8]).count();
+
+    runs == 1
+}
+
+/// Trace the boundary of a simply connected polyomino with no corner-only
+/// contacts, in clockwise order.
+fn trace_cells(cells: &FxHashSet<Point>) -> ClosedPath {
+    // counterclockwise edges of each cell, with shared edges cancelling out
+    let mut boundary: FxHashSet<(Point, Point)> = FxHashSet::default();
+
+    for cell in cells.iter() {
+        let corners = [
+            *cell,
+            Point::new(cell.x + 1, cell.y),
+            Point::new(cell.x + 1, cell.y + 1),
+            Point::new(cell.x, cell.y + 1),
+        ];
+
+        for i in 0..4 {
+            let from = corners[i];
+            let to = corners[(i + 1) % 4];
+
+            if !boundary.remove(&(to, from)) {
+                boundary.insert((from, to));
+            }
+        }
+    }
+
+    // without corner-only contacts, every vertex has exactly one outgoing edge
+    let edges: FxHashMap<Point, Point> = boundary.into_iter().collect();
+
+    let start = *edges.keys().min().unwrap();
+    let mut points = vec![start];
+    let mut cur = start;
+
+    loop {
+        cur = edges[&cur];
+        points.push(cur);
+        if cur == start {
+            break;
+        }
+    }
+
+    // counterclockwise to clockwise
+    points.reverse();
+    ClosedPath::from_iter(points)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::path::{PathCondenser, PathMutator, PointLocation, Winding};
+
+    use super::*;
+
+    const BOUNDS: Bound2D = Bound2D {
+        min_x: -20,
+        max_x: 20,
+        min_y: -20,
+        max_y: 20,
+    };
+
+    const ZONE: Bound2D = Bound2D {
+        min_x: -3,
+        max_x: 3,
+        min_y: -3,
+        max_y: 3,
+    };
+
+    fn check(path: &ClosedPath) {
+        assert!(path.is_closed());
+        assert!(path.is_lattice());
+        assert!(path.is_simple());
+        assert_eq!(path.winding(), Some(Winding::Clockwise));
+        assert!(path.points().all(|p| BOUNDS.contains(p)));
+
+        for x in ZONE.min_x..=ZONE.max_x {
+            for y in ZONE.min_y..=ZONE.max_y {
+                assert_eq!(path.locate(&Point::new(x, y)), PointLocation::Outside);
+            }
+        }
+    }
+
+    #[test]
+    fn shapes() {
+        let mut rng = StdRng::seed_from_u64(33);
+
+        for (shape, min_area) in [
+            (PolygonShape::Polyomino, 300),
+            (PolygonShape::Star, 100),
+            (PolygonShape::Orthogonal { vertices: 4 }, 50),
+            (PolygonShape::Orthogonal { vertices: 30 }, 100),
+        ] {
+            let generator = RandomPolygon::builder()
+                .shape(shape)
+                .bounds(BOUNDS)
+                .min_area(min_area)
+                .exclude(ZONE)
+                .build()
+                .unwrap();
+
+            for _ in 0..5 {
+                let mut p = generator.gen_polygon(&mut rng).unwrap();
+                check(&p);
+                assert!(p.area() >= min_area as i64);
+
+                match shape {
+                    PolygonShape::Polyomino => {
+                        assert!(p.is_unit());
+                        assert_eq!(p.area(), min_area as i64);
+                    }
+                    PolygonShape::Star => assert!(p.is_unit()),
+                    PolygonShape::Orthogonal { vertices } => {
+                        assert_eq!(p.len(), vertices + 1);
+                        // already condensed
+                        assert!(!PathCondenser::default().mutate(&mut p));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let mut rng = StdRng::seed_from_u64(1);
+
+        let generator = RandomPolygon::builder()
+            .shape(PolygonShape::Orthogonal { vertices: 5 })
+            .bounds(BOUNDS)
+            .build()
+            .unwrap();
+        assert_eq!(
+            generator.gen_polygon(&mut rng),
+            Err(RandomPolygonError::InvalidVertexCount(5))
+        );
+
+        let generator = RandomPolygon::builder()
+            .bounds(Bound2D {
+                min_x: 0,
+                max_x: 3,
+                min_y: 0,
+                max_y: 3,
+            })
+            .min_area(10)
+            .max_attempts(5)
+            .build()
+            .unwrap();
+        assert_eq!(
+            generator.gen_polygon(&mut rng),
+            Err(RandomPolygonError::Exhausted(5))
+        );
+    }
+}
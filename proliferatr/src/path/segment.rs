@@ -0,0 +1,155 @@
+use crate::{
+    direction::{Cardinal, Relative},
+    point::Point,
+};
+
+/// A single segment of a [PointPath], from one point to the next.
+///
+/// [PointPath]: super::PointPath
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Segment {
+    pub from: Point,
+    pub to: Point,
+
+    /// The direction of the segment, if it is horizontal or vertical and of
+    /// non-zero length.
+    pub direction: Option<Cardinal>,
+
+    /// The manhattan length of the segment. This is the actual length for
+    /// horizontal and vertical segments.
+    pub length: i64,
+}
+
+impl Segment {
+    pub fn new(from: Point, to: Point) -> Self {
+        Self {
+            from,
+            to,
+            direction: from.cardinal_to(&to),
+            length: from.manhattan_distance(&to),
+        }
+    }
+
+    /// Returns `true` if this segment is horizontal or vertical and of non-zero
+    /// length.
+    pub fn is_lattice(&self) -> bool {
+        self.direction.is_some()
+    }
+
+    /// Returns `true` if this segment is horizontal or vertical and exactly
+    /// one unit long.
+    pub fn is_unit(&self) -> bool {
+        self.is_lattice() && self.length == 1
+    }
+
+    /// The direction of this segment as a [Relative] direction (north is up).
+    pub fn relative(&self) -> Option<Relative> {
+        self.direction.map(Relative::from)
+    }
+
+    /// The turn needed to go from traveling along this segment to traveling
+    /// along the `next` one.
+    ///
+    /// Continuing straight is [Relative::Up] and reversing is
+    /// [Relative::Down]. Returns `None` if either segment is not a lattice
+    /// segment.
+    ///
+    /// # Examples
+    /// ```
+    /// use proliferatr::{direction::Relative, path::Segment};
+    ///
+    /// let a = Segment::new((0, 0).into(), (0, 3).into());
+    /// let b = Segment::new((0, 3).into(), (2, 3).into());
+    /// let c = Segment::new((2, 3).into(), (1, 4).into());
+    ///
+    /// assert_eq!(a.turn_to(&b), Some(Relative::Right));
+    /// assert_eq!(b.turn_to(&a), Some(Relative::Left));
+    /// assert_eq!(a.turn_to(&a), Some(Relative::Up));
+    /// assert_eq!(b.turn_to(&c), None);
+    /// ```
+    pub fn turn_to(&self, next: &Segment) -> Option<Relative> {
+        let cur = self.direction?;
+        let next = next.direction?;
+
+        Some(if next == cur {
+            Relative::Up
+        } else if next == cur.right() {
+            Relative::Right
+        } else if next == cur.left() {
+            Relative::Left
+        } else {
+            Relative::Down
+        })
+    }
+
+    /// The unit steps along this segment, excluding `from` and including `to`.
+    ///
+    /// Returns `None` if this is not a lattice segment.
+    pub fn unit_points(&self) -> Option<impl Iterator<Item = Point>> {
+        let from = self.from;
+        let dx = (self.to.x - from.x).signum();
+        let dy = (self.to.y - from.y).signum();
+
+        self.direction
+            .map(|_| (1..=self.length).map(move |i| Point::new(from.x + dx * i, from.y + dy * i)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::path::{ClosedPath, Path, PathCondenser, PathMutator, PointPath, ValidationError};
+
+    use super::*;
+
+    #[test]
+    fn expanding() {
+        let original = ClosedPath::rect_path(10, 15).unwrap();
+        let mut p = original.clone();
+        PathCondenser::default().mutate(&mut p);
+
+        let segs: Vec<_> = p.segments().collect();
+        assert_eq!(segs.len(), 4);
+        assert_eq!(segs[0].direction, Some(Cardinal::North));
+        assert_eq!(segs[0].length, 14);
+        assert_eq!(segs[1].length, 9);
+
+        assert_eq!(p.expand_to_unit(), Ok(true));
+        assert_eq!(p, original);
+        assert_eq!(p.expand_to_unit(), Ok(false));
+
+        let mut p: Path = [(0, 0), (0, 2), (3, 5)]
+            .into_iter()
+            .map(Point::from)
+            .collect();
+        assert!(matches!(
+            p.expand_to_unit(),
+            Err(ValidationError::NonLaticeSegment { idx: 1, .. })
+        ));
+        assert_eq!(p.len(), 3);
+    }
+
+    #[test]
+    fn directions_and_turns() {
+        let p = ClosedPath::rect_path(3, 3).unwrap();
+
+        let dirs: Vec<_> = p.directions().map(Option::unwrap).collect();
+        assert_eq!(dirs.len(), 8);
+        assert_eq!(&dirs[..3], &[Relative::Up, Relative::Up, Relative::Right]);
+
+        // a clockwise loop turns right four more times than it turns left,
+        // including the turn back onto the first segment
+        let turns: Vec<_> = p.turns().map(Option::unwrap).collect();
+        assert_eq!(turns.len(), 8);
+        assert_eq!(turns.iter().filter(|t| **t == Relative::Right).count(), 4);
+        assert_eq!(turns.iter().filter(|t| **t == Relative::Up).count(), 4);
+
+        let p: Path = [(0, 0), (2, 0), (2, 2), (3, 3)]
+            .into_iter()
+            .map(Point::from)
+            .collect();
+        assert_eq!(
+            p.turns().collect::<Vec<_>>(),
+            vec![Some(Relative::Left), None]
+        );
+    }
+}
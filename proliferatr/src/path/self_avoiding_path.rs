@@ -0,0 +1,549 @@
+use std::collections::VecDeque;
+
+use derive_builder::Builder;
+use rand::Rng;
+use rustc_hash::FxHashSet;
+use thiserror::Error;
+
+use crate::{bound::Bound2D, direction::Cardinal, point::Point, InputGenerator};
+
+use super::Path;
+
+const CARDINALS: [Cardinal; 4] = [
+    Cardinal::North,
+    Cardinal::East,
+    Cardinal::South,
+    Cardinal::West,
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum SelfAvoidingPathError {
+    #[error("Point {0:?} is outside the bounds.")]
+    OutOfBounds(Point),
+
+    #[error("Point {0:?} is in the avoided set.")]
+    Avoided(Point),
+
+    #[error("No path of length {min}..={max} can join points {distance} apart.")]
+    ImpossibleLength {
+        min: usize,
+        max: usize,
+        distance: usize,
+    },
+
+    #[error("No path exists between the start and end.")]
+    NoPath,
+
+    #[error("Gave up after {0} iterations.")]
+    Exhausted(usize),
+}
+
+/// A type that can generate a random, self-avoiding, unit [Path] from a start
+/// point to an end point.
+///
+/// The path is built by a randomized depth-first walk that backtracks when it
+/// runs into a dead end. The walk will not leave the (optional) bounds, and
+/// will not visit avoided points or points it has already visited. Rather than
+/// searching forever, the walk gives up after `max_iterations` steps.
+///
+/// The length of the path is the number of unit segments, which is always at
+/// least the Manhattan distance between the start and end, and always has the
+/// same parity as that distance. `min_length` and `max_length` are hard
+/// limits, while `target_length` (defaulting to the minimum) is the length the
+/// walk aims for: while it has length to spare, the walk prefers moves away
+/// from the end with a probability of `1 - target_bias`.
+///
+/// `turn_chance` controls the preference for turning instead of continuing in
+/// the same direction, where it's possible to do either.
+///
+/// # Examples
+/// ```
+/// use rand::thread_rng;
+/// use proliferatr::{
+///     bound::Bound2D,
+///     path::{PointPath, SelfAvoidingPath},
+///     InputGenerator,
+/// };
+///
+/// let mut generator = SelfAvoidingPath::builder()
+///     .start((0, 0))
+///     .end((10, 10))
+///     .bounds(Bound2D { min_x: 0, max_x: 20, min_y: 0, max_y: 20 })
+///     .min_length(40)
+///     .max_length(60)
+///     .build()
+///     .unwrap();
+///
+/// generator.insert_avoided((5, 5).into());
+///
+/// let p = generator.gen_input(&mut thread_rng()).unwrap();
+///
+/// assert!(p.len() - 1 >= 40 && p.len() - 1 <= 60);
+/// assert_eq!(p.get(0).copied(), Some((0, 0).into()));
+/// assert_eq!(p.get(p.len() - 1).copied(), Some((10, 10).into()));
+/// assert!(p.is_unit());
+/// assert!(p.is_simple());
+/// assert!(p.points().all(|pt| *pt != (5, 5).into()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct SelfAvoidingPath {
+    #[builder(setter(into))]
+    start: Point,
+
+    #[builder(setter(into))]
+    end: Point,
+
+    /// The optional bound for the path.
+    #[builder(default, setter(into, strip_option))]
+    bounds: Option<Bound2D>,
+
+    /// The minimum number of segments in the path.
+    #[builder(default)]
+    min_length: usize,
+
+    /// The maximum number of segments in the path.
+    #[builder(default, setter(strip_option))]
+    max_length: Option<usize>,
+
+    /// The number of segments the walk aims for.
+    #[builder(default, setter(strip_option))]
+    target_length: Option<usize>,
+
+    /// The preference for turning over continuing straight.
+    #[builder(default = "0.3")]
+    turn_chance: f64,
+
+    /// The preference for moving toward the end while the walk has length to
+    /// spare.
+    #[builder(default = "0.6")]
+    target_bias: f64,
+
+    /// The number of steps (including backtracking) before giving up.
+    #[builder(default = "100_000")]
+    max_iterations: usize,
+
+    #[builder(default)]
+    avoid: FxHashSet<Point>,
+}
+
+impl SelfAvoidingPathBuilder {
+    fn validate(&self) -> Result<(), String> {
+        for (name, value) in [
+            ("turn_chance", self.turn_chance),
+            ("target_bias", self.target_bias),
+        ] {
+            if let Some(v) = value {
+                if !(0.0..=1.0).contains(&v) {
+                    return Err(format!("{} must be between 0 and 1.", name));
+                }
+            }
+        }
+
+        if let (Some(min), Some(Some(max))) = (self.min_length, self.max_length) {
+            if min > max {
+                return Err(format!("min_length ({}) > max_length ({}).", min, max));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl SelfAvoidingPath {
+    pub fn builder() -> SelfAvoidingPathBuilder {
+        SelfAvoidingPathBuilder::default()
+    }
+
+    pub fn insert_avoided(&mut self, point: Point) {
+        self.avoid.insert(point);
+    }
+
+    pub fn clear_avoided(&mut self) {
+        self.avoid.clear();
+    }
+
+    /// Generate a random path using the configuration of this generator.
+    pub fn gen_path<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<Path, SelfAvoidingPathError> {
+        for p in [self.start, self.end] {
+            if !self.allowed(&p) {
+                return Err(if self.avoid.contains(&p) {
+                    SelfAvoidingPathError::Avoided(p)
+                } else {
+                    SelfAvoidingPathError::OutOfBounds(p)
+                });
+            }
+        }
+
+        let distance = self.start.manhattan_distance(&self.end) as usize;
+        let max = self.max_length.unwrap_or(usize::MAX);
+        let mut min = self.min_length.max(distance);
+        if (min - distance) % 2 == 1 {
+            min += 1;
+        }
+
+        if min > max || (self.start == self.end && min > 0) {
+            return Err(SelfAvoidingPathError::ImpossibleLength {
+                min: self.min_length,
+                max,
+                distance,
+            });
+        }
+
+        if self.start == self.end {
+            return Ok(Path::from_iter([self.start]));
+        }
+
+        // a walled in end can't be reached, no matter how long we search
+        if !CARDINALS
+            .iter()
+            .map(|dir| step(&self.end, *dir))
+            .any(|p| p == self.start || self.allowed(&p))
+        {
+            return Err(SelfAvoidingPathError::NoPath);
+        }
+
+        let target = self.target_length.unwrap_or(min).clamp(min, max);
+        let walk = Walk {
+            config: self,
+            min,
+            max,
+            target,
+        };
+
+        walk.run(rng)
+    }
+
+    fn allowed(&self, point: &Point) -> bool {
+        !self.avoid.contains(point)
+            && self
+                .bounds
+                .as_ref()
+                .map(|b| b.contains(point))
+                .unwrap_or(true)
+    }
+}
+
+impl InputGenerator for SelfAvoidingPath {
+    type GeneratorError = SelfAvoidingPathError;
+    type Output = Path;
+
+    fn gen_input<R: Rng + Clone>(&self, rng: &mut R) -> Result<Self::Output, Self::GeneratorError> {
+        self.gen_path(rng)
+    }
+}
+
+/// The state for a single attempt at generating a path.
+struct Walk<'a> {
+    config: &'a SelfAvoidingPath,
+    min: usize,
+    max: usize,
+    target: usize,
+}
+
+impl<'a> Walk<'a> {
+    fn run<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<Path, SelfAvoidingPathError> {
+        let start = self.config.start;
+        let end = self.config.end;
+
+        let mut path = vec![start];
+        let mut visited = FxHashSet::default();
+        visited.insert(start);
+
+        // the remaining candidates for the next point at each depth
+        let mut stack = vec![self.candidates(rng, &path, &visited)];
+        let mut iterations = 0;
+
+        while let Some(candidates) = stack.last_mut() {
+            iterations += 1;
+            if iterations > self.config.max_iterations {
+                return Err(SelfAvoidingPathError::Exhausted(self.config.max_iterations));
+            }
+
+            let Some(next) = candidates.pop() else {
+                // dead end, so backtrack
+                stack.pop();
+                if let Some(p) = path.pop() {
+                    visited.remove(&p);
+                }
+                continue;
+            };
+
+            if next == end {
+                // path.len() is the number of segments once we add the end
+                if path.len() >= self.min {
+                    path.push(end);
+                    return Ok(Path::from_iter(path));
+                }
+                continue;
+            }
+
+            path.push(next);
+            visited.insert(next);
+            stack.push(self.candidates(rng, &path, &visited));
+        }
+
+        Err(SelfAvoidingPathError::NoPath)
+    }
+
+    /// Get the valid next points, ordered such that the most preferred point
+    /// is last.
+    fn candidates<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+        path: &[Point],
+        visited: &FxHashSet<Point>,
+    ) -> Vec<Point> {
+        let end = self.config.end;
+        let cur = path[path.len() - 1];
+        let prev_dir = path
+            .len()
+            .checked_sub(2)
+            .and_then(|i| path[i].cardinal_to(&cur));
+        let cur_dist = cur.manhattan_distance(&end) as usize;
+        // the number of segments once we move to the candidate
+        let steps = path.len();
+
+        let mut weighted: Vec<(f64, Point)> = Vec::with_capacity(4);
+
+        for dir in CARDINALS {
+            let next = step(&cur, dir);
+
+            if visited.contains(&next) || !self.config.allowed(&next) {
+                continue;
+            }
+
+            let dist = next.manhattan_distance(&end) as usize;
+            if steps + dist > self.max {
+                continue;
+            }
+
+            if next != end && !self.viable(&next, steps, visited) {
+                continue;
+            }
+
+            let toward = dist < cur_dist;
+            let has_slack = steps + dist < self.target;
+
+            let mut weight = match (toward, has_slack) {
+                (true, true) => self.config.target_bias,
+                (false, true) => 1.0 - self.config.target_bias,
+                (true, false) => 1.0,
+                // only as a last resort
+                (false, false) => 0.0,
+            };
+
+            if let Some(prev) = prev_dir {
+                weight *= if prev == dir {
+                    1.0 - self.config.turn_chance
+                } else {
+                    self.config.turn_chance
+                };
+            }
+
+            // weighted random ordering via keys of u^(1/w), where a weight of
+            // zero sorts to the front (least preferred)
+            let key = if weight > 0.0 {
+                rng.gen::<f64>().powf(1.0 / weight)
+            } else {
+                0.0
+            };
+
+            weighted.push((key, next));
+        }
+
+        weighted.sort_by(|a, b| a.0.total_cmp(&b.0));
+        weighted.into_iter().map(|(_, p)| p).collect()
+    }
+
+    /// Flood fill from `next` to determine if the end is still reachable and
+    /// if there are enough free points left to reach the minimum length.
+    ///
+    /// This stops as soon as both conditions are met, so it's cheap in open
+    /// areas and prevents the walk from wandering into dead-end pockets.
+    ///
+    /// Without bounds, the fill gives up and assumes the point is viable once
+    /// it has seen more points than are needed plus the diamond around `next`
+    /// that reaches the end in open space. Otherwise the fill would never
+    /// finish when the end can't be reached, and larger regions are left to
+    /// the walk itself, which is limited by `max_iterations`.
+    fn viable(&self, next: &Point, steps: usize, visited: &FxHashSet<Point>) -> bool {
+        let end = self.config.end;
+        // every remaining segment moves to a new point, including the end
+        let needed = self.min.saturating_sub(steps);
+        let radius = next.manhattan_distance(&end) as usize + 1;
+        let limit = match self.config.bounds {
+            Some(_) => usize::MAX,
+            None => needed.saturating_add(radius.saturating_mul(radius).saturating_mul(2)),
+        };
+
+        let mut seen = FxHashSet::default();
+        let mut queue = VecDeque::from([*next]);
+        seen.insert(*next);
+
+        let mut found_end = false;
+        let mut count = 0;
+
+        while let Some(cur) = queue.pop_front() {
+            for dir in CARDINALS {
+                let n = step(&cur, dir);
+
+                if seen.contains(&n) || visited.contains(&n) || !self.config.allowed(&n) {
+                    continue;
+                }
+
+                seen.insert(n);
+                count += 1;
+
+                if n == end {
+                    found_end = true;
+                } else {
+                    queue.push_back(n);
+                }
+
+                if (found_end && count >= needed) || count > limit {
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+}
+
+fn step(point: &Point, dir: Cardinal) -> Point {
+    match dir {
+        Cardinal::North => Point::new(point.x, point.y + 1),
+        Cardinal::East => Point::new(point.x + 1, point.y),
+        Cardinal::South => Point::new(point.x, point.y - 1),
+        Cardinal::West => Point::new(point.x - 1, point.y),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::path::PointPath;
+
+    use super::*;
+
+    #[test]
+    fn lengths() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let bounds = Bound2D {
+            min_x: -10,
+            max_x: 10,
+            min_y: -10,
+            max_y: 10,
+        };
+
+        for (min, max) in [(0, 4), (20, 30), (101, 121)] {
+            let generator = SelfAvoidingPath::builder()
+                .start((-4, 0))
+                .end((0, 0))
+                .bounds(bounds)
+                .min_length(min)
+                .max_length(max)
+                .target_length(max)
+                .build()
+                .unwrap();
+
+            for _ in 0..3 {
+                let p = generator.gen_path(&mut rng).unwrap();
+                let len = p.len() - 1;
+                assert!(len >= min && len <= max, "{} not in {}..={}", len, min, max);
+                assert_eq!(len % 2, 0);
+                assert!(p.is_unit());
+                assert!(p.is_simple());
+                assert!(p.points().all(|pt| bounds.contains(pt)));
+            }
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let mut rng = StdRng::seed_from_u64(5);
+
+        let generator = SelfAvoidingPath::builder()
+            .start((0, 0))
+            .end((3, 0))
+            .max_length(2)
+            .build()
+            .unwrap();
+        assert!(matches!(
+            generator.gen_path(&mut rng),
+            Err(SelfAvoidingPathError::ImpossibleLength { .. })
+        ));
+
+        let mut generator = SelfAvoidingPath::builder()
+            .start((0, 0))
+            .end((3, 0))
+            .bounds(Bound2D {
+                min_x: 0,
+                max_x: 3,
+                min_y: 0,
+                max_y: 3,
+            })
+            .build()
+            .unwrap();
+
+        // wall off the end
+        for y in 0..=3 {
+            generator.insert_avoided((2, y).into());
+        }
+        assert_eq!(
+            generator.gen_path(&mut rng),
+            Err(SelfAvoidingPathError::NoPath)
+        );
+
+        generator.insert_avoided((3, 0).into());
+        assert_eq!(
+            generator.gen_path(&mut rng),
+            Err(SelfAvoidingPathError::Avoided((3, 0).into()))
+        );
+
+        let generator = SelfAvoidingPath::builder()
+            .start((0, 0))
+            .end((1, 0))
+            .min_length(1001)
+            .max_iterations(100)
+            .build()
+            .unwrap();
+        assert_eq!(
+            generator.gen_path(&mut rng),
+            Err(SelfAvoidingPathError::Exhausted(100))
+        );
+    }
+
+    #[test]
+    fn unbounded_unreachable_end() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let mut generator = SelfAvoidingPath::builder()
+            .start((0, 0))
+            .end((5, 5))
+            .max_iterations(10)
+            .build()
+            .unwrap();
+
+        for dir in CARDINALS {
+            generator.insert_avoided(step(&(5, 5).into(), dir));
+        }
+        assert_eq!(
+            generator.gen_path(&mut rng),
+            Err(SelfAvoidingPathError::NoPath)
+        );
+
+        // a ring around the end leaves its neighbors free, but unreachable
+        generator.clear_avoided();
+        for i in 3..=7 {
+            for p in [(i, 3), (i, 7), (3, i), (7, i)] {
+                generator.insert_avoided(p.into());
+            }
+        }
+        assert_eq!(
+            generator.gen_path(&mut rng),
+            Err(SelfAvoidingPathError::Exhausted(10))
+        );
+    }
+}
@@ -4,7 +4,7 @@ use rustc_hash::FxHashSet;
 
 use crate::{bound::Bound2D, direction::Cardinal, point::Point};
 
-use super::{PathMutator, PointPath};
+use super::{Occupancy, PathMutator, PointPath, RandomPathMutator};
 
 /// Randomly adds segments to a path by inserting two additional points between
 /// existing pairs of points, making a specified number of passes through the
@@ -18,18 +18,26 @@ use super::{PathMutator, PointPath};
 /// described by two points is one unit long. If the path is a unit-path, this
 /// is guaranteed to produce paths that do not cross themselves.
 ///
-/// Because this constructs an internal hash set of points to prevent
-/// self-crossing, it is more expensive to call `.mutate(...)` multiple times
-/// than it is to set the number of passes to a value greater than one.
+/// Each pass collects the insertions and then rebuilds the path once, so a
+/// pass is linear in the length of the path. Points are tracked with an
+/// [Occupancy], which is dense within the bounds (if specified), so it is
+/// preferable to specify bounds for very long paths.
 ///
 /// Specific points can be marked as off-limits for the mutations by using the
 /// [insert_avoided] method. The set of avoided points can be cleared with
 /// [clear_avoided].
 ///
+/// The random number generator can either be owned by the adder (and used via
+/// [PathMutator]), or borrowed for each call (via [RandomPathMutator]). The
+/// latter keeps the caller's seeded stream intact, and does not require an RNG
+/// to be specified when building.
+///
 /// # Examples
 /// ```
-/// use rand::thread_rng;
-/// use proliferatr::path::{Path, PathMutator, PointPath, UnitSegmentAdder};
+/// use rand::{rngs::StdRng, thread_rng, SeedableRng};
+/// use proliferatr::path::{
+///     Path, PathMutator, PointPath, RandomPathMutator, UnitSegmentAdder,
+/// };
 ///
 /// let mut p = Path::from_iter([
 ///     (0, 0).into(),
@@ -45,11 +53,21 @@ use super::{PathMutator, PointPath};
 ///     .unwrap();
 ///
 /// adder.mutate(&mut p);
+///
+/// // borrowing the RNG instead
+/// let mut rng = StdRng::seed_from_u64(1234);
+/// let mut adder: UnitSegmentAdder = UnitSegmentAdder::builder()
+///     .passes(3)
+///     .build()
+///     .unwrap();
+///
+/// adder.mutate_with(&mut p, &mut rng);
+/// assert!(p.is_unit());
 /// ```
 #[derive(Debug, Clone, PartialEq, Builder)]
-pub struct UnitSegmentAdder<R>
+pub struct UnitSegmentAdder<R = ThreadRng>
 where
-    R: Rng + Clone + ?Sized,
+    R: Rng + Clone,
 {
     /// The optional bound for this mutator.
     #[builder(default, setter(into, strip_option))]
@@ -69,8 +87,10 @@ where
     #[builder(default = "0.5")]
     plus_bias: f64,
 
-    /// The random number generator for internal use.
-    rng: R,
+    /// The optional random number generator used by [PathMutator]. If this is
+    /// not specified, [PathMutator] uses the thread-local RNG.
+    #[builder(default, setter(strip_option))]
+    rng: Option<R>,
 
     #[builder(default)]
     avoid: FxHashSet<Point>,
@@ -83,7 +103,7 @@ impl Default for UnitSegmentAdder<ThreadRng> {
             passes: 1,
             attempt: 0.5,
             plus_bias: 0.5,
-            rng: thread_rng(),
+            rng: None,
             avoid: FxHashSet::default(),
         }
     }
@@ -91,7 +111,7 @@ impl Default for UnitSegmentAdder<ThreadRng> {
 
 impl<R> UnitSegmentAdder<R>
 where
-    R: Rng + Clone + ?Sized,
+    R: Rng + Clone,
 {
     pub fn builder() -> UnitSegmentAdderBuilder<R> {
         UnitSegmentAdderBuilder::default()
@@ -104,68 +124,154 @@ where
     pub fn clear_avoided(&mut self) {
         self.avoid.clear();
     }
+
+    /// Attempt to find a shifted copy of the segment from `p1` to `p2`.
+    fn shifted<G: Rng + ?Sized>(
+        &self,
+        mut p1: Point,
+        mut p2: Point,
+        occupied: &Occupancy,
+        rng: &mut G,
+    ) -> Option<(Point, Point)> {
+        if !rng.gen_bool(self.attempt) {
+            return None;
+        }
+
+        let delta = if rng.gen_bool(self.plus_bias) { 1 } else { -1 };
+
+        match p1.cardinal_to(&p2)? {
+            Cardinal::East | Cardinal::West => {
+                p1.y += delta;
+                p2.y += delta;
+            }
+            Cardinal::North | Cardinal::South => {
+                p1.x += delta;
+                p2.x += delta;
+            }
+        }
+
+        if let Some(ref bounds) = self.bounds {
+            if !bounds.contains(&p1) || !bounds.contains(&p2) {
+                return None;
+            }
+        }
+
+        if occupied.contains(&p1) || occupied.contains(&p2) {
+            return None;
+        }
+
+        Some((p1, p2))
+    }
 }
 
-impl<R> PathMutator for UnitSegmentAdder<R>
+impl<R> RandomPathMutator for UnitSegmentAdder<R>
 where
-    R: Rng + Clone + ?Sized,
+    R: Rng + Clone,
 {
-    fn mutate<P: PointPath>(&mut self, path: &mut P) -> bool {
-        // extend the avoid cache with the current set of points
-        self.avoid.extend(path.points().copied());
+    fn mutate_with<P: PointPath, G: Rng + ?Sized>(&mut self, path: &mut P, rng: &mut G) -> bool {
+        if path.len() < 2 {
+            return false;
+        }
+
+        let mut occupied = Occupancy::new(self.bounds);
+        occupied.extend(path.points().copied());
+        occupied.extend(self.avoid.iter().copied());
 
         let mut any_mutations = false;
 
         for _ in 0..self.passes {
-            // walk from the end of the path to the front so we can insert points
-            // without messing up our index iterator.
-            for i in (0..(path.len() - 1)).rev() {
-                let mut p1 = path.get(i).copied().unwrap();
-                let mut p2 = path.get(i + 1).copied().unwrap();
-
-                if self.rng.gen_bool(self.attempt) {
-                    if let Some(dir) = p1.cardinal_to(&p2) {
-                        match dir {
-                            Cardinal::East | Cardinal::West => {
-                                if self.rng.gen_bool(self.plus_bias) {
-                                    p1.y += 1;
-                                    p2.y += 1;
-                                } else {
-                                    p1.y -= 1;
-                                    p2.y -= 1;
-                                }
-                            }
-                            Cardinal::North | Cardinal::South => {
-                                if self.rng.gen_bool(self.plus_bias) {
-                                    p1.x += 1;
-                                    p2.x += 1;
-                                } else {
-                                    p1.x -= 1;
-                                    p2.x -= 1;
-                                }
-                            }
-                        }
-
-                        if let Some(ref bounds) = self.bounds {
-                            if !bounds.contains(&p1) || !bounds.contains(&p2) {
-                                continue;
-                            }
-                        }
-
-                        if !self.avoid.contains(&p1) && !self.avoid.contains(&p2) {
-                            self.avoid.insert(p1);
-                            self.avoid.insert(p2);
-                            any_mutations = true;
-                            // insert in reverse order because of the shifting
-                            // behavior
-                            path.insert(i + 1, p2);
-                            path.insert(i + 1, p1);
-                        }
-                    }
+            // walk from the end of the path to the front, building the new path
+            // in reverse so that each pass is linear in the length of the path
+            let len = path.len();
+            let mut rev = Vec::with_capacity(len * 2);
+            rev.push(*path.get(len - 1).unwrap());
+
+            for i in (0..(len - 1)).rev() {
+                let p1 = *path.get(i).unwrap();
+                let p2 = *path.get(i + 1).unwrap();
+
+                if let Some((s1, s2)) = self.shifted(p1, p2, &occupied, rng) {
+                    occupied.insert(s1);
+                    occupied.insert(s2);
+                    rev.push(s2);
+                    rev.push(s1);
+                    any_mutations = true;
                 }
+
+                rev.push(p1);
+            }
+
+            if rev.len() != len {
+                path.replace_points(rev.into_iter().rev());
             }
         }
 
         any_mutations
     }
 }
+
+impl<R> PathMutator for UnitSegmentAdder<R>
+where
+    R: Rng + Clone,
+{
+    fn mutate<P: PointPath>(&mut self, path: &mut P) -> bool {
+        match self.rng.take() {
+            Some(mut rng) => {
+                let mutated = self.mutate_with(path, &mut rng);
+                self.rng = Some(rng);
+                mutated
+            }
+            None => self.mutate_with(path, &mut thread_rng()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, RngCore, SeedableRng};
+
+    use crate::path::ClosedPath;
+
+    use super::*;
+
+    #[test]
+    fn borrowed_rng_is_reproducible() {
+        let mut adder: UnitSegmentAdder = UnitSegmentAdder::builder().passes(5).build().unwrap();
+
+        let mut a = ClosedPath::rect_path(10, 10).unwrap();
+        let mut b = a.clone();
+        let mut rng_a = StdRng::seed_from_u64(35);
+        let mut rng_b = StdRng::seed_from_u64(35);
+
+        assert!(adder.mutate_with(&mut a, &mut rng_a));
+        assert!(adder.mutate_with(&mut b, &mut rng_b));
+        assert_eq!(a, b);
+
+        // the caller's stream was advanced by the same amount
+        assert_eq!(rng_a.next_u64(), rng_b.next_u64());
+        assert_ne!(rng_a.next_u64(), StdRng::seed_from_u64(35).next_u64());
+    }
+
+    #[test]
+    fn large_paths() {
+        let bounds = Bound2D {
+            min_x: -500,
+            max_x: 600,
+            min_y: -500,
+            max_y: 600,
+        };
+        let mut adder: UnitSegmentAdder = UnitSegmentAdder::builder()
+            .passes(100)
+            .bounds(bounds)
+            .build()
+            .unwrap();
+
+        let mut p = ClosedPath::rect_path(100, 100).unwrap();
+        adder.mutate_with(&mut p, &mut StdRng::seed_from_u64(1));
+
+        assert!(p.len() > 20_000);
+        assert!(p.is_closed());
+        assert!(p.is_unit());
+        assert!(p.is_simple());
+    }
+}
@@ -0,0 +1,398 @@
+use derive_builder::Builder;
+use rand::{distributions::WeightedIndex, prelude::Distribution, rngs::ThreadRng, thread_rng, Rng};
+use rustc_hash::FxHashSet;
+
+use crate::{bound::Bound2D, direction::Cardinal, point::Point};
+
+use super::{Occupancy, PathMutator, PointPath, RandomPathMutator};
+
+/// Randomly expands or contracts a unit-path, making a specified number of
+/// passes through the segments that comprise the path.
+///
+/// For each segment, this picks one of three alterations using the configured
+/// weights:
+///
+/// * expand: shift the segment one unit to one side, joining it back to the
+///   path by inserting two additional points (like [UnitSegmentAdder]).
+/// * contract: if the segment is the middle of a one unit wide "bump" (the
+///   points either side of the segment are themselves adjacent), remove the
+///   bump by removing the two points of the segment.
+/// * nothing: leave the segment alone.
+///
+/// This works for both open and closed paths. The first and last points of the
+/// path are never moved or removed, and the optional `end_margin` can be used
+/// to leave additional segments at either end of the path unaltered.
+///
+/// If the bounds are specified, this will skip expansions that would lie
+/// outside of those bounds. Specific points can be marked as off-limits for
+/// expansions by using the [UnitSegmentAlterer::insert_avoided] method.
+///
+/// As with [UnitSegmentAdder], the random number generator can either be owned
+/// (and used via [PathMutator]) or borrowed for each call (via
+/// [RandomPathMutator]), and each pass is linear in the length of the path.
+///
+/// This also requires that the path being mutated is a unit-path. If it is,
+/// the result is guaranteed to be a unit-path that does not cross itself.
+///
+/// # Examples
+/// ```
+/// use rand::thread_rng;
+/// use proliferatr::path::{ClosedPath, PathMutator, PointPath, UnitSegmentAlterer};
+///
+/// let mut p = ClosedPath::rect_path(10, 10).unwrap();
+///
+/// let mut alterer = UnitSegmentAlterer::builder()
+///     .passes(20)
+///     .expand_weight(2)
+///     .contract_weight(1)
+///     .nothing_weight(1)
+///     .rng(thread_rng())
+///     .build()
+///     .unwrap();
+///
+/// alterer.mutate(&mut p);
+///
+/// assert!(p.is_closed());
+/// assert!(p.is_unit());
+/// assert!(p.is_simple());
+/// ```
+///
+/// [UnitSegmentAdder]: super::UnitSegmentAdder
+#[derive(Debug, Clone, PartialEq, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct UnitSegmentAlterer<R = ThreadRng>
+where
+    R: Rng + Clone,
+{
+    /// The optional bound for this mutator.
+    #[builder(default, setter(into, strip_option))]
+    bounds: Option<Bound2D>,
+
+    /// The number of iterations through the list of points.
+    #[builder(default = "1")]
+    passes: usize,
+
+    /// The relative weight of attempting to expand a segment.
+    #[builder(default = "1")]
+    expand_weight: u32,
+
+    /// The relative weight of attempting to contract a segment.
+    #[builder(default = "1")]
+    contract_weight: u32,
+
+    /// The relative weight of leaving a segment alone.
+    #[builder(default = "1")]
+    nothing_weight: u32,
+
+    /// The probability that an expanded segment will be shifted in the
+    /// positive direction.
+    #[builder(default = "0.5")]
+    plus_bias: f64,
+
+    /// The number of segments at each end of the path to leave unaltered.
+    #[builder(default)]
+    end_margin: usize,
+
+    /// The optional random number generator used by [PathMutator]. If this is
+    /// not specified, [PathMutator] uses the thread-local RNG.
+    #[builder(default, setter(strip_option))]
+    rng: Option<R>,
+
+    #[builder(default)]
+    avoid: FxHashSet<Point>,
+}
+
+impl Default for UnitSegmentAlterer<ThreadRng> {
+    fn default() -> Self {
+        Self {
+            bounds: None,
+            passes: 1,
+            expand_weight: 1,
+            contract_weight: 1,
+            nothing_weight: 1,
+            plus_bias: 0.5,
+            end_margin: 0,
+            rng: None,
+            avoid: FxHashSet::default(),
+        }
+    }
+}
+
+impl<R> UnitSegmentAltererBuilder<R>
+where
+    R: Rng + Clone,
+{
+    fn validate(&self) -> Result<(), String> {
+        let expand = self.expand_weight.unwrap_or(1);
+        let contract = self.contract_weight.unwrap_or(1);
+        let nothing = self.nothing_weight.unwrap_or(1);
+
+        if expand == 0 && contract == 0 && nothing == 0 {
+            return Err("At least one weight must be greater than 0.".into());
+        }
+
+        if let Some(plus_bias) = self.plus_bias {
+            if !(0.0..=1.0).contains(&plus_bias) {
+                return Err("plus_bias must be between 0 and 1.".into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<R> UnitSegmentAlterer<R>
+where
+    R: Rng + Clone,
+{
+    pub fn builder() -> UnitSegmentAltererBuilder<R> {
+        UnitSegmentAltererBuilder::default()
+    }
+
+    pub fn insert_avoided(&mut self, point: Point) {
+        self.avoid.insert(point);
+    }
+
+    pub fn clear_avoided(&mut self) {
+        self.avoid.clear();
+    }
+
+    /// Attempt to find a shifted copy of the segment from `p1` to `p2`.
+    fn expand<G: Rng + ?Sized>(
+        &self,
+        mut p1: Point,
+        mut p2: Point,
+        occupied: &Occupancy,
+        rng: &mut G,
+    ) -> Option<(Point, Point)> {
+        let dir = p1.cardinal_to(&p2)?;
+
+        let delta = if rng.gen_bool(self.plus_bias) { 1 } else { -1 };
+
+        match dir {
+            Cardinal::East | Cardinal::West => {
+                p1.y += delta;
+                p2.y += delta;
+            }
+            Cardinal::North | Cardinal::South => {
+                p1.x += delta;
+                p2.x += delta;
+            }
+        }
+
+        if let Some(ref bounds) = self.bounds {
+            if !bounds.contains(&p1) || !bounds.contains(&p2) {
+                return None;
+            }
+        }
+
+        if occupied.contains(&p1)
+            || occupied.contains(&p2)
+            || self.avoid.contains(&p1)
+            || self.avoid.contains(&p2)
+        {
+            return None;
+        }
+
+        Some((p1, p2))
+    }
+}
+
+impl<R> RandomPathMutator for UnitSegmentAlterer<R>
+where
+    R: Rng + Clone,
+{
+    fn mutate_with<P: PointPath, G: Rng + ?Sized>(&mut self, path: &mut P, rng: &mut G) -> bool {
+        if path.len() < 2 {
+            return false;
+        }
+
+        // a closed path needs at least four distinct points to remain a
+        // polygon on the latice
+        let min_len = if path.get(0) == path.get(path.len() - 1) {
+            5
+        } else {
+            2
+        };
+
+        let dist = WeightedIndex::new([
+            self.expand_weight,
+            self.contract_weight,
+            self.nothing_weight,
+        ])
+        .expect("weights validated by builder");
+
+        let mut occupied = Occupancy::new(self.bounds);
+        occupied.extend(path.points().copied());
+
+        let mut any_mutations = false;
+
+        for _ in 0..self.passes {
+            let len = path.len();
+            if len < 2 * self.end_margin + 2 {
+                break;
+            }
+
+            // walk from the end of the path to the front. Everything after the
+            // current point `i` is kept on a stack (so the top is the point
+            // after `i`), and everything before it is untouched, so each pass
+            // is linear in the length of the path.
+            let last = len - 2 - self.end_margin;
+            let mut rev: Vec<Point> = ((last + 1)..len)
+                .rev()
+                .map(|j| *path.get(j).unwrap())
+                .collect();
+            let mut mutated = false;
+
+            for i in (self.end_margin..=last).rev() {
+                let cur = *path.get(i).unwrap();
+                let next = *rev.last().unwrap();
+
+                match dist.sample(rng) {
+                    0 => {
+                        if let Some((s1, s2)) = self.expand(cur, next, &occupied, rng) {
+                            occupied.insert(s1);
+                            occupied.insert(s2);
+                            rev.push(s2);
+                            rev.push(s1);
+                            mutated = true;
+                        }
+                    }
+                    1 => {
+                        // this alters the segments on either side of `i`, so
+                        // those must be outside of the margins
+                        let cur_len = i + 1 + rev.len();
+                        if i > self.end_margin
+                            && i + 2 + self.end_margin < cur_len
+                            && cur_len - 2 >= min_len
+                            && path
+                                .get(i - 1)
+                                .unwrap()
+                                .manhattan_distance(&rev[rev.len() - 2])
+                                == 1
+                        {
+                            // remove the bump formed by `i - 1` through `i + 2`
+                            occupied.remove(&cur);
+                            occupied.remove(&next);
+                            rev.pop();
+                            mutated = true;
+                            continue;
+                        }
+                    }
+                    _ => {}
+                }
+
+                rev.push(cur);
+            }
+
+            if mutated {
+                let head = (0..self.end_margin).rev().map(|j| *path.get(j).unwrap());
+                rev.extend(head);
+                path.replace_points(rev.into_iter().rev());
+                any_mutations = true;
+            }
+        }
+
+        any_mutations
+    }
+}
+
+impl<R> PathMutator for UnitSegmentAlterer<R>
+where
+    R: Rng + Clone,
+{
+    fn mutate<P: PointPath>(&mut self, path: &mut P) -> bool {
+        match self.rng.take() {
+            Some(mut rng) => {
+                let mutated = self.mutate_with(path, &mut rng);
+                self.rng = Some(rng);
+                mutated
+            }
+            None => self.mutate_with(path, &mut thread_rng()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::path::{ClosedPath, Path};
+
+    use super::*;
+
+    #[test]
+    fn builder_validation() {
+        let a = UnitSegmentAlterer::builder()
+            .expand_weight(0)
+            .contract_weight(0)
+            .nothing_weight(0)
+            .rng(thread_rng())
+            .build();
+        assert!(a.is_err());
+
+        let a = UnitSegmentAlterer::builder()
+            .plus_bias(1.5)
+            .rng(thread_rng())
+            .build();
+        assert!(a.is_err());
+    }
+
+    #[test]
+    fn contracting() {
+        let mut p = ClosedPath::rect_path(20, 20).unwrap();
+        let mut expander = UnitSegmentAlterer::builder()
+            .passes(10)
+            .contract_weight(0)
+            .rng(StdRng::seed_from_u64(10))
+            .build()
+            .unwrap();
+        expander.mutate(&mut p);
+        let expanded_len = p.len();
+
+        let mut contractor = UnitSegmentAlterer::builder()
+            .passes(10)
+            .expand_weight(0)
+            .nothing_weight(0)
+            .rng(StdRng::seed_from_u64(10))
+            .build()
+            .unwrap();
+        assert!(contractor.mutate(&mut p));
+        assert!(p.len() < expanded_len);
+        assert!(p.is_closed());
+        assert!(p.is_unit());
+        assert!(p.is_simple());
+    }
+
+    #[test]
+    fn open_path_respects_bounds_and_margins() {
+        let mut p: Path = (0..10).map(|x| Point::new(x, 0)).collect();
+        let bounds = Bound2D {
+            min_x: 0,
+            max_x: 9,
+            min_y: -3,
+            max_y: 3,
+        };
+
+        let mut alterer = UnitSegmentAlterer::builder()
+            .passes(30)
+            .bounds(bounds)
+            .end_margin(2)
+            .rng(StdRng::seed_from_u64(3))
+            .build()
+            .unwrap();
+        alterer.mutate(&mut p);
+
+        assert!(p.is_unit());
+        assert!(p.is_simple());
+        assert!(p.points().all(|pt| bounds.contains(pt)));
+
+        for x in 0..3 {
+            assert_eq!(p.get(x as usize).copied(), Some(Point::new(x, 0)));
+            assert_eq!(
+                p.get(p.len() - 1 - x as usize).copied(),
+                Some(Point::new(9 - x, 0))
+            );
+        }
+    }
+}
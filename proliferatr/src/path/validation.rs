@@ -0,0 +1,299 @@
+use rustc_hash::FxHashSet;
+use thiserror::Error;
+
+use crate::point::Point;
+
+use super::PointPath;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ValidationError {
+    #[error("Path of {len} points is too short to be closed.")]
+    TooShort { len: usize },
+
+    #[error("Path has {distinct} distinct points, but a closed path needs at least 3.")]
+    TooFewDistinctPoints { distinct: usize },
+
+    #[error("Path is not closed: first point {first:?} != last point {last:?}.")]
+    NotClosed { first: Point, last: Point },
+
+    #[error("Segment {idx} has zero length.")]
+    ZeroLengthSegment { idx: usize },
+
+    #[error("Segment {idx} ({from:?} -> {to:?}) is not horizontal or vertical.")]
+    NonLaticeSegment { idx: usize, from: Point, to: Point },
+
+    #[error("Segment {idx} has length {length}, not 1.")]
+    NonUnitSegment { idx: usize, length: i64 },
+
+    #[error("Segments {first} and {second} intersect.")]
+    SelfIntersection { first: usize, second: usize },
+}
+
+// Segment indices refer to the segment starting at the point with that index.
+
+pub(super) fn check_closed<P: PointPath + ?Sized>(path: &P) -> Result<(), ValidationError> {
+    let len = path.len();
+
+    // at least three segments, so three points plus the duplicated start
+    if len < 4 {
+        return Err(ValidationError::TooShort { len });
+    }
+
+    let first = *path.get(0).unwrap();
+    let last = *path.get(len - 1).unwrap();
+
+    if first != last {
+        return Err(ValidationError::NotClosed { first, last });
+    }
+
+    let distinct = path.points().collect::<FxHashSet<_>>().len();
+    if distinct < 3 {
+        return Err(ValidationError::TooFewDistinctPoints { distinct });
+    }
+
+    Ok(())
+}
+
+pub(super) fn check_lattice<P: PointPath + ?Sized>(path: &P) -> Result<(), ValidationError> {
+    for (idx, (from, to)) in segments(path).enumerate() {
+        if from == to {
+            return Err(ValidationError::ZeroLengthSegment { idx });
+        }
+
+        if from.cardinal_to(&to).is_none() {
+            return Err(ValidationError::NonLaticeSegment { idx, from, to });
+        }
+    }
+
+    Ok(())
+}
+
+pub(super) fn check_unit<P: PointPath + ?Sized>(path: &P) -> Result<(), ValidationError> {
+    check_lattice(path)?;
+
+    for (idx, (from, to)) in segments(path).enumerate() {
+        let length = from.manhattan_distance(&to);
+        if length != 1 {
+            return Err(ValidationError::NonUnitSegment { idx, length });
+        }
+    }
+
+    Ok(())
+}
+
+/// Sweep through the segments ordered by their smallest x value, only testing
+/// pairs of segments whose bounding boxes overlap.
+///
+/// Segments that share an endpoint in the path (including the first and last
+/// segments of a closed path) may only touch at that endpoint.
+pub(super) fn check_simple<P: PointPath + ?Sized>(path: &P) -> Result<(), ValidationError> {
+    let segs: Vec<(Point, Point)> = segments(path).collect();
+    let num = segs.len();
+
+    if let Some(idx) = segs.iter().position(|(a, b)| a == b) {
+        return Err(ValidationError::ZeroLengthSegment { idx });
+    }
+
+    let closed = num > 1 && segs[0].0 == segs[num - 1].1;
+
+    let mut order: Vec<usize> = (0..num).collect();
+    order.sort_unstable_by_key(|&i| segs[i].0.x.min(segs[i].1.x));
+
+    for (pos, &i) in order.iter().enumerate() {
+        let (a, b) = segs[i];
+        let max_x = a.x.max(b.x);
+        let (min_y, max_y) = (a.y.min(b.y), a.y.max(b.y));
+
+        for &j in order[(pos + 1)..].iter() {
+            let (c, d) = segs[j];
+
+            if c.x.min(d.x) > max_x {
+                break;
+            }
+
+            if c.y.max(d.y) < min_y || c.y.min(d.y) > max_y {
+                continue;
+            }
+
+            let (first, second) = (i.min(j), i.max(j));
+            let adjacent = second == first + 1 || (closed && first == 0 && second == num - 1);
+
+            let intersects = if adjacent {
+                // normalize so the shared point is `b` of `first` and `c` of
+                // `second`, except when wrapping around a closed path
+                let (shared, p, q) = if second == first + 1 {
+                    (segs[first].1, segs[first].0, segs[second].1)
+                } else {
+                    (segs[first].0, segs[first].1, segs[second].0)
+                };
+
+                if num == 2 && closed {
+                    // a path that goes out and back along the same segment
+                    true
+                } else {
+                    overlaps_beyond(shared, p, q)
+                }
+            } else {
+                segments_intersect(a, b, c, d)
+            };
+
+            if intersects {
+                return Err(ValidationError::SelfIntersection { first, second });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn segments<P: PointPath + ?Sized>(path: &P) -> impl Iterator<Item = (Point, Point)> + '_ {
+    (1..path.len()).map(|i| (*path.get(i - 1).unwrap(), *path.get(i).unwrap()))
+}
+
+/// The sign of the cross product of `b - a` and `c - a`.
+fn orientation(a: Point, b: Point, c: Point) -> i8 {
+    let v = (b.x - a.x) as i128 * (c.y - a.y) as i128 - (b.y - a.y) as i128 * (c.x - a.x) as i128;
+    v.signum() as i8
+}
+
+/// Returns `true` if `p` lies within the bounding box of `a` and `b`. Only
+/// meaningful if the three points are collinear.
+fn on_segment(a: Point, b: Point, p: Point) -> bool {
+    a.x.min(b.x) <= p.x && p.x <= a.x.max(b.x) && a.y.min(b.y) <= p.y && p.y <= a.y.max(b.y)
+}
+
+fn segments_intersect(a: Point, b: Point, c: Point, d: Point) -> bool {
+    let o1 = orientation(a, b, c);
+    let o2 = orientation(a, b, d);
+    let o3 = orientation(c, d, a);
+    let o4 = orientation(c, d, b);
+
+    if o1 != o2 && o3 != o4 && o1 != 0 && o2 != 0 && o3 != 0 && o4 != 0 {
+        return true;
+    }
+
+    (o1 == 0 && on_segment(a, b, c))
+        || (o2 == 0 && on_segment(a, b, d))
+        || (o3 == 0 && on_segment(c, d, a))
+        || (o4 == 0 && on_segment(c, d, b))
+}
+
+/// Two segments `shared -> p` and `shared -> q` sharing a point intersect
+/// elsewhere only if they are collinear and point the same way.
+fn overlaps_beyond(shared: Point, p: Point, q: Point) -> bool {
+    if orientation(shared, p, q) != 0 {
+        return false;
+    }
+
+    let dot = (p.x - shared.x) as i128 * (q.x - shared.x) as i128
+        + (p.y - shared.y) as i128 * (q.y - shared.y) as i128;
+
+    dot > 0
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::path::{ClosedPath, Path, PathCondenser, PathMutator, UnitSegmentAdder};
+
+    use super::*;
+
+    fn path(points: &[(i64, i64)]) -> Path {
+        points.iter().map(|p| Point::from(*p)).collect()
+    }
+
+    #[test]
+    fn lattice_and_unit() {
+        let p = path(&[(0, 0), (0, 1), (1, 1), (3, 1)]);
+        assert!(p.is_lattice());
+        assert_eq!(
+            p.check_unit(),
+            Err(ValidationError::NonUnitSegment { idx: 2, length: 2 })
+        );
+
+        let p = path(&[(0, 0), (0, 1), (1, 2)]);
+        assert!(matches!(
+            p.check_lattice(),
+            Err(ValidationError::NonLaticeSegment { idx: 1, .. })
+        ));
+
+        let p = path(&[(0, 0), (0, 1), (0, 1)]);
+        assert_eq!(
+            p.check_lattice(),
+            Err(ValidationError::ZeroLengthSegment { idx: 1 })
+        );
+    }
+
+    #[test]
+    fn closed() {
+        assert!(ClosedPath::rect_path(2, 2).unwrap().is_closed());
+
+        let p = path(&[(0, 0), (0, 1), (0, 0)]);
+        assert_eq!(p.check_closed(), Err(ValidationError::TooShort { len: 3 }));
+
+        let p = path(&[(0, 0), (0, 1), (1, 1), (1, 0)]);
+        assert!(matches!(
+            p.check_closed(),
+            Err(ValidationError::NotClosed { .. })
+        ));
+
+        let p = path(&[(0, 0), (0, 1), (0, 0), (0, 1), (0, 0)]);
+        assert_eq!(
+            p.check_closed(),
+            Err(ValidationError::TooFewDistinctPoints { distinct: 2 })
+        );
+    }
+
+    #[test]
+    fn simple() {
+        // backtracking along the previous segment
+        let p = path(&[(0, 0), (0, 3), (0, 2)]);
+        assert_eq!(
+            p.check_simple(),
+            Err(ValidationError::SelfIntersection {
+                first: 0,
+                second: 1
+            })
+        );
+
+        // touching an earlier vertex
+        let p = path(&[(0, 0), (2, 0), (2, 2), (1, 2), (1, 0)]);
+        assert_eq!(
+            p.check_simple(),
+            Err(ValidationError::SelfIntersection {
+                first: 0,
+                second: 3
+            })
+        );
+
+        // crossing diagonals
+        let p = path(&[(0, 0), (2, 2), (2, 0), (0, 2)]);
+        assert_eq!(
+            p.check_simple(),
+            Err(ValidationError::SelfIntersection {
+                first: 0,
+                second: 2
+            })
+        );
+
+        // the closing segment doubles back over the first one
+        let p = path(&[(0, 0), (0, 2), (1, 2), (1, 1), (0, 1), (0, 0)]);
+        assert!(!p.is_simple());
+
+        let mut p = ClosedPath::rect_path(20, 20).unwrap();
+        let mut adder = UnitSegmentAdder::builder()
+            .passes(10)
+            .rng(StdRng::seed_from_u64(42))
+            .build()
+            .unwrap();
+        adder.mutate(&mut p);
+        assert!(p.is_unit());
+        assert!(p.is_simple());
+
+        PathCondenser::default().mutate(&mut p);
+        assert!(!p.is_unit());
+        assert!(p.is_lattice());
+        assert!(p.is_simple());
+    }
+}
@@ -0,0 +1,422 @@
+use std::{collections::HashSet, ops::Range};
+
+use derive_builder::Builder;
+use rand::{seq::SliceRandom, Rng};
+
+use crate::{
+    generic::{StringToken, UniqueTokens},
+    InputGenerator,
+};
+
+use super::{Comparison, Condition, Rule, Target, Workflow, WorkflowError, WorkflowSet};
+
+/// The targets of a workflow, with the ranges of values that reach each one.
+type Reached = Vec<(Target, Vec<Range<u64>>)>;
+
+/// Generates trees of workflows, where the workflows of each layer send parts
+/// to the workflows of the next, and the workflows of the last layer send
+/// parts to either accept or reject.
+///
+/// The first layer is the single `start` workflow, and the size of each of
+/// the following `layers` is chosen from its range. Every workflow of a layer
+/// is the target of exactly one rule or fallthrough of the layer above, with
+/// each workflow above getting a fallthrough first and the rest of the
+/// targets handed out in turn as rules. Because every workflow needs a
+/// fallthrough, no layer can be smaller than the one above it. Each workflow
+/// of the last layer gets a number of rules chosen from `terminal_rules`, and
+/// every one of its targets is accept with the `accept_probability`.
+///
+/// Conditions compare a random variable to a value from `thresholds`, using
+/// `<` with the `less_probability`. Each condition is chosen so that it
+/// splits the values that can still reach its rule, so every workflow can be
+/// reached by some part. The generated set is verified by splitting the
+/// ranges of values, and is only kept if no workflow is unreachable and the
+/// fraction of parts that are accepted is in the `accepted_fraction` range.
+///
+/// # Examples
+/// ```
+/// use rand::thread_rng;
+/// use proliferatr::workflow::LayeredWorkflows;
+///
+/// let set = LayeredWorkflows::builder()
+///     .layer(2..4)
+///     .layer(5..8)
+///     .accepted_fraction(0.1..0.9)
+///     .build()
+///     .unwrap()
+///     .generate(&mut thread_rng())
+///     .unwrap();
+///
+/// assert!(set.workflows.len() >= 8);
+/// assert!(set.unreachable().unwrap().is_empty());
+///
+/// let fraction = set.accepted_volume().unwrap() as f64 / set.total_volume() as f64;
+/// assert!((0.1..0.9).contains(&fraction));
+/// ```
+#[derive(Debug, Clone, PartialEq, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct LayeredWorkflows {
+    #[builder(default = "vec!['x', 'm', 'a', 's']", setter(into))]
+    variables: Vec<char>,
+
+    #[builder(default = "1..4001")]
+    values: Range<u64>,
+
+    #[builder(default = "1000..3001")]
+    thresholds: Range<u64>,
+
+    #[builder(default = "0.5")]
+    less_probability: f64,
+
+    #[builder(setter(each(name = "layer")))]
+    layers: Vec<Range<usize>>,
+
+    #[builder(default = "1..3")]
+    terminal_rules: Range<usize>,
+
+    #[builder(default = "0.5")]
+    accept_probability: f64,
+
+    #[builder(default = "0.0..1.0")]
+    accepted_fraction: Range<f64>,
+
+    #[builder(default = "\"in\".into()", setter(into))]
+    start: String,
+
+    /// Generates the names of the workflows other than `start`.
+    #[builder(default)]
+    names: StringToken,
+
+    #[builder(default = "100")]
+    attempts: usize,
+}
+
+impl LayeredWorkflowsBuilder {
+    fn validate(&self) -> Result<(), String> {
+        if let Some(variables) = &self.variables {
+            let unique: HashSet<&char> = variables.iter().collect();
+            if variables.is_empty() || unique.len() != variables.len() {
+                return Err("variables must be non-empty and unique.".into());
+            }
+        }
+
+        if let Some(values) = &self.values {
+            if values.end <= values.start + 1 {
+                return Err("values must contain at least two values.".into());
+            }
+        }
+
+        if self.thresholds.as_ref().map(|t| t.is_empty()) == Some(true) {
+            return Err("thresholds must not be empty.".into());
+        }
+
+        for p in [self.less_probability, self.accept_probability]
+            .into_iter()
+            .flatten()
+        {
+            if !(0.0..=1.0).contains(&p) {
+                return Err("probabilities must be between 0 and 1.".into());
+            }
+        }
+
+        if let Some(layers) = &self.layers {
+            if layers.is_empty() || layers.iter().any(|l| l.is_empty() || l.start == 0) {
+                return Err("There must be at least one layer, and no layer can be empty.".into());
+            }
+
+            if layers.windows(2).any(|w| w[1].start < w[0].end - 1) {
+                return Err("No layer can be smaller than the layer above it.".into());
+            }
+        }
+
+        if self.terminal_rules.as_ref().map(|r| r.is_empty()) == Some(true) {
+            return Err("terminal_rules must not be empty.".into());
+        }
+
+        if let Some(fraction) = &self.accepted_fraction {
+            if fraction.start < 0.0 || fraction.end > 1.0 || fraction.start >= fraction.end {
+                return Err("accepted_fraction must be a range within 0 and 1.".into());
+            }
+        }
+
+        if self.names.as_ref().map(|n| *n.length().start() == 0) == Some(true) {
+            return Err("names must not generate empty names.".into());
+        }
+
+        if self.attempts == Some(0) {
+            return Err("attempts must be greater than 0.".into());
+        }
+
+        Ok(())
+    }
+}
+
+impl LayeredWorkflows {
+    pub fn builder() -> LayeredWorkflowsBuilder {
+        LayeredWorkflowsBuilder::default()
+    }
+
+    /// Generate a new set of workflows.
+    pub fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<WorkflowSet, WorkflowError> {
+        for _ in 0..self.attempts {
+            let Some(set) = self.make_set(rng)? else {
+                continue;
+            };
+
+            if !set.unreachable()?.is_empty() {
+                continue;
+            }
+
+            let fraction = set.accepted_volume()? as f64 / set.total_volume() as f64;
+            if self.accepted_fraction.contains(&fraction) {
+                return Ok(set);
+            }
+        }
+
+        Err(WorkflowError::AttemptsExhausted(self.attempts))
+    }
+
+    /// Make a single set of workflows, which still needs to be verified, or
+    /// `None` if some workflow could not be given all of its rules.
+    fn make_set<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<Option<WorkflowSet>, WorkflowError> {
+        let mut names = UniqueTokens::builder()
+            .token(self.names.clone())
+            .reserve(self.start.clone())
+            .reserve("A")
+            .reserve("R")
+            .build()
+            .expect("there are no reserved affixes");
+        let full = vec![self.values.clone(); self.variables.len()];
+
+        // each layer is a list of names and the ranges of values reaching them
+        let mut layer = vec![(self.start.clone(), full)];
+        let mut workflows = Vec::new();
+
+        let sizes: Vec<usize> = self
+            .layers
+            .iter()
+            .map(|l| rng.gen_range(l.clone()))
+            .collect();
+        for size in sizes {
+            let mut children = Vec::with_capacity(size);
+            for _ in 0..size {
+                children.push(Target::Workflow(names.next(rng)?));
+            }
+            children.shuffle(rng);
+
+            // every parent gets one fallthrough, and the rest are dealt out as
+            // rules
+            let mut targets: Vec<Vec<Target>> = vec![Vec::new(); layer.len()];
+            for (idx, child) in children.into_iter().enumerate() {
+                targets[idx % layer.len()].push(child);
+            }
+
+            let mut next = Vec::with_capacity(size);
+            for ((name, ranges), mut targets) in layer.into_iter().zip(targets) {
+                let fallthrough = targets.remove(0);
+                let Some((workflow, reached)) =
+                    self.make_workflow(name, ranges, targets, fallthrough, rng)
+                else {
+                    return Ok(None);
+                };
+
+                for (target, ranges) in reached {
+                    if let Target::Workflow(name) = target {
+                        next.push((name, ranges));
+                    }
+                }
+                workflows.push(workflow);
+            }
+
+            layer = next;
+        }
+
+        for (name, ranges) in layer {
+            let targets = (0..rng.gen_range(self.terminal_rules.clone()))
+                .map(|_| self.make_terminal(rng))
+                .collect();
+            let fallthrough = self.make_terminal(rng);
+            let Some((workflow, _)) = self.make_workflow(name, ranges, targets, fallthrough, rng)
+            else {
+                return Ok(None);
+            };
+            workflows.push(workflow);
+        }
+
+        workflows.shuffle(rng);
+
+        let mut set = WorkflowSet::new(self.variables.clone(), self.values.clone(), &self.start);
+        set.workflows = workflows;
+
+        Ok(Some(set))
+    }
+
+    /// Make a workflow sending parts to each of the `targets` in turn, and to
+    /// the `fallthrough` otherwise, given the `ranges` of values that reach
+    /// it. Along with the workflow, this returns the ranges of values that
+    /// reach each target.
+    ///
+    /// Returns `None` if no condition can split the values left for a rule.
+    fn make_workflow<R: Rng + ?Sized>(
+        &self,
+        name: String,
+        mut ranges: Vec<Range<u64>>,
+        targets: Vec<Target>,
+        fallthrough: Target,
+        rng: &mut R,
+    ) -> Option<(Workflow, Reached)> {
+        let mut rules = Vec::with_capacity(targets.len());
+        let mut reached = Vec::with_capacity(targets.len() + 1);
+
+        for target in targets {
+            let (idx, condition) = self.make_condition(&ranges, rng)?;
+
+            let (pass, fail) = condition.split(&ranges[idx]);
+            let mut passed = ranges.clone();
+            passed[idx] = pass;
+            ranges[idx] = fail;
+
+            rules.push(Rule {
+                condition,
+                target: target.clone(),
+            });
+            reached.push((target, passed));
+        }
+
+        reached.push((fallthrough.clone(), ranges));
+
+        Some((
+            Workflow {
+                name,
+                rules,
+                fallthrough,
+            },
+            reached,
+        ))
+    }
+
+    /// Make a condition that splits the `ranges` of values into two non-empty
+    /// parts, returning it with the index of its variable.
+    fn make_condition<R: Rng + ?Sized>(
+        &self,
+        ranges: &[Range<u64>],
+        rng: &mut R,
+    ) -> Option<(usize, Condition)> {
+        let comparison = if rng.gen_bool(self.less_probability) {
+            Comparison::Less
+        } else {
+            Comparison::Greater
+        };
+
+        let mut order: Vec<usize> = (0..self.variables.len()).collect();
+        order.shuffle(rng);
+
+        for idx in order {
+            let range = &ranges[idx];
+            // values that leave something on both sides of the split
+            let valid = match comparison {
+                Comparison::Less => (range.start + 1)..range.end,
+                Comparison::Greater => range.start..range.end.saturating_sub(1),
+            };
+            let start = valid.start.max(self.thresholds.start);
+            let end = valid.end.min(self.thresholds.end);
+
+            if start < end {
+                return Some((
+                    idx,
+                    Condition {
+                        variable: self.variables[idx],
+                        comparison,
+                        value: rng.gen_range(start..end),
+                    },
+                ));
+            }
+        }
+
+        None
+    }
+
+    fn make_terminal<R: Rng + ?Sized>(&self, rng: &mut R) -> Target {
+        if rng.gen_bool(self.accept_probability) {
+            Target::Accept
+        } else {
+            Target::Reject
+        }
+    }
+}
+
+impl InputGenerator for LayeredWorkflows {
+    type GeneratorError = WorkflowError;
+    type Output = WorkflowSet;
+
+    fn gen_input<R: Rng + Clone>(&self, rng: &mut R) -> Result<Self::Output, Self::GeneratorError> {
+        self.generate(rng)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn layers() {
+        let mut rng = StdRng::seed_from_u64(19);
+        let generator = LayeredWorkflows::builder()
+            .layer(2..6)
+            .layer(9..15)
+            .layer(40..51)
+            .accepted_fraction(0.2..0.8)
+            .build()
+            .unwrap();
+
+        for _ in 0..5 {
+            let set = generator.generate(&mut rng).unwrap();
+            assert!((52..=72).contains(&set.workflows.len()));
+            assert!(set.unreachable().unwrap().is_empty());
+
+            let fraction = set.accepted_volume().unwrap() as f64 / set.total_volume() as f64;
+            assert!((0.2..0.8).contains(&fraction));
+
+            // every workflow but the start is the target of exactly one rule
+            let mut targets: Vec<&str> = set
+                .workflows
+                .iter()
+                .flat_map(|w| {
+                    w.rules
+                        .iter()
+                        .map(|r| &r.target)
+                        .chain(std::iter::once(&w.fallthrough))
+                })
+                .filter_map(|t| match t {
+                    Target::Workflow(name) => Some(name.as_str()),
+                    _ => None,
+                })
+                .collect();
+            targets.sort_unstable();
+            targets.dedup();
+            assert_eq!(targets.len(), set.workflows.len() - 1);
+        }
+    }
+
+    #[test]
+    fn validation() {
+        assert!(LayeredWorkflows::builder().build().is_err());
+        assert!(LayeredWorkflows::builder()
+            .layer(5..10)
+            .layer(2..4)
+            .build()
+            .is_err());
+        assert!(LayeredWorkflows::builder()
+            .layer(2..4)
+            .accepted_fraction(0.5..0.5)
+            .build()
+            .is_err());
+        assert!(LayeredWorkflows::builder()
+            .layer(2..4)
+            .variables(vec!['x', 'x'])
+            .build()
+            .is_err());
+    }
+}
@@ -0,0 +1,449 @@
+//! Sets of workflows that sort parts by comparing their named values, along
+//! with a generator for layered trees of workflows.
+use std::{collections::HashMap, fmt::Display, ops::Range};
+
+use thiserror::Error;
+
+use crate::generic::TokenError;
+
+mod layered;
+
+pub use layered::{LayeredWorkflows, LayeredWorkflowsBuilder, LayeredWorkflowsBuilderError};
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum WorkflowError {
+    #[error("There is no workflow named {0}.")]
+    UnknownWorkflow(String),
+
+    #[error("There is no variable named {0}.")]
+    UnknownVariable(char),
+
+    #[error("Workflow {0} can be reached from itself.")]
+    Cycle(String),
+
+    #[error("Expected {expected} values, but found {found}.")]
+    ValueCount { expected: usize, found: usize },
+
+    #[error("Failed to generate workflows after {0} attempts.")]
+    AttemptsExhausted(usize),
+
+    #[error(transparent)]
+    Token(#[from] TokenError),
+}
+
+/// Where a part is sent by a rule.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Target {
+    Accept,
+    Reject,
+    Workflow(String),
+}
+
+impl Display for Target {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Accept => "A".fmt(f),
+            Self::Reject => "R".fmt(f),
+            Self::Workflow(name) => name.fmt(f),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Comparison {
+    Less,
+    Greater,
+}
+
+impl Display for Comparison {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Less => "<".fmt(f),
+            Self::Greater => ">".fmt(f),
+        }
+    }
+}
+
+/// A comparison of the value of a `variable` against a fixed `value`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Condition {
+    pub variable: char,
+    pub comparison: Comparison,
+    pub value: u64,
+}
+
+impl Condition {
+    /// Split the `range` of values into the values that pass the condition
+    /// and the values that fail it, either of which may be empty.
+    pub fn split(&self, range: &Range<u64>) -> (Range<u64>, Range<u64>) {
+        let clamp = |v: u64| v.clamp(range.start, range.end);
+
+        match self.comparison {
+            Comparison::Less => {
+                let mid = clamp(self.value);
+                (range.start..mid, mid..range.end)
+            }
+            Comparison::Greater => {
+                let mid = clamp(self.value.saturating_add(1));
+                (mid..range.end, range.start..mid)
+            }
+        }
+    }
+}
+
+impl Display for Condition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}{}", self.variable, self.comparison, self.value)
+    }
+}
+
+/// A rule that sends a part to the `target` if it passes the `condition`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Rule {
+    pub condition: Condition,
+    pub target: Target,
+}
+
+impl Display for Rule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.condition, self.target)
+    }
+}
+
+/// A named list of rules, tried in order, with a `fallthrough` target for
+/// parts that pass none of them.
+///
+/// Workflows are written as `px{a<2006:qkq,m>2090:A,rfg}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Workflow {
+    pub name: String,
+    pub rules: Vec<Rule>,
+    pub fallthrough: Target,
+}
+
+impl Display for Workflow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{{", self.name)?;
+        for rule in self.rules.iter() {
+            write!(f, "{},", rule)?;
+        }
+        write!(f, "{}}}", self.fallthrough)
+    }
+}
+
+/// A set of workflows that sort parts, each of which has a value from the
+/// `values` range for every one of the `variables`, starting at the workflow
+/// named `start`.
+///
+/// The workflows are analysed by splitting the ranges of values at each rule,
+/// following every target that can still be reached, rather than by trying
+/// every combination of values.
+///
+/// # Examples
+/// ```
+/// use proliferatr::workflow::{Comparison, Condition, Rule, Target, Workflow, WorkflowSet};
+///
+/// let mut set = WorkflowSet::new(vec!['x', 'm'], 1..11, "in");
+/// set.workflows.push(Workflow {
+///     name: "in".into(),
+///     rules: vec![Rule {
+///         condition: Condition {
+///             variable: 'x',
+///             comparison: Comparison::Less,
+///             value: 4,
+///         },
+///         target: Target::Workflow("ab".into()),
+///     }],
+///     fallthrough: Target::Reject,
+/// });
+/// set.workflows.push(Workflow {
+///     name: "ab".into(),
+///     rules: vec![Rule {
+///         condition: Condition {
+///             variable: 'm',
+///             comparison: Comparison::Greater,
+///             value: 8,
+///         },
+///         target: Target::Accept,
+///     }],
+///     fallthrough: Target::Reject,
+/// });
+///
+/// // x can be 1, 2 or 3, and m can be 9 or 10
+/// assert_eq!(set.accepted_volume().unwrap(), 6);
+/// assert_eq!(set.total_volume(), 100);
+/// assert!(set.accepts(&[3, 9]).unwrap());
+/// assert!(!set.accepts(&[4, 9]).unwrap());
+/// assert!(set.unreachable().unwrap().is_empty());
+/// assert_eq!(set.to_string(), "in{x<4:ab,R}\nab{m>8:A,R}");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkflowSet {
+    pub variables: Vec<char>,
+    pub values: Range<u64>,
+    pub start: String,
+    pub workflows: Vec<Workflow>,
+}
+
+impl WorkflowSet {
+    pub fn new(variables: Vec<char>, values: Range<u64>, start: impl Into<String>) -> Self {
+        Self {
+            variables,
+            values,
+            start: start.into(),
+            workflows: Vec::new(),
+        }
+    }
+
+    /// Get the workflow with the given `name`.
+    pub fn get(&self, name: &str) -> Option<&Workflow> {
+        self.workflows.iter().find(|w| w.name == name)
+    }
+
+    /// The number of distinct parts, which is the number of possible values
+    /// raised to the power of the number of variables.
+    pub fn total_volume(&self) -> u128 {
+        volume(&vec![self.values.clone(); self.variables.len()])
+    }
+
+    /// The number of distinct parts that are accepted.
+    pub fn accepted_volume(&self) -> Result<u128, WorkflowError> {
+        let mut accepted = 0;
+        self.split(
+            vec![self.values.clone(); self.variables.len()],
+            |target, ranges| {
+                if *target == Target::Accept {
+                    accepted += volume(ranges);
+                }
+            },
+        )?;
+
+        Ok(accepted)
+    }
+
+    /// Check whether the part with the given `values`, one per variable, is
+    /// accepted.
+    pub fn accepts(&self, values: &[u64]) -> Result<bool, WorkflowError> {
+        if values.len() != self.variables.len() {
+            return Err(WorkflowError::ValueCount {
+                expected: self.variables.len(),
+                found: values.len(),
+            });
+        }
+
+        let mut accepted = false;
+        self.split(values.iter().map(|v| *v..*v + 1).collect(), |target, _| {
+            accepted |= *target == Target::Accept;
+        })?;
+
+        Ok(accepted)
+    }
+
+    /// The names of the workflows that no part can reach, in the order they
+    /// appear in the set.
+    pub fn unreachable(&self) -> Result<Vec<&str>, WorkflowError> {
+        let mut reached = vec![self.start.as_str()];
+        self.split(
+            vec![self.values.clone(); self.variables.len()],
+            |target, _| {
+                if let Target::Workflow(name) = target {
+                    reached.push(name);
+                }
+            },
+        )?;
+
+        Ok(self
+            .workflows
+            .iter()
+            .map(|w| w.name.as_str())
+            .filter(|name| !reached.contains(name))
+            .collect())
+    }
+
+    /// Split the `ranges` of values from the start workflow, calling `reach`
+    /// with every target that can be reached and the ranges of the values
+    /// that reach it.
+    fn split<'a, F>(&'a self, ranges: Vec<Range<u64>>, mut reach: F) -> Result<(), WorkflowError>
+    where
+        F: FnMut(&'a Target, &[Range<u64>]),
+    {
+        let lookup: HashMap<&str, &Workflow> = self
+            .workflows
+            .iter()
+            .map(|w| (w.name.as_str(), w))
+            .collect();
+        let start = lookup
+            .get(self.start.as_str())
+            .ok_or_else(|| WorkflowError::UnknownWorkflow(self.start.clone()))?;
+
+        self.visit(start, ranges, &lookup, &mut Vec::new(), &mut reach)
+    }
+
+    fn visit<'a, F>(
+        &'a self,
+        workflow: &'a Workflow,
+        mut ranges: Vec<Range<u64>>,
+        lookup: &HashMap<&str, &'a Workflow>,
+        path: &mut Vec<&'a str>,
+        reach: &mut F,
+    ) -> Result<(), WorkflowError>
+    where
+        F: FnMut(&'a Target, &[Range<u64>]),
+    {
+        if path.contains(&workflow.name.as_str()) {
+            return Err(WorkflowError::Cycle(workflow.name.clone()));
+        }
+        path.push(&workflow.name);
+
+        let mut remaining = true;
+        for rule in workflow.rules.iter() {
+            let idx = self
+                .variables
+                .iter()
+                .position(|v| *v == rule.condition.variable)
+                .ok_or(WorkflowError::UnknownVariable(rule.condition.variable))?;
+            let (pass, fail) = rule.condition.split(&ranges[idx]);
+
+            if !pass.is_empty() {
+                let mut next = ranges.clone();
+                next[idx] = pass;
+                self.follow(&rule.target, next, lookup, path, reach)?;
+            }
+
+            if fail.is_empty() {
+                remaining = false;
+                break;
+            }
+            ranges[idx] = fail;
+        }
+
+        if remaining {
+            self.follow(&workflow.fallthrough, ranges, lookup, path, reach)?;
+        }
+
+        path.pop();
+        Ok(())
+    }
+
+    fn follow<'a, F>(
+        &'a self,
+        target: &'a Target,
+        ranges: Vec<Range<u64>>,
+        lookup: &HashMap<&str, &'a Workflow>,
+        path: &mut Vec<&'a str>,
+        reach: &mut F,
+    ) -> Result<(), WorkflowError>
+    where
+        F: FnMut(&'a Target, &[Range<u64>]),
+    {
+        reach(target, &ranges);
+
+        if let Target::Workflow(name) = target {
+            let next = lookup
+                .get(name.as_str())
+                .ok_or_else(|| WorkflowError::UnknownWorkflow(name.clone()))?;
+            self.visit(next, ranges, lookup, path, reach)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for WorkflowSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (idx, workflow) in self.workflows.iter().enumerate() {
+            if idx > 0 {
+                writeln!(f)?;
+            }
+            workflow.fmt(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn volume(ranges: &[Range<u64>]) -> u128 {
+    ranges
+        .iter()
+        .map(|r| u128::from(r.end.saturating_sub(r.start)))
+        .product()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(variable: char, comparison: Comparison, value: u64, target: Target) -> Rule {
+        Rule {
+            condition: Condition {
+                variable,
+                comparison,
+                value,
+            },
+            target,
+        }
+    }
+
+    #[test]
+    fn splitting() {
+        let less = Condition {
+            variable: 'x',
+            comparison: Comparison::Less,
+            value: 5,
+        };
+        assert_eq!(less.split(&(1..11)), (1..5, 5..11));
+        assert_eq!(less.split(&(6..11)), (6..6, 6..11));
+
+        let greater = Condition {
+            comparison: Comparison::Greater,
+            ..less
+        };
+        assert_eq!(greater.split(&(1..11)), (6..11, 1..6));
+        assert_eq!(greater.split(&(1..4)), (4..4, 1..4));
+    }
+
+    #[test]
+    fn unreachable_and_cycles() {
+        let mut set = WorkflowSet::new(vec!['x'], 1..4001, "in");
+        set.workflows.push(Workflow {
+            name: "in".into(),
+            rules: vec![
+                rule('x', Comparison::Less, 3000, Target::Workflow("a".into())),
+                rule('x', Comparison::Less, 2000, Target::Workflow("b".into())),
+            ],
+            fallthrough: Target::Reject,
+        });
+        set.workflows.push(Workflow {
+            name: "a".into(),
+            rules: vec![],
+            fallthrough: Target::Accept,
+        });
+        set.workflows.push(Workflow {
+            name: "b".into(),
+            rules: vec![],
+            fallthrough: Target::Accept,
+        });
+
+        assert_eq!(set.unreachable().unwrap(), vec!["b"]);
+        assert_eq!(set.accepted_volume().unwrap(), 2999);
+        assert_eq!(
+            set.accepts(&[1, 2]),
+            Err(WorkflowError::ValueCount {
+                expected: 1,
+                found: 2
+            })
+        );
+
+        set.workflows[1].fallthrough = Target::Workflow("in".into());
+        assert_eq!(
+            set.accepted_volume(),
+            Err(WorkflowError::Cycle("in".into()))
+        );
+
+        set.workflows[1].fallthrough = Target::Workflow("c".into());
+        assert_eq!(
+            set.accepted_volume(),
+            Err(WorkflowError::UnknownWorkflow("c".into()))
+        );
+    }
+}